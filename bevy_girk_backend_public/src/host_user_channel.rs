@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::{Bytes, serde_as};
 
 //standard shortcuts
-
+use std::time::Duration;
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -20,9 +20,57 @@ pub enum HostToUserMsg
     LobbyLeave{ id: u64 },
     PendingLobbyAckRequest{ id: u64 },
     PendingLobbyAckFail{ id: u64 },
+    /// The user's fully-acked pending lobby is waiting in the host's launch queue for game hub capacity.
+    /// - `position` is the lobby's place in the queue, starting at 1 for the next lobby to launch.
+    /// - `estimated_wait` is a rough estimate based on recent launches (`None` if there is no estimate yet).
+    GameLaunchQueued{ id: u64, position: u32, estimated_wait: Option<Duration> },
     GameStart{ id: u64, token: ServerConnectToken, start: GameStartInfo },
     GameAborted{ id: u64 },
     GameOver{ id: u64, report: GameOverReport },
+    /// A chat message sent by a member of the user's lobby (or pending lobby).
+    LobbyChat{ id: u64, sender: u128, text: String },
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reason a [`UserToHostRequest`] was rejected by the host server.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HostRequestError
+{
+    /// The user must be idle (not in a lobby, queue, or game) to make this request.
+    NotIdle,
+    /// The user is not in the specified lobby.
+    NotInLobby,
+    /// The user is not in the specified game.
+    NotInGame,
+    /// The user is not in the matchmaking queue.
+    NotInQueue,
+    /// The specified lobby doesn't exist.
+    LobbyNotFound,
+    /// The lobby password is wrong.
+    WrongPassword,
+    /// The lobby has no room for a member of the requested type.
+    LobbyFull,
+    /// The user is banned from the lobby.
+    Banned,
+    /// The lobby is private and can only be joined with an invite code.
+    InviteOnly,
+    /// The invite code doesn't exist or expired.
+    InvalidInvite,
+    /// Only the lobby owner can make this request.
+    NotOwner,
+    /// The target user is not a valid lobby member for this request.
+    InvalidMember,
+    /// The lobby can't be launched (e.g. there are not enough players).
+    CannotLaunch,
+    /// The request was rejected by the server's lobby checker (e.g. invalid lobby data).
+    Rejected,
+    /// The requested feature is disabled on this server.
+    Disabled,
+    /// The request would exceed a server limit (e.g. the matchmaking queue is full).
+    LimitReached,
+    /// The user has sent too many requests recently.
+    RateLimited,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -36,6 +84,10 @@ pub enum HostToUserResponse
     LobbyJoin{ lobby: LobbyData },
     /// Response to [`UserToHostRequest::GetConnectToken`];
     ConnectToken{ id: u64, token: ServerConnectToken },
+    /// Response to [`UserToHostRequest::MakeLobbyInvite`].
+    LobbyInvite{ id: u64, code: String, expires_in: Duration },
+    /// Response to any [`UserToHostRequest`] that was rejected.
+    Error(HostRequestError),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -45,10 +97,17 @@ pub enum UserToHostMsg
 {
     NackPendingLobby{ id: u64 },
     AckPendingLobby{ id: u64 },
+    /// Send a chat message to all members of the user's lobby (or pending lobby).
+    ///
+    /// Messages that are too long, rate limited, or rejected by the server's chat filter will be dropped.
+    LobbyChat{ id: u64, text: String },
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Requests from users to the host server.
+///
+/// Requests that are rejected will receive a [`HostToUserResponse::Error`] with the reason.
 #[serde_as]
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum UserToHostRequest
@@ -64,6 +123,10 @@ pub enum UserToHostRequest
     },
     /// Join the specified lobby.
     JoinLobby{ id: u64, mcolor: LobbyMemberColor, pwd: String },
+    /// Join the lobby associated with an invite code.
+    ///
+    /// The lobby's password is not required. Private lobbies can only be joined this way.
+    JoinLobbyWithInvite{ code: String, mcolor: LobbyMemberColor },
     /// Leave the specified lobby.
     ///
     /// Will be acked on success.
@@ -78,14 +141,71 @@ pub enum UserToHostRequest
     ///
     /// Used to reconnect to ongoing games.
     GetConnectToken{ id: u64 },
+    /// Remove a member from the specified lobby.
+    ///
+    /// Only the lobby owner can send this. If `ban` is set, the member won't be able to rejoin the lobby.
+    ///
+    /// Will be acked on success.
+    KickMember{ lobby_id: u64, member_id: u128, ban: bool },
+    /// Make another member the owner of the specified lobby.
+    ///
+    /// Only the lobby owner can send this. All members will receive [`HostToUserMsg::LobbyState`] with the new owner.
+    ///
+    /// Will be acked on success.
+    TransferOwnership{ lobby_id: u64, new_owner_id: u128 },
+    /// Replace the custom data of the specified lobby.
+    ///
+    /// Only the lobby owner can send this. All members will receive [`HostToUserMsg::LobbyState`] with the new data.
+    ///
+    /// Will be acked on success.
+    UpdateLobbyData{
+        id: u64,
+        #[serde_as(as = "Bytes")]
+        data: Vec<u8>
+    },
+    /// Set whether the specified lobby is private.
+    ///
+    /// Private lobbies are hidden from lobby searches and can only be joined with an invite code.
+    ///
+    /// Only the lobby owner can send this. Will be acked on success.
+    SetLobbyPrivate{ id: u64, private: bool },
+    /// Make an invite code for the specified lobby.
+    ///
+    /// If `single_use` is set, the code will expire after one user joins the lobby with it.
+    ///
+    /// Only the lobby owner can send this.
+    MakeLobbyInvite{ id: u64, single_use: bool },
+    /// Revoke an invite code for the specified lobby.
+    ///
+    /// Only the lobby owner can send this. Will be acked on success.
+    RevokeLobbyInvite{ id: u64, code: String },
+    /// Enter the matchmaking queue.
+    ///
+    /// When the user is matched with other users, they will receive [`HostToUserMsg::LobbyState`] for their new
+    /// lobby followed by [`HostToUserMsg::PendingLobbyAckRequest`].
+    ///
+    /// Will be acked on success.
+    EnterQueue{
+        mcolor: LobbyMemberColor,
+        #[serde_as(as = "Bytes")]
+        data: Vec<u8>
+    },
+    /// Leave the matchmaking queue.
+    ///
+    /// Will be acked on success.
+    LeaveQueue,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
+#[serde_as]
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct HostUserConnectMsg
 {
-    pub connection_type: ConnectionType
+    pub connection_type: ConnectionType,
+    /// Authentication payload passed to the host server's user authenticator.
+    #[serde_as(as = "Bytes")]
+    pub auth: Vec<u8>,
 }
 
 impl HostUserConnectMsg
@@ -93,7 +213,13 @@ impl HostUserConnectMsg
     /// Makes a new connect message.
     pub fn new() -> Self
     {
-        Self{ connection_type: ConnectionType::inferred() }
+        Self{ connection_type: ConnectionType::inferred(), auth: Vec::default() }
+    }
+
+    /// Makes a new connect message with an authentication payload.
+    pub fn with_auth(auth: Vec<u8>) -> Self
+    {
+        Self{ connection_type: ConnectionType::inferred(), auth }
     }
 }
 
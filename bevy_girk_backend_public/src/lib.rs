@@ -6,6 +6,8 @@ mod games;
 mod host_user_channel;
 mod lobbies;
 mod lobby_checker;
+mod lobby_searcher;
+mod matchmaker;
 
 //API exports
 pub use game_launch_pack_source::*;
@@ -13,3 +15,5 @@ pub use games::*;
 pub use host_user_channel::*;
 pub use lobbies::*;
 pub use lobby_checker::*;
+pub use lobby_searcher::*;
+pub use matchmaker::*;
@@ -6,7 +6,41 @@ use serde::{Deserialize, Serialize};
 use serde_with::{Bytes, serde_as};
 
 //standard shortcuts
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Filter for [`LobbySearchRequest::Filtered`].
+#[serde_as]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LobbySearchFilter
+{
+    /// Only include lobbies with at least this many members.
+    pub min_members: Option<u16>,
+    /// Only include lobbies with at most this many members.
+    ///
+    /// Use this to find lobbies with open slots.
+    pub max_members: Option<u16>,
+    /// Only include lobbies that do (`true`) or do not (`false`) have a password.
+    pub has_password: Option<bool>,
+    /// Custom filter passed to the server's [`LobbySearcher`](crate::LobbySearcher).
+    ///
+    /// Ignored if the server doesn't have a lobby searcher.
+    #[serde_as(as = "Bytes")]
+    pub custom: Vec<u8>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Position of a lobby in the sorted list of lobbies that pass a [`LobbySearchRequest::Filtered`] filter.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LobbySearchCursor
+{
+    /// The lobby's sort key.
+    pub sort_key: i64,
+    /// The lobby's id.
+    pub id: u64,
+}
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -19,6 +53,13 @@ pub enum LobbySearchRequest
     PageNewer{ oldest_id: u64, num: u16 },
     /// Request the first `num` lobbies in the range [`youngest_id`, min] (ending at the oldest lobby).
     PageOlder{ youngest_id: u64, num: u16 },
+    /// Request the first `num` lobbies that pass `filter` and are sorted after the `after` cursor (or from the start
+    /// of the sorted list of passing lobbies if `None`).
+    ///
+    /// Lobbies are sorted by the server's [`LobbySearcher`](crate::LobbySearcher), or from newest to oldest if
+    /// the server doesn't have a lobby searcher. Use [`LobbySearchResult::next_cursor`] to request the next page;
+    /// pages won't skip or repeat lobbies when other lobbies are added or removed between requests.
+    Filtered{ filter: LobbySearchFilter, after: Option<LobbySearchCursor>, num: u16 },
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -36,9 +77,19 @@ pub struct LobbySearchResult
     ///
     /// If there are no lobbies in this response, then this value represents the number of lobbies in the server
     /// younger than the request's search position.
+    ///
+    /// For [`LobbySearchRequest::Filtered`], this is the number of passing lobbies sorted before this response.
     pub num_younger: usize,
     /// The total number of lobbies cached in the server.
+    ///
+    /// For [`LobbySearchRequest::Filtered`], this is the total number of passing lobbies.
     pub total: usize,
+    /// For [`LobbySearchRequest::Filtered`], the cursor of the last lobby in this response.
+    ///
+    /// Pass it as `after` in the next request to get the next page. This is `None` for other requests, or if there
+    /// are no lobbies in this response.
+    #[serde(default)]
+    pub next_cursor: Option<LobbySearchCursor>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -84,13 +135,22 @@ pub struct LobbyData
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Lobby
 {
     /// Lobby data.
     pub data: LobbyData,
     /// Lobby password.
-    password: String
+    password: String,
+    /// Users banned from this lobby.
+    #[serde(default)]
+    banned: HashSet<u128>,
+    /// Member ids in the order they joined the lobby.
+    #[serde(default)]
+    join_order: Vec<u128>,
+    /// Private lobbies are hidden from lobby searches and can only be joined with an invite.
+    #[serde(default)]
+    private: bool,
 }
 
 impl Lobby
@@ -101,6 +161,9 @@ impl Lobby
         Lobby{
             data: LobbyData { id: lobby_id, owner_id, serialized_custom_data, ..Default::default() },
             password,
+            banned: HashSet::default(),
+            join_order: Vec::default(),
+            private: false,
         }
     }
 
@@ -108,7 +171,9 @@ impl Lobby
     pub fn add_member(&mut self, new_member_id: u128, member: LobbyMemberData) -> bool
     {
         // add based on member type
-        self.data.members.insert(new_member_id, member).is_none()
+        let is_new = self.data.members.insert(new_member_id, member).is_none();
+        if is_new { self.join_order.push(new_member_id); }
+        is_new
     }
 
     /// Remove a member from the lobby.
@@ -116,9 +181,65 @@ impl Lobby
     pub fn remove_member(&mut self, member_id: u128) -> Option<LobbyMemberData>
     {
         if self.is_owner(member_id) { return None; }
+        self.join_order.retain(|id| *id != member_id);
         self.data.members.remove(&member_id)
     }
 
+    /// Set the lobby owner.
+    /// - Returns `false` if the new owner is not a member.
+    pub fn set_owner(&mut self, new_owner_id: u128) -> bool
+    {
+        if !self.has_member(new_owner_id) { return false; }
+        self.data.owner_id = new_owner_id;
+        true
+    }
+
+    /// Iterate over member ids from oldest to youngest member.
+    pub fn members_by_age(&self) -> impl Iterator<Item = u128> + '_
+    {
+        // members missing from the join order (e.g. added directly to the lobby data) are treated as youngest
+        let mut unordered: Vec<u128> = self.data.members
+            .keys()
+            .filter(|member_id| !self.join_order.contains(member_id))
+            .copied()
+            .collect();
+        unordered.sort_unstable();
+
+        self.join_order
+            .iter()
+            .copied()
+            .filter(|member_id| self.has_member(*member_id))
+            .chain(unordered)
+    }
+
+    /// Ban a user from the lobby.
+    /// - Does not remove the user if they are a member.
+    /// - Returns `false` if the user is the owner.
+    pub fn ban_user(&mut self, user_id: u128) -> bool
+    {
+        if self.is_owner(user_id) { return false; }
+        self.banned.insert(user_id);
+        true
+    }
+
+    /// Test if a user is banned from the lobby.
+    pub fn is_banned(&self, user_id: u128) -> bool
+    {
+        self.banned.contains(&user_id)
+    }
+
+    /// Set whether the lobby is private.
+    pub fn set_private(&mut self, private: bool)
+    {
+        self.private = private;
+    }
+
+    /// Test if the lobby is private.
+    pub fn is_private(&self) -> bool
+    {
+        self.private
+    }
+
     /// This lobby's id.
     pub fn id(&self) -> u64 { self.data.id }
 
@@ -1,5 +1,5 @@
 //local shortcuts
-use crate::{Lobby, LobbyMemberData};
+use crate::{HostRequestError, Lobby, LobbyMemberData};
 
 //third-party shortcuts
 
@@ -14,16 +14,39 @@ pub trait LobbyChecker: Debug + Send + Sync
     fn check_lobby(&self, lobby: &Lobby) -> bool;
 
     /// Check if a new lobby member may be added to a lobby.
+    /// - Returns the reason the member was rejected on failure (e.g. [`HostRequestError::WrongPassword`]).
     fn allow_new_member(
         &self,
         lobby       : &Lobby,
         member_id   : u128,
         member_data : LobbyMemberData,
         password    : &String,
-    ) -> bool;
+    ) -> Result<(), HostRequestError>;
 
     /// Check if a lobby is launchable.
     fn can_launch(&self, lobby: &Lobby) -> bool;
+
+    /// Select a new owner for a lobby when its owner leaves.
+    ///
+    /// Only used with [`OwnerSuccession::LobbyChecker`]. Returning `None` will disband the lobby.
+    fn select_new_owner(&self, _lobby: &Lobby) -> Option<u128> { None }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Policy for keeping a lobby alive when its owner leaves.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum OwnerSuccession
+{
+    /// Remove the lobby when its owner leaves.
+    #[default]
+    Disband,
+    /// Promote the member who has been in the lobby the longest.
+    ///
+    /// Members rejected as owner by [`LobbyChecker::check_lobby`] are skipped.
+    OldestMember,
+    /// Promote the member selected by [`LobbyChecker::select_new_owner`].
+    LobbyChecker,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,93 @@
+//local shortcuts
+use crate::LobbyData;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use serde::de::DeserializeOwned;
+
+//standard shortcuts
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Game-defined filter and sort order for [`LobbySearchRequest::Filtered`](crate::LobbySearchRequest::Filtered).
+pub trait LobbySearcher: Debug + Send + Sync
+{
+    /// Prepare the custom filter from a search request.
+    ///
+    /// This is called once per search request. Returns `None` if the filter is invalid, in which case no lobbies
+    /// will match.
+    fn make_filter(&self, custom_filter: &[u8]) -> Option<Box<dyn LobbyFilter + '_>>;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Custom filter prepared by a [`LobbySearcher`] for one search request.
+pub trait LobbyFilter
+{
+    /// Get a lobby's sort key, or `None` if the lobby doesn't match the filter.
+    ///
+    /// Lobbies are sorted by ascending sort key. Lobbies with the same key are sorted from newest to oldest.
+    fn sort_key(&self, lobby: &LobbyData) -> Option<i64>;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`LobbySearcher`] that deserializes lobby custom data to `D` and custom filters to `F`.
+///
+/// Lobbies with custom data that can't be deserialized never match. If a custom filter can't be deserialized then no
+/// lobbies will match.
+pub struct TypedLobbySearcher<D, F>
+{
+    predicate: fn(&D, &F) -> bool,
+    sort_key: fn(&D, &F) -> i64,
+    phantom: PhantomData<fn() -> (D, F)>,
+}
+
+impl<D, F> TypedLobbySearcher<D, F>
+{
+    pub fn new(predicate: fn(&D, &F) -> bool, sort_key: fn(&D, &F) -> i64) -> Self
+    {
+        Self{ predicate, sort_key, phantom: PhantomData }
+    }
+}
+
+impl<D, F> Debug for TypedLobbySearcher<D, F>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("TypedLobbySearcher").finish()
+    }
+}
+
+impl<D: DeserializeOwned + 'static, F: DeserializeOwned + 'static> LobbySearcher for TypedLobbySearcher<D, F>
+{
+    fn make_filter(&self, custom_filter: &[u8]) -> Option<Box<dyn LobbyFilter + '_>>
+    {
+        let filter = deser_msg::<F>(custom_filter)?;
+        Some(Box::new(TypedLobbyFilter{ searcher: self, filter }))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`LobbyFilter`] for [`TypedLobbySearcher`].
+struct TypedLobbyFilter<'a, D, F>
+{
+    searcher: &'a TypedLobbySearcher<D, F>,
+    filter: F,
+}
+
+impl<D: DeserializeOwned, F> LobbyFilter for TypedLobbyFilter<'_, D, F>
+{
+    fn sort_key(&self, lobby: &LobbyData) -> Option<i64>
+    {
+        let data = deser_msg::<D>(&lobby.serialized_custom_data)?;
+        if !(self.searcher.predicate)(&data, &self.filter) { return None; }
+
+        Some((self.searcher.sort_key)(&data, &self.filter))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
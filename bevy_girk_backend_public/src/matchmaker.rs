@@ -0,0 +1,55 @@
+//local shortcuts
+use crate::LobbyMemberData;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::fmt::Debug;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A user waiting in the matchmaking queue.
+#[derive(Debug, Clone)]
+pub struct QueuedUser
+{
+    /// The user's id.
+    pub user_id: u128,
+    /// The user's lobby member data (used if the user is placed in a lobby).
+    pub member_data: LobbyMemberData,
+    /// Custom queue data defined by the user (e.g. preferred game mode).
+    pub serialized_custom_data: Vec<u8>,
+    /// Amount of time the user has been waiting in the queue.
+    pub wait_time: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A group of queued users selected by a [`Matchmaker`].
+#[derive(Debug, Clone)]
+pub struct MatchmakerMatch
+{
+    /// The id of the user who will own the new lobby.
+    ///
+    /// Must be one of the matched members.
+    pub owner_id: u128,
+    /// The ids of matched users.
+    pub members: Vec<u128>,
+    /// Custom lobby data for the new lobby.
+    pub serialized_custom_data: Vec<u8>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub trait Matchmaker: Debug + Send + Sync
+{
+    /// Try to select a group of queued users to place in a new lobby.
+    ///
+    /// Users are ordered from longest to shortest wait time. This is called repeatedly until it returns `None`.
+    /// The new lobby must pass [`LobbyChecker::check_lobby`](crate::LobbyChecker::check_lobby) and
+    /// [`LobbyChecker::can_launch`](crate::LobbyChecker::can_launch), otherwise the match will be discarded and the
+    /// users will stay in the queue.
+    fn try_make_match(&self, queue: &[QueuedUser]) -> Option<MatchmakerMatch>;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -89,6 +89,7 @@ fn handle_game_instance_report(w: &mut World, report: GameInstanceReport) -> Opt
 
             None
         }
+        GameInstanceReport::Heartbeat(_) => None,
     }
 }
 
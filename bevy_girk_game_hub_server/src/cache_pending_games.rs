@@ -80,6 +80,20 @@ impl PendingGamesCache
         self.pending.len()
     }
 
+    /// iterate over the start requests of all pending games
+    pub fn requests(&self) -> impl Iterator<Item = &GameStartRequest> + '_
+    {
+        self.pending.values().map(|(req, _)| req)
+    }
+
+    /// iterate over pending games
+    /// - returns (game id, amount of time the game has been pending)
+    pub fn games(&self) -> impl Iterator<Item = (u64, Duration)> + '_
+    {
+        let elapsed = self.timer.elapsed();
+        self.pending.iter().map(move |(game_id, (_, birth_time))| (*game_id, elapsed.saturating_sub(*birth_time)))
+    }
+
     /// drain expired pending games
     /// - iterates over all pending games (may be inefficient)
     pub fn drain_expired(&mut self) -> impl IntoIterator<Item = GameStartRequest> + '_
@@ -12,11 +12,56 @@ use std::time::{Duration, Instant};
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Config for detecting unresponsive game instances (see [`RunningGamesCache::drain_unresponsive`]).
+#[derive(Debug, Copy, Clone)]
+pub struct GameInstanceWatchdogConfig
+{
+    /// amount of time a game instance may take to send its game start report
+    pub startup_timeout: Duration,
+    /// amount of time a started game instance may go without sending a heartbeat report
+    /// - Should be several times longer than the game app's heartbeat interval (see
+    ///   [`GameInstanceHeartbeatConfig`]).
+    pub heartbeat_timeout: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug)]
 pub struct RunningGamesCacheConfig
 {
     /// amount of time a game may remain in the cache before it expires
     pub expiry_duration: Duration,
+    /// watchdog config (optional: unresponsive game instances will only be removed when they expire if `None`)
+    pub watchdog: Option<GameInstanceWatchdogConfig>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reason a game instance was considered unresponsive.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum UnresponsiveGameReason
+{
+    /// The instance didn't send a game start report within the startup timeout.
+    NoGameStart,
+    /// The instance didn't send a heartbeat report within the heartbeat timeout.
+    MissedHeartbeats,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct RunningGame
+{
+    /// game instance
+    //todo: get rid of Option once extract_if is stabilized
+    instance: Option<GameInstance>,
+    /// game start request
+    start_request: GameStartRequest,
+    /// time the game was added to the cache
+    birth_time: Duration,
+    /// set when the game start report is received
+    started: bool,
+    /// time of the last game start or heartbeat report
+    last_heartbeat: Duration,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -34,9 +79,8 @@ pub struct RunningGamesCache
     instance_report_receiver : IoReceiver<GameInstanceReport>,
     /// timer
     timer: Instant,
-    /// [ game id : (game instance, game start request birth time) ]
-    //todo: get rid of Option once extract_if is stabilized
-    games: HashMap<u64, (Option<GameInstance>, GameStartRequest, Duration)>,
+    /// [ game id : running game ]
+    games: HashMap<u64, RunningGame>,
 }
 
 impl RunningGamesCache
@@ -75,7 +119,15 @@ impl RunningGamesCache
         let game_instance = self.game_launcher.launch(launch_pack, self.instance_report_sender.clone());
 
         // insert the game
-        if let Some(_) = self.games.insert(game_id, (Some(game_instance), start_request, self.timer.elapsed()))
+        let birth_time = self.timer.elapsed();
+        let running_game = RunningGame{
+                instance       : Some(game_instance),
+                start_request,
+                birth_time,
+                started        : false,
+                last_heartbeat : birth_time,
+            };
+        if let Some(_) = self.games.insert(game_id, running_game)
         { tracing::error!("game instance insertion error"); }
 
         Ok(())
@@ -85,14 +137,35 @@ impl RunningGamesCache
     /// - returns `None` if the game instance doesn't exist
     pub fn extract_instance(&mut self, game_id: u64) -> Option<GameInstance>
     {
-        self.games.remove(&game_id).map(|(instance, _, _)| instance).flatten()
+        self.games.remove(&game_id).and_then(|running_game| running_game.instance)
     }
 
     /// Tries to access the game start request for a game instance.
     /// - Returns `None` if the game instance doesn't exist.
     pub fn game_start_request(&self, game_id: u64) -> Option<&GameStartRequest>
     {
-        self.games.get(&game_id).map(|(_, game_start_request, _)| game_start_request)
+        self.games.get(&game_id).map(|running_game| &running_game.start_request)
+    }
+
+    /// Records that a game instance sent its game start report.
+    /// - Returns `false` if the game instance doesn't exist.
+    pub fn record_game_start(&mut self, game_id: u64) -> bool
+    {
+        let elapsed = self.timer.elapsed();
+        let Some(running_game) = self.games.get_mut(&game_id) else { return false; };
+        running_game.started = true;
+        running_game.last_heartbeat = elapsed;
+        true
+    }
+
+    /// Records that a game instance sent a heartbeat report.
+    /// - Returns `false` if the game instance doesn't exist.
+    pub fn record_heartbeat(&mut self, game_id: u64) -> bool
+    {
+        let elapsed = self.timer.elapsed();
+        let Some(running_game) = self.games.get_mut(&game_id) else { return false; };
+        running_game.last_heartbeat = elapsed;
+        true
     }
 
     /// Checks if the cache has a game with the given game id.
@@ -107,6 +180,22 @@ impl RunningGamesCache
         self.games.len()
     }
 
+    /// Iterates over the game start requests of all running games.
+    pub fn requests(&self) -> impl Iterator<Item = &GameStartRequest> + '_
+    {
+        self.games.values().map(|running_game| &running_game.start_request)
+    }
+
+    /// Iterates over running games.
+    /// - Returns (game id, game start time, game start request).
+    pub fn games(&self) -> impl Iterator<Item = (u64, Instant, &GameStartRequest)> + '_
+    {
+        self.games.iter().map(
+            |(game_id, running_game)|
+            (*game_id, self.timer + running_game.birth_time, &running_game.start_request)
+        )
+    }
+
     /// Takes the next available instance report.
     pub fn try_next_instance_report(&mut self) -> Option<GameInstanceReport>
     {
@@ -127,17 +216,61 @@ impl RunningGamesCache
         //todo: use .extract_if once stabilized
         let mut extracted = Vec::default();
         self.games.retain(
-            | game_id, (running_game, _, birth_time) |
+            | game_id, running_game |
             {
-                let Some(running) = running_game else { return false };
+                let Some(running) = &mut running_game.instance else { return false };
 
                 // retain: still running and not expired
-                if running.try_get().is_none() && (*birth_time >= min_birth_time)
+                if running.try_get().is_none() && (running_game.birth_time >= min_birth_time)
                 { return true; }
 
                 // remove: game has a result or is expired
                 tracing::trace!(game_id, "removing dead/expired running game");
-                extracted.push(running_game.take().unwrap());
+                extracted.push(running_game.instance.take().unwrap());
+                false
+            }
+        );
+        extracted
+    }
+
+    /// Drains running games that haven't sent a game start report or heartbeat report in time.
+    /// - Does nothing if there is no [`GameInstanceWatchdogConfig`].
+    /// - Iterates over all running games (may be inefficient).
+    /// - The caller is expected to abort and kill the drained game instances.
+    /// - Game instances that can't be killed (see [`GameInstance::is_killable`]) are not drained, since they would
+    ///   keep running untracked. They stay in the cache until they terminate or expire (see [`Self::drain_invalid`]).
+    pub fn drain_unresponsive(&mut self) -> impl IntoIterator<Item = (GameInstance, UnresponsiveGameReason)> + '_
+    {
+        let mut extracted = Vec::default();
+        let Some(watchdog) = self.config.watchdog else { return extracted; };
+        let elapsed = self.timer.elapsed();
+
+        //todo: use .extract_if once stabilized
+        self.games.retain(
+            | game_id, running_game |
+            {
+                let Some(instance) = &running_game.instance else { return false };
+
+                // retain: responsive
+                let reason = match running_game.started
+                {
+                    false if elapsed.saturating_sub(running_game.birth_time) > watchdog.startup_timeout =>
+                        UnresponsiveGameReason::NoGameStart,
+                    true if elapsed.saturating_sub(running_game.last_heartbeat) > watchdog.heartbeat_timeout =>
+                        UnresponsiveGameReason::MissedHeartbeats,
+                    _ => return true,
+                };
+
+                // retain: can't be killed
+                if !instance.is_killable()
+                {
+                    tracing::trace!(game_id, ?reason, "unresponsive running game can't be killed, waiting for expiry");
+                    return true;
+                }
+
+                // remove: unresponsive
+                tracing::trace!(game_id, ?reason, "removing unresponsive running game");
+                extracted.push((running_game.instance.take().unwrap(), reason));
                 false
             }
         );
@@ -147,7 +280,7 @@ impl RunningGamesCache
     /// Drains all running games.
     pub fn drain_all(&mut self) -> impl Iterator<Item = GameInstance> + '_
     {
-        self.games.drain().map(|(_, (game_instance, _, _))| game_instance).flatten()
+        self.games.drain().filter_map(|(_, running_game)| running_game.instance)
     }
 }
 
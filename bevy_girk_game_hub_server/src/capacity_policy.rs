@@ -0,0 +1,282 @@
+//local shortcuts
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Trait for limiting game hub capacity based on the state of the machine the hub runs on.
+pub trait CapacityPolicy: Debug + Send + Sync + 'static
+{
+    /// Get the hub's current capacity limit.
+    /// - `max_capacity` is the hub's configured max capacity (see
+    ///   [`GameHubCommand::SetMaxCapacity`](crate::GameHubCommand::SetMaxCapacity)).
+    /// - `load` is the total cost of pending and running games.
+    /// - The hub's advertised capacity is `min(max capacity, limit) - load`.
+    ///
+    /// Called every tick.
+    fn capacity_limit(&mut self, max_capacity: GameHubCapacity, load: usize) -> GameHubCapacity;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Snapshot of machine resource use.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct SystemLoad
+{
+    /// Recent CPU load per CPU (e.g. 1-minute load average / number of CPUs).
+    pub cpu_load: f32,
+    /// Memory available for new processes, in bytes.
+    pub available_memory: u64,
+    /// Total resident memory of the hub's child processes (e.g. game instance processes), in bytes.
+    pub children_memory: u64,
+    /// Number of child processes.
+    pub num_children: usize,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Trait for sampling machine resource use.
+pub trait SystemLoadSampler: Debug + Send + Sync + 'static
+{
+    /// Sample current resource use.
+    /// - Returns `None` if resource use could not be sampled.
+    fn sample(&mut self) -> Option<SystemLoad>;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`SystemLoadSampler`] that reads `/proc`.
+///
+/// Child processes are found by scanning `/proc/[pid]/status` for processes whose parent is the current process.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Default)]
+pub struct ProcLoadSampler;
+
+#[cfg(target_os = "linux")]
+impl ProcLoadSampler
+{
+    fn cpu_load() -> Option<f32>
+    {
+        let loadavg = std::fs::read_to_string("/proc/loadavg").ok()?;
+        let load: f32 = loadavg.split_whitespace().next()?.parse().ok()?;
+        let num_cpus = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+
+        Some(load / num_cpus as f32)
+    }
+
+    fn available_memory() -> Option<u64>
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        meminfo.lines().find_map(|line| Self::parse_kb_field(line, "MemAvailable:"))
+    }
+
+    /// Returns (total resident memory, number of children).
+    fn children_memory() -> (u64, usize)
+    {
+        let self_pid = std::process::id().to_string();
+        let Ok(entries) = std::fs::read_dir("/proc") else { return (0, 0); };
+
+        let mut memory = 0;
+        let mut num_children = 0;
+        for entry in entries.flatten()
+        {
+            // only look at process directories
+            let file_name = entry.file_name();
+            let Some(pid) = file_name.to_str() else { continue; };
+            if !pid.bytes().all(|b| b.is_ascii_digit()) { continue; }
+
+            // the process may have exited since the directory was listed
+            let Ok(status) = std::fs::read_to_string(entry.path().join("status")) else { continue; };
+            let is_child = status.lines()
+                .find_map(|line| line.strip_prefix("PPid:"))
+                .is_some_and(|ppid| ppid.trim() == self_pid);
+            if !is_child { continue; }
+
+            num_children += 1;
+            memory += status.lines().find_map(|line| Self::parse_kb_field(line, "VmRSS:")).unwrap_or(0);
+        }
+
+        (memory, num_children)
+    }
+
+    /// Parses a line like `MemAvailable:   1234 kB` into bytes.
+    fn parse_kb_field(line: &str, field: &str) -> Option<u64>
+    {
+        let kb: u64 = line.strip_prefix(field)?.trim().trim_end_matches("kB").trim().parse().ok()?;
+        Some(kb * 1024)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl SystemLoadSampler for ProcLoadSampler
+{
+    fn sample(&mut self) -> Option<SystemLoad>
+    {
+        let (children_memory, num_children) = Self::children_memory();
+
+        Some(SystemLoad{
+            cpu_load         : Self::cpu_load()?,
+            available_memory : Self::available_memory()?,
+            children_memory,
+            num_children,
+        })
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone)]
+pub struct LoadAwareCapacityConfig
+{
+    /// Amount of time to wait between resource samples.
+    pub sample_period: Duration,
+    /// The machine is saturated when CPU load per CPU reaches this value.
+    pub max_cpu_load: f32,
+    /// The machine stops being CPU-saturated when CPU load per CPU falls to this value.
+    /// - Should be lower than `max_cpu_load`.
+    pub resume_cpu_load: f32,
+    /// The machine is saturated when available memory falls below this number of bytes.
+    pub min_available_memory: u64,
+    /// The machine stops being memory-saturated when available memory rises to this number of bytes.
+    /// - Should be higher than `min_available_memory`.
+    /// - New games are only accepted if their estimated memory use (based on the memory use of running game processes)
+    ///   would leave this much memory available.
+    pub resume_available_memory: u64,
+}
+
+impl Default for LoadAwareCapacityConfig
+{
+    fn default() -> Self
+    {
+        Self{
+            sample_period           : Duration::from_secs(5),
+            max_cpu_load            : 0.9,
+            resume_cpu_load         : 0.75,
+            min_available_memory    : 512 * 1024 * 1024,
+            resume_available_memory : 1024 * 1024 * 1024,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`CapacityPolicy`] that lowers hub capacity when the machine is saturated.
+///
+/// - While saturated (CPU load or available memory past their limits), the hub accepts no new games.
+/// - Saturation uses separate enter/exit thresholds so capacity doesn't flap when load hovers around a limit.
+/// - While not saturated, capacity is limited by how many more games fit in available memory, estimated from the
+///   memory use of child processes. Hubs that run games in-process only use the saturation thresholds.
+/// - The memory limit is lowered as soon as a sample shows less headroom, but only raised by one game per sample.
+#[derive(Debug)]
+pub struct LoadAwareCapacityPolicy
+{
+    /// config
+    config: LoadAwareCapacityConfig,
+    /// resource sampler
+    sampler: Box<dyn SystemLoadSampler>,
+    /// tracked: time of the last sample
+    last_sample: Option<Instant>,
+    /// tracked: CPU saturation
+    cpu_saturated: bool,
+    /// tracked: memory saturation
+    memory_saturated: bool,
+    /// tracked: capacity limit computed from the last sample when not saturated
+    limit: Option<usize>,
+}
+
+impl LoadAwareCapacityPolicy
+{
+    /// Make a new policy with a custom resource sampler.
+    pub fn new(config: LoadAwareCapacityConfig, sampler: Box<dyn SystemLoadSampler>) -> Self
+    {
+        Self{
+            config,
+            sampler,
+            last_sample      : None,
+            cpu_saturated    : false,
+            memory_saturated : false,
+            limit            : None,
+        }
+    }
+
+    /// Make a new policy that samples resource use from `/proc`.
+    #[cfg(target_os = "linux")]
+    pub fn from_proc(config: LoadAwareCapacityConfig) -> Self
+    {
+        Self::new(config, Box::new(ProcLoadSampler))
+    }
+
+    /// Check if the machine is currently considered saturated.
+    pub fn is_saturated(&self) -> bool
+    {
+        self.cpu_saturated || self.memory_saturated
+    }
+
+    /// Update tracked state with a new resource sample.
+    fn update(&mut self, sample: SystemLoad, load: usize)
+    {
+        let config = &self.config;
+        let was_saturated = self.is_saturated();
+
+        // saturation (with hysteresis)
+        if sample.cpu_load >= config.max_cpu_load { self.cpu_saturated = true; }
+        else if sample.cpu_load <= config.resume_cpu_load { self.cpu_saturated = false; }
+
+        if sample.available_memory < config.min_available_memory { self.memory_saturated = true; }
+        else if sample.available_memory >= config.resume_available_memory { self.memory_saturated = false; }
+
+        if was_saturated != self.is_saturated()
+        {
+            tracing::info!(saturated = self.is_saturated(), ?sample, "game hub saturation changed");
+        }
+
+        // memory headroom for new games
+        if sample.num_children == 0 || load == 0 { self.limit = None; return; }
+
+        let memory_per_load = (sample.children_memory / load as u64).max(1);
+        let headroom = sample.available_memory.saturating_sub(config.resume_available_memory) / memory_per_load;
+        let target = load.saturating_add(headroom as usize);
+
+        // - the limit drops immediately but only rises by one game per sample, so estimates that fluctuate as games
+        //   start and finish don't make capacity flap
+        self.limit = Some(match self.limit
+        {
+            Some(prev_limit) => target.min(prev_limit.max(load).saturating_add(1)),
+            None             => target,
+        });
+    }
+}
+
+impl CapacityPolicy for LoadAwareCapacityPolicy
+{
+    fn capacity_limit(&mut self, max_capacity: GameHubCapacity, load: usize) -> GameHubCapacity
+    {
+        // sample resource use
+        let sample_due = self.last_sample.is_none_or(|last| last.elapsed() >= self.config.sample_period);
+        if sample_due
+        {
+            self.last_sample = Some(Instant::now());
+            match self.sampler.sample()
+            {
+                Some(sample) => self.update(sample, load),
+                None => tracing::warn!("failed sampling system load, keeping previous capacity limit"),
+            }
+        }
+
+        // no new games while saturated
+        let limit = match self.is_saturated()
+        {
+            true  => load,
+            false => self.limit.unwrap_or(usize::MAX),
+        };
+
+        GameHubCapacity(limit.min(max_capacity.0 as usize) as u16)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
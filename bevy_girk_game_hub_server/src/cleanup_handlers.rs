@@ -14,12 +14,14 @@ use bevy::prelude::*;
 /// Remove expired pending games and send 'abort game' to host.
 pub(crate) fn clean_pending_games(
     mut pending_games_cache : ResMut<PendingGamesCache>,
+    mut metrics             : Option<ResMut<GameHubServerMetrics>>,
     host_client             : Res<HostHubClient>,
 ){
     for game_start_request in pending_games_cache.drain_expired()
     {
         let game_id = game_start_request.game_id();
         tracing::warn!(game_id, "removed expired pending game");
+        if let Some(metrics) = &mut metrics { metrics.pending_game_expirations += 1; }
 
         // notify the host the game was aborted
         host_client.send(HubToHostMsg::Abort{ id: game_id });
@@ -32,12 +34,14 @@ pub(crate) fn clean_pending_games(
 /// - This should not run very often, it is inefficient.
 pub(crate) fn clean_running_games(
     mut running_games_cache : ResMut<RunningGamesCache>,
+    mut metrics             : Option<ResMut<GameHubServerMetrics>>,
     host_client             : Res<HostHubClient>,
 ){
     for mut game_instance in running_games_cache.drain_invalid()
     {
         let game_id: u64 = game_instance.id();
         tracing::warn!(game_id, "removed invalid running game");
+        if let Some(metrics) = &mut metrics { metrics.invalid_running_games += 1; }
 
         // if game instance completed successfully, we don't need to do anything else
         if let Some(true) = game_instance.try_get() { continue; }
@@ -52,3 +56,29 @@ pub(crate) fn clean_running_games(
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Abort and kill running games that stopped sending reports (see [`GameInstanceWatchdogConfig`]).
+/// - This should not run very often, it is inefficient.
+pub(crate) fn clean_unresponsive_games(
+    mut running_games_cache : ResMut<RunningGamesCache>,
+    mut metrics             : Option<ResMut<GameHubServerMetrics>>,
+    host_client             : Res<HostHubClient>,
+){
+    for (game_instance, reason) in running_games_cache.drain_unresponsive()
+    {
+        let game_id: u64 = game_instance.id();
+        tracing::warn!(game_id, ?reason, "removed unresponsive running game");
+        if let Some(metrics) = &mut metrics { metrics.unresponsive_running_games += 1; }
+
+        // command game instance to abort in case it recovers, then kill it
+        if let Err(_) = game_instance.send_command(GameInstanceCommand::Abort)
+        { tracing::error!(game_id, "failed sending abort game command to game instance"); }
+        if !game_instance.kill()
+        { tracing::warn!(game_id, "failed killing unresponsive game instance"); }
+
+        // notify the host the game was aborted
+        host_client.send(HubToHostMsg::Abort{ id: game_id });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,5 +1,6 @@
 //local shortcuts
 use crate::*;
+use bevy_girk_backend_public::*;
 use bevy_girk_host_server::*;
 
 //third-party shortcuts
@@ -8,6 +9,20 @@ use bevy::prelude::*;
 //standard shortcuts
 
 
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource, Debug)]
+pub(crate) struct HubGameCostEstimator(pub(crate) Box<dyn GameCostEstimator>);
+
+impl HubGameCostEstimator
+{
+    /// Get the capacity cost of a game.
+    pub(crate) fn cost(&self, request: &GameStartRequest) -> u16
+    {
+        self.0.game_cost(request).max(1)
+    }
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Tracks game hub capacity.
@@ -16,7 +31,11 @@ pub(crate) struct GameHubCapacityTracker
 {
     /// config: maximum capacity
     max_capacity: GameHubCapacity,
-    /// tracked: previously updated load
+    /// config: capacity policy
+    capacity_policy: Option<Box<dyn CapacityPolicy>>,
+    /// tracked: previously updated capacity limit from the capacity policy
+    prev_limit: GameHubCapacity,
+    /// tracked: previously updated load (total cost of pending and running games)
     prev_load: usize,
     /// tracked: previously updated capacity
     prev_capacity: GameHubCapacity,
@@ -25,9 +44,18 @@ pub(crate) struct GameHubCapacityTracker
 impl GameHubCapacityTracker
 {
     /// Make a new tracker.
-    pub(crate) fn new(initial_max_capacity: GameHubCapacity) -> GameHubCapacityTracker
+    pub(crate) fn new(
+        initial_max_capacity : GameHubCapacity,
+        capacity_policy      : Option<Box<dyn CapacityPolicy>>,
+    ) -> GameHubCapacityTracker
     {
-        GameHubCapacityTracker{ max_capacity: initial_max_capacity, prev_load: 0, prev_capacity: initial_max_capacity }
+        GameHubCapacityTracker{
+            max_capacity  : initial_max_capacity,
+            capacity_policy,
+            prev_limit    : initial_max_capacity,
+            prev_load     : 0,
+            prev_capacity : initial_max_capacity,
+        }
     }
 
     /// Change max capacity setting.
@@ -44,8 +72,7 @@ impl GameHubCapacityTracker
     }
 
     /// Get max allowed capacity.
-    //todo: use this when collecting game hub stats
-    pub(crate) fn _max_capacity(&self) -> GameHubCapacity
+    pub(crate) fn max_capacity(&self) -> GameHubCapacity
     {
         self.max_capacity
     }
@@ -53,7 +80,8 @@ impl GameHubCapacityTracker
     /// Get current capacity.
     pub(crate) fn capacity(&self) -> GameHubCapacity
     {
-        GameHubCapacity((self.max_capacity.0 as usize).saturating_sub(self.prev_load) as u16)
+        let max_capacity = self.max_capacity.0.min(self.prev_limit.0);
+        GameHubCapacity((max_capacity as usize).saturating_sub(self.prev_load) as u16)
     }
 
     /// Get previously updated game hub capacity.
@@ -65,6 +93,12 @@ impl GameHubCapacityTracker
     /// Update tracked capacity with the current game hub load.
     fn set_current_load(&mut self, load: usize)
     {
+        let max_capacity = self.max_capacity;
+        self.prev_limit = match &mut self.capacity_policy
+        {
+            Some(policy) => policy.capacity_limit(max_capacity, load),
+            None         => max_capacity,
+        };
         self.prev_load = load;
         self.prev_capacity = self.capacity();
     }
@@ -79,13 +113,18 @@ pub(crate) fn update_capacity(
     mut capacity_tracker : ResMut<GameHubCapacityTracker>,
     pending_games_cache  : Res<PendingGamesCache>,
     running_games_cache  : Res<RunningGamesCache>,
+    cost_estimator       : Res<HubGameCostEstimator>,
     host_client          : Res<HostHubClient>,
 ){
     // old capacity
     let prev_capacity = capacity_tracker.prev_capacity();
 
     // set the current load
-    capacity_tracker.set_current_load(pending_games_cache.num_pending() + running_games_cache.num_running());
+    let load = pending_games_cache.requests()
+        .chain(running_games_cache.requests())
+        .map(|request| cost_estimator.cost(request) as usize)
+        .sum();
+    capacity_tracker.set_current_load(load);
 
     // leave if capacity hasn't changed
     let current_capacity = capacity_tracker.capacity();
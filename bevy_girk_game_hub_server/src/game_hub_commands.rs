@@ -1,18 +1,40 @@
 //local shortcuts
+use crate::*;
 use bevy_girk_host_server::*;
+use bevy_girk_utils::*;
 
 //third-party shortcuts
 
 //standard shortcuts
 
 
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Progress of a game hub drain (see [`GameHubCommand::Drain`]).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameHubDrainStatus
+{
+    /// The hub is waiting for its remaining games to finish.
+    Draining{
+        pending_games: usize,
+        running_games: usize,
+    },
+    /// All games have finished. The hub will shut down at the end of the current tick.
+    Drained,
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 pub enum GameHubCommand
 {
     SetMaxCapacity(GameHubCapacity),
+    /// Stop accepting new games, wait for all pending and running games to finish, then shut down.
+    /// - The hub reports zero capacity to the host server while draining. `SetMaxCapacity` commands are ignored.
+    /// - Drain progress is sent to the sender every time the number of remaining games changes.
+    Drain(Sender<GameHubDrainStatus>),
     ShutDown,
-    //todo: get stats
+    /// Get a snapshot of the hub's current state.
+    GetStats(Sender<GameHubStats>),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,64 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use bevy::app::*;
+use bevy::prelude::*;
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks an in-progress game hub drain.
+#[derive(Resource)]
+pub(crate) struct GameHubDrain
+{
+    /// drain progress reporter
+    reporter: Sender<GameHubDrainStatus>,
+    /// tracked: previously reported number of (pending, running) games
+    prev_remaining: Option<(usize, usize)>,
+}
+
+impl GameHubDrain
+{
+    /// Make a new drain tracker.
+    pub(crate) fn new(reporter: Sender<GameHubDrainStatus>) -> GameHubDrain
+    {
+        GameHubDrain{ reporter, prev_remaining: None }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Report drain progress and shut down the game hub once all games have finished.
+pub(crate) fn update_drain(
+    drain               : Option<ResMut<GameHubDrain>>,
+    pending_games_cache : Res<PendingGamesCache>,
+    running_games_cache : Res<RunningGamesCache>,
+    mut app_exit        : EventWriter<AppExit>,
+){
+    let Some(mut drain) = drain else { return; };
+
+    // leave if remaining games haven't changed
+    let remaining = (pending_games_cache.num_pending(), running_games_cache.num_running());
+    if drain.prev_remaining == Some(remaining) { return; }
+    drain.prev_remaining = Some(remaining);
+
+    // report progress
+    let (pending_games, running_games) = remaining;
+    if pending_games + running_games > 0
+    {
+        tracing::info!(pending_games, running_games, "draining game hub...");
+        let _ = drain.reporter.send(GameHubDrainStatus::Draining{ pending_games, running_games });
+        return;
+    }
+
+    // close the app at the end of this tick
+    tracing::info!("game hub drained, shutting down...");
+    let _ = drain.reporter.send(GameHubDrainStatus::Drained);
+    app_exit.write(AppExit::Success);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,72 @@
+//local shortcuts
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A pending game in [`GameHubStats`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PendingGameStats
+{
+    /// The game's id.
+    pub id: u64,
+    /// Amount of time the game has been waiting for its launch pack.
+    pub age: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A running game in [`GameHubStats`].
+#[derive(Debug, Clone)]
+pub struct RunningGameStats
+{
+    /// The game's id.
+    pub id: u64,
+    /// When the game's instance was launched.
+    pub start_time: Instant,
+    /// The game's start request.
+    pub start_request: GameStartRequest,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Snapshot of game hub state (see [`GameHubCommand::GetStats`](crate::GameHubCommand::GetStats)).
+#[derive(Debug, Clone)]
+pub struct GameHubStats
+{
+    /// Configured max capacity.
+    pub max_capacity: GameHubCapacity,
+    /// Capacity currently advertised to the host server.
+    pub capacity: GameHubCapacity,
+    /// Games waiting for launch packs, sorted by id.
+    pub pending_games: Vec<PendingGameStats>,
+    /// Running games, sorted by id.
+    pub running_games: Vec<RunningGameStats>,
+    /// Number of games that failed to launch (no launch pack, or the game instance could not be made).
+    pub launch_failures: u64,
+    /// Number of game instances that aborted.
+    pub aborts: u64,
+    /// Number of game instances that reported game over.
+    pub game_overs: u64,
+    /// Indicates if the hub is currently connected to the host server.
+    pub host_connected: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Counts game lifecycle events for [`GameHubStats`].
+#[derive(Resource, Debug, Default)]
+pub(crate) struct GameHubStatsCounters
+{
+    pub(crate) launch_failures: u64,
+    pub(crate) aborts: u64,
+    pub(crate) game_overs: u64,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
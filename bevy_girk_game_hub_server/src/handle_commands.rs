@@ -17,6 +17,13 @@ use bevy_girk_utils::*;
 
 fn command_set_max_capacity(In(new_max_capacity): In<GameHubCapacity>, world: &mut World)
 {
+    // ignore capacity changes while draining
+    if world.contains_resource::<GameHubDrain>() && new_max_capacity != GameHubCapacity(0u16)
+    {
+        tracing::warn!(?new_max_capacity, "ignoring max capacity change while draining");
+        return;
+    }
+
     // set max capacity
     world.resource_mut::<GameHubCapacityTracker>().set_max_capacity(new_max_capacity);
 
@@ -29,6 +36,21 @@ fn command_set_max_capacity(In(new_max_capacity): In<GameHubCapacity>, world: &m
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
+fn command_drain(In(reporter): In<Sender<GameHubDrainStatus>>, world: &mut World)
+{
+    tracing::info!("draining game hub...");
+
+    // stop accepting new games
+    world.syscall(GameHubCapacity(0u16), command_set_max_capacity);
+
+    // track drain progress
+    // - Replaces the previous drain tracker if the hub is already draining.
+    world.insert_resource(GameHubDrain::new(reporter));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
 fn command_shut_down(
     mut pending_games_cache : ResMut<PendingGamesCache>,
     mut running_games_cache : ResMut<RunningGamesCache>,
@@ -71,6 +93,44 @@ fn command_shut_down(
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
+fn command_get_stats(
+    In(response)        : In<Sender<GameHubStats>>,
+    pending_games_cache : Res<PendingGamesCache>,
+    running_games_cache : Res<RunningGamesCache>,
+    capacity_tracker    : Res<GameHubCapacityTracker>,
+    counters            : Res<GameHubStatsCounters>,
+    host_client         : Res<HostHubClient>,
+){
+    let mut pending_games: Vec<PendingGameStats> = pending_games_cache
+        .games()
+        .map(|(id, age)| PendingGameStats{ id, age })
+        .collect();
+    pending_games.sort_unstable_by_key(|game| game.id);
+
+    let mut running_games: Vec<RunningGameStats> = running_games_cache
+        .games()
+        .map(|(id, start_time, start_request)| RunningGameStats{ id, start_time, start_request: start_request.clone() })
+        .collect();
+    running_games.sort_unstable_by_key(|game| game.id);
+
+    let stats = GameHubStats{
+            max_capacity    : capacity_tracker.max_capacity(),
+            capacity        : capacity_tracker.capacity(),
+            pending_games,
+            running_games,
+            launch_failures : counters.launch_failures,
+            aborts          : counters.aborts,
+            game_overs      : counters.game_overs,
+            host_connected  : host_client.is_connected(),
+        };
+
+    if let Err(_) = response.send(stats)
+    { tracing::warn!("failed sending game hub stats"); }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(crate) fn handle_commands(world: &mut World)
 {
     while let Some(command) = world.resource::<Receiver<GameHubCommand>>().try_recv()
@@ -78,6 +138,8 @@ pub(crate) fn handle_commands(world: &mut World)
         match command
         {
             GameHubCommand::SetMaxCapacity(capacity) => world.syscall(capacity, command_set_max_capacity),
+            GameHubCommand::Drain(reporter)          => world.syscall(reporter, command_drain),
+            GameHubCommand::GetStats(response)       => world.syscall(response, command_get_stats),
             GameHubCommand::ShutDown                 =>
             {
                 world.syscall(GameHubCapacity(0u16), command_set_max_capacity);
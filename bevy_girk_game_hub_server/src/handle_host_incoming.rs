@@ -30,6 +30,7 @@ fn host_connected(world: &mut World)
 fn host_start_game(
     In(game_start_request)      : In<GameStartRequest>,
     capacity_tracker            : Res<GameHubCapacityTracker>,
+    cost_estimator              : Res<HubGameCostEstimator>,
     mut pending_games_cache     : ResMut<PendingGamesCache>,
     running_games_cache         : Res<RunningGamesCache>,
     mut game_launch_pack_source : ResMut<GameLaunchPackSource>,
@@ -43,8 +44,8 @@ fn host_start_game(
     if running_games_cache.has_game(game_id)
     { tracing::warn!(game_id, "received game start request but game is already running"); return; }
 
-    // send 'abort game' if no capacity
-    if capacity_tracker.capacity() == GameHubCapacity(0u16)
+    // send 'abort game' if not enough capacity
+    if capacity_tracker.capacity().0 < cost_estimator.cost(&game_start_request)
     {
         host_client.send(HubToHostMsg::Abort{ id: game_id });
         return;
@@ -16,7 +16,7 @@ use bevy_cobweb::prelude::*;
 
 fn instance_report_game_start(
     In((game_id, game_start_report)) : In<(u64, GameStartReport)>,
-    running_games_cache              : Res<RunningGamesCache>,
+    mut running_games_cache          : ResMut<RunningGamesCache>,
     host_client                      : Res<HostHubClient>,
 ){
     // get game start request for this game
@@ -24,13 +24,15 @@ fn instance_report_game_start(
     //   finished (unlikely and unwanted, but technically not an error)
     let Some(game_start_request) = running_games_cache.game_start_request(game_id)
     else { tracing::warn!(game_id, "dropping game start report for game not present in running games cache"); return; };
+    let game_start_request = game_start_request.clone();
+    running_games_cache.record_game_start(game_id);
 
     // forward game start report to host server
     // - we include the game start request so the server can check for consistency with its local records
     host_client.send(
             HubToHostMsg::GameStart{
                     id      : game_id,
-                    request : game_start_request.clone(),
+                    request : game_start_request,
                     report  : game_start_report 
                 }
         );
@@ -45,8 +47,11 @@ fn instance_report_game_start(
 fn instance_report_game_over(
     In((game_id, game_over_report)) : In<(u64, GameOverReport)>,
     mut running_games_cache         : ResMut<RunningGamesCache>,
+    mut counters                    : ResMut<GameHubStatsCounters>,
     host_client                     : Res<HostHubClient>,
 ){
+    counters.game_overs += 1;
+
     // forward game over report to host server
     host_client.send(HubToHostMsg::GameOver{ id: game_id, report: game_over_report });
 
@@ -59,10 +64,15 @@ fn instance_report_game_over(
 //-------------------------------------------------------------------------------------------------------------------
 
 fn instance_report_game_aborted(
-    In((game_id, reason))             : In<(u64, String)>,
+    In((game_id, reason))   : In<(u64, String)>,
     mut running_games_cache : ResMut<RunningGamesCache>,
+    metrics                 : Option<ResMut<GameHubServerMetrics>>,
+    mut counters            : ResMut<GameHubStatsCounters>,
     host_client             : Res<HostHubClient>,
 ){
+    if let Some(mut metrics) = metrics { metrics.instance_aborts += 1; }
+    counters.aborts += 1;
+
     // try to remove instance
     // - note that the instance may have already been removed for one reason or another
     if let None = running_games_cache.extract_instance(game_id)
@@ -79,6 +89,15 @@ fn instance_report_game_aborted(
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
+fn instance_report_heartbeat(In(game_id): In<u64>, mut running_games_cache: ResMut<RunningGamesCache>)
+{
+    if !running_games_cache.record_heartbeat(game_id)
+    { tracing::trace!(game_id, "dropping heartbeat for game not present in running games cache"); }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(crate) fn handle_instance_reports(world: &mut World)
 {
     while let Some(instance_report) = world.resource_mut::<RunningGamesCache>().try_next_instance_report()
@@ -88,6 +107,7 @@ pub(crate) fn handle_instance_reports(world: &mut World)
             GameInstanceReport::GameStart(id, report) => world.syscall((id, report), instance_report_game_start),
             GameInstanceReport::GameOver(id, report)  => world.syscall((id, report), instance_report_game_over),
             GameInstanceReport::GameAborted(id, reason)       => world.syscall((id, reason), instance_report_game_aborted),
+            GameInstanceReport::Heartbeat(id)         => world.syscall(id, instance_report_heartbeat),
         }
     }
 }
@@ -18,6 +18,7 @@ fn handle_report_pack(
     In(launch_pack)         : In<GameLaunchPack>,
     mut pending_games_cache : ResMut<PendingGamesCache>,
     mut running_games_cache : ResMut<RunningGamesCache>,
+    mut counters            : ResMut<GameHubStatsCounters>,
 ){
     let game_id = launch_pack.game_id;
 
@@ -28,7 +29,7 @@ fn handle_report_pack(
 
     // launch game
     if let Err(_) = running_games_cache.make_instance(game_start_request, launch_pack)
-    { tracing::warn!(game_id, "failed to make game instance"); }
+    { tracing::warn!(game_id, "failed to make game instance"); counters.launch_failures += 1; }
     else
     { tracing::info!(game_id, "launched game instance"); }
 }
@@ -39,9 +40,11 @@ fn handle_report_pack(
 fn handle_report_failure(
     In(game_id)             : In<u64>,
     mut pending_games_cache : ResMut<PendingGamesCache>,
+    mut counters            : ResMut<GameHubStatsCounters>,
     host_client             : Res<HostHubClient>,
 ){
     tracing::warn!(game_id, "failed to acquire launch pack for a game");
+    counters.launch_failures += 1;
 
     // discard pending game
     if let None = pending_games_cache.extract_game(game_id)
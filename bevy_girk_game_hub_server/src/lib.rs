@@ -2,24 +2,32 @@
 
 //module tree
 mod cache_pending_games;
+mod capacity_policy;
 mod cache_running_games;
 mod cleanup_handlers;
 mod game_hub_capacity_tracker;
 mod game_hub_commands;
+mod game_hub_drain;
+mod game_hub_stats;
 mod handle_commands;
 mod handle_host_incoming;
 mod handle_instance_reports;
 mod handle_launch_pack_reports;
+mod metrics;
 mod server_setup;
 
 //API exports
 pub use crate::cache_pending_games::*;
 pub use crate::cache_running_games::*;
+pub use crate::capacity_policy::*;
 pub(crate) use crate::cleanup_handlers::*;
 pub(crate) use crate::game_hub_capacity_tracker::*;
 pub use crate::game_hub_commands::*;
+pub(crate) use crate::game_hub_drain::*;
+pub use crate::game_hub_stats::*;
 pub(crate) use crate::handle_commands::*;
 pub(crate) use crate::handle_host_incoming::*;
 pub(crate) use crate::handle_instance_reports::*;
 pub(crate) use crate::handle_launch_pack_reports::*;
+pub use crate::metrics::*;
 pub use crate::server_setup::*;
@@ -0,0 +1,105 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Exports game hub server metrics.
+///
+/// Cache sizes are sampled periodically, and lifecycle events are counted as they happen. Metrics are served in
+/// Prometheus text format on a local HTTP listener (see [`MetricsConfig`]).
+#[derive(Resource, Debug)]
+pub struct GameHubServerMetrics
+{
+    /// metrics exporter
+    exporter: MetricsExporter,
+
+    /// number of pending games that expired before their instances launched
+    pub(crate) pending_game_expirations: u64,
+    /// number of running games removed because they expired or stopped running
+    pub(crate) invalid_running_games: u64,
+    /// number of running games removed because they stopped sending reports
+    pub(crate) unresponsive_running_games: u64,
+    /// number of game instances that aborted
+    pub(crate) instance_aborts: u64,
+}
+
+impl GameHubServerMetrics
+{
+    /// Make new metrics.
+    pub fn new(exporter: MetricsExporter) -> Self
+    {
+        Self{
+            exporter,
+            pending_game_expirations   : 0,
+            invalid_running_games      : 0,
+            unresponsive_running_games : 0,
+            instance_aborts            : 0,
+        }
+    }
+
+    /// Get the address metrics are served on.
+    pub fn local_addr(&self) -> std::net::SocketAddr
+    {
+        self.exporter.local_addr()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn sample_game_hub_server_metrics(
+    metrics             : Res<GameHubServerMetrics>,
+    pending_games_cache : Res<PendingGamesCache>,
+    running_games_cache : Res<RunningGamesCache>,
+    capacity_tracker    : Res<GameHubCapacityTracker>,
+){
+    let mut writer = PrometheusWriter::default();
+
+    // caches
+    writer.gauge(
+            "girk_hub_pending_games",
+            "Number of games waiting for launch packs.",
+            pending_games_cache.num_pending() as f64
+        );
+    writer.gauge(
+            "girk_hub_running_games",
+            "Number of running game instances.",
+            running_games_cache.num_running() as f64
+        );
+
+    // capacity
+    writer.gauge("girk_hub_capacity", "Current game hub capacity.", capacity_tracker.capacity().0 as f64);
+    writer.gauge("girk_hub_max_capacity", "Max game hub capacity.", capacity_tracker.max_capacity().0 as f64);
+
+    // lifecycle events
+    writer.counter(
+            "girk_hub_pending_game_expirations_total",
+            "Number of pending games that expired.",
+            metrics.pending_game_expirations
+        );
+    writer.counter(
+            "girk_hub_invalid_running_games_total",
+            "Number of running games removed because they expired or stopped running.",
+            metrics.invalid_running_games
+        );
+    writer.counter(
+            "girk_hub_unresponsive_running_games_total",
+            "Number of running games removed because they stopped sending reports.",
+            metrics.unresponsive_running_games
+        );
+    writer.counter(
+            "girk_hub_instance_aborts_total",
+            "Number of game instances that aborted.",
+            metrics.instance_aborts
+        );
+
+    metrics.exporter.publish(writer.finish());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -4,6 +4,8 @@ use bevy_girk_backend_public::*;
 use bevy_girk_game_instance::*;
 use bevy_girk_host_server::*;
 use bevy_girk_utils::increment_tick_counter;
+use bevy_girk_utils::MetricsConfig;
+use bevy_girk_utils::MetricsExporter;
 use bevy_girk_utils::on_tick_counter;
 use bevy_girk_utils::tps_to_duration;
 
@@ -38,11 +40,13 @@ fn init_resources(
     running_games_cache_config : RunningGamesCacheConfig,
     game_launcher              : GameInstanceLauncher,
     initial_max_capacity       : u16,
+    capacity_policy            : Option<Box<dyn CapacityPolicy>>,
 ){
     app.insert_resource(TickCounter::default());
+    app.insert_resource(GameHubStatsCounters::default());
     app.insert_resource(PendingGamesCache::new(pending_games_cache_config));
     app.insert_resource(RunningGamesCache::new(running_games_cache_config, game_launcher));
-    app.insert_resource(GameHubCapacityTracker::new(GameHubCapacity(initial_max_capacity)));
+    app.insert_resource(GameHubCapacityTracker::new(GameHubCapacity(initial_max_capacity), capacity_policy));
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -65,6 +69,7 @@ pub struct GameHubServerConfig
     /// initial max capacity (max number of game instances that can exist simultaneously) (may be updated via commands)
     pub initial_max_capacity: u16,
     /// number of ticks to wait between each running game cache purge
+    /// - Unresponsive games are also detected on this period (see [`GameInstanceWatchdogConfig`]).
     pub running_game_purge_period_ticks: u64,
 }
 
@@ -80,6 +85,16 @@ pub struct GameHubServerStartupPack
     pub pending_games_cache_config: PendingGamesCacheConfig,
     /// config: running games cache
     pub running_games_cache_config: RunningGamesCacheConfig,
+
+    /// config: metrics (optional: metrics will not be exported if `None`)
+    pub metrics_config: Option<MetricsConfig>,
+
+    /// game cost estimator (optional: [`UnitGameCost`] will be used if `None`)
+    /// - Should match the estimator used by the host server.
+    pub game_cost_estimator: Option<Box<dyn GameCostEstimator>>,
+    /// capacity policy (optional: capacity will only be limited by max capacity if `None`)
+    /// - See [`LoadAwareCapacityPolicy`].
+    pub capacity_policy: Option<Box<dyn CapacityPolicy>>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -110,10 +125,22 @@ pub fn make_game_hub_server(
         startup_pack.running_games_cache_config,
         game_launcher,
         game_hub_server_config.initial_max_capacity,
+        startup_pack.capacity_policy,
     );
     app.insert_resource(command_receiver);
     app.insert_resource(host_hub_client);
     app.insert_resource(game_launch_pack_source);
+    app.insert_resource(HubGameCostEstimator(
+        startup_pack.game_cost_estimator.unwrap_or_else(|| Box::new(UnitGameCost))
+    ));
+    if let Some(metrics_config) = startup_pack.metrics_config
+    {
+        match MetricsExporter::new(metrics_config.listen_addr)
+        {
+            Ok(exporter) => { app.insert_resource(GameHubServerMetrics::new(exporter)); }
+            Err(err) => tracing::error!(?err, "failed making metrics exporter, metrics are disabled"),
+        }
+    }
 
     // add server systems
     app.add_systems(Main,
@@ -127,11 +154,26 @@ pub fn make_game_hub_server(
             clean_running_games.run_if(
                     on_tick_counter(game_hub_server_config.running_game_purge_period_ticks)
                 ),
+            clean_unresponsive_games.run_if(
+                    on_tick_counter(game_hub_server_config.running_game_purge_period_ticks)
+                ),
             update_capacity,
+            update_drain,             //after 'update capacity' so the host sees zero capacity before the hub exits
             handle_shutdown.run_if(on_event::<AppExit>),
         ).chain()
     );
 
+    // add metrics systems
+    if let Some(metrics_config) = startup_pack.metrics_config
+    {
+        app.add_systems(Main,
+            sample_game_hub_server_metrics
+                .run_if(resource_exists::<GameHubServerMetrics>)
+                .run_if(on_tick_counter(metrics_config.sample_period_ticks))
+                .after(update_capacity)
+        );
+    }
+
     app
 }
 
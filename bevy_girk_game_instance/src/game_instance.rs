@@ -23,6 +23,8 @@ pub struct GameInstance
     _command_receiver: IoReceiver<GameInstanceCommand>,
     /// join handle for the game instance (used to detect status of the instance)
     instance_handle: enfync::PendingResult<bool>,
+    /// kill signal for the game instance (optional: not all instances can be killed)
+    kill_signal: Option<IoSender<()>>,
     /// cached result
     result: Option<bool>,
 }
@@ -39,7 +41,14 @@ impl GameInstance
         instance_handle   : enfync::PendingResult<bool>,
     ) -> GameInstance
     {
-        GameInstance{ game_id, command_sender, _command_receiver, instance_handle, result: None }
+        GameInstance{ game_id, command_sender, _command_receiver, instance_handle, kill_signal: None, result: None }
+    }
+
+    /// Set a signal for forcefully terminating the game instance (see [`Self::kill`]).
+    pub fn with_kill_signal(mut self, kill_signal: IoSender<()>) -> Self
+    {
+        self.kill_signal = Some(kill_signal);
+        self
     }
 
     /// Send a command to the game instance.
@@ -49,6 +58,21 @@ impl GameInstance
         self.command_sender.send(command).map_err(|_| ())
     }
 
+    /// Forcefully terminate the game instance.
+    /// - Use this when the instance is unresponsive; otherwise prefer sending [`GameInstanceCommand::Abort`].
+    /// - Returns `false` if the instance can't be killed (e.g. it runs in a thread of the current process).
+    pub fn kill(&self) -> bool
+    {
+        let Some(kill_signal) = &self.kill_signal else { return false; };
+        kill_signal.send(()).is_ok()
+    }
+
+    /// Check if the instance can be forcefully terminated with [`Self::kill`].
+    pub fn is_killable(&self) -> bool
+    {
+        self.kill_signal.is_some()
+    }
+
     /// Get the game's id.
     pub fn id(&self) -> u64
     {
@@ -4,6 +4,7 @@ use bevy_girk_utils::*;
 
 //third-party shortcuts
 use clap::Parser;
+use tokio::io::AsyncWriteExt;
 
 //standard shortcuts
 use std::fmt::Debug;
@@ -14,13 +15,28 @@ use std::process::Stdio;
 #[derive(Parser, Debug)]
 pub struct GameInstanceCli
 {
+    /// The game's launch pack.
+    /// - If not provided, the launch pack will be read from `stdin` as the first message (see
+    ///   [`GameInstanceLauncherProcessPool`]).
     #[arg(short = 'G', value_parser = parse_json::<GameLaunchPack>)]
-    pub launch_pack: GameLaunchPack,
+    pub launch_pack: Option<GameLaunchPack>,
+    /// The wire format for messages passed over `stdin`/`stdout`.
+    #[arg(long, default_value = "bincode", value_parser = ChildProcessProtocol::parse)]
+    pub protocol: ChildProcessProtocol,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Launch a game instance in a new process on the current machine.
+///
+/// Each process's `stderr` and any `stdout` lines that aren't game instance reports are captured in a
+/// [`ChildProcessLog`] named `game_{game id}`. If a process dies unexpectedly, the tail of its log is attached to the
+/// [`GameInstanceReport::GameAborted`] reason.
+///
+/// The launch pack and all commands/reports are passed over the process's `stdin`/`stdout` using the launcher's
+/// [`ChildProcessProtocol`].
+///
+/// Game instance processes can be killed with [`GameInstance::kill`].
 #[derive(Debug)]
 pub struct GameInstanceLauncherProcess
 {
@@ -28,13 +44,36 @@ pub struct GameInstanceLauncherProcess
     path: String,
     /// Spawner for internal async tasks.
     spawner: enfync::builtin::native::TokioHandle,
+    /// Config for capturing process output.
+    log_config: ChildProcessLogConfig,
+    /// Wire format for `stdin`/`stdout` messages.
+    protocol: ChildProcessProtocol,
 }
 
 impl GameInstanceLauncherProcess
 {
     pub fn new(path: String, spawner: enfync::builtin::native::TokioHandle) -> Self
     {
-        Self{ path, spawner }
+        Self{
+            path,
+            spawner,
+            log_config : ChildProcessLogConfig::default(),
+            protocol   : ChildProcessProtocol::default(),
+        }
+    }
+
+    /// Set the config for capturing process output.
+    pub fn with_log_config(mut self, log_config: ChildProcessLogConfig) -> Self
+    {
+        self.log_config = log_config;
+        self
+    }
+
+    /// Set the wire format for `stdin`/`stdout` messages.
+    pub fn with_protocol(mut self, protocol: ChildProcessProtocol) -> Self
+    {
+        self.protocol = protocol;
+        self
     }
 }
 
@@ -49,77 +88,174 @@ impl GameInstanceLauncherImpl for GameInstanceLauncherProcess
         // prepare command channel
         let (command_sender, command_receiver) = new_io_channel::<GameInstanceCommand>();
         let command_receiver_clone = command_receiver.clone();
+        let (kill_sender, kill_receiver) = new_io_channel::<()>();
 
         // launch game process
         let game_id = launch_pack.game_id;
-        let Ok(launch_pack_ser) = serde_json::to_string(&launch_pack)
-        else
-        {
-            tracing::warn!(game_id, "failed serializing game launch pack for game instance process");
-            return GameInstance::new(game_id, command_sender, command_receiver, enfync::PendingResult::make_ready(false));
-        };
-
+        let protocol = self.protocol;
         let spawner = self.spawner.clone();
-        let (_process_handle, stdout_handle) = self.spawner.0.block_on(
+        let log = ChildProcessLog::new(self.log_config.clone(), &format!("game_{game_id}"));
+        let stdout_handle = self.spawner.0.block_on(
             async move {
-                let Ok(child_process) = tokio::process::Command::new(&self.path)
+                let Ok(mut child_process) = tokio::process::Command::new(&self.path)
                     .stdin(Stdio::piped())
                     .stdout(Stdio::piped())
-                    .args(["-G", &launch_pack_ser])
+                    .stderr(Stdio::piped())
+                    .args(["--protocol", protocol.as_str()])
                     .spawn()
                 else
                 {
                     tracing::warn!(game_id, "failed spawning game instance process");
-                    return (enfync::PendingResult::make_ready(false), enfync::PendingResult::make_ready(false));
+                    return enfync::PendingResult::make_ready(false);
                 };
 
-                // manage the process
-                let report_sender_clone = report_sender.clone();
-                let (process_handle, stdout_handle) = manage_child_process(
+                if !send_launch_pack(&mut child_process, protocol, &launch_pack).await
+                {
+                    let _ = child_process.kill().await;
+                    return enfync::PendingResult::make_ready(false);
+                }
+
+                manage_game_instance_process(
                     spawner,
                     game_id,
+                    protocol,
                     child_process,
+                    log,
                     command_receiver,
-                    move |report: GameInstanceReport| -> Option<bool>
-                    {
-                        match &report
-                        {
-                            GameInstanceReport::GameStart(_, _) =>
-                            {
-                                tracing::trace!(game_id, "game instance process report: game start");
-                                let _ = report_sender.send(report);
-                            }
-                            GameInstanceReport::GameOver(_, _) =>
-                            {
-                                tracing::trace!(game_id, "game instance process report: game over");
-                                let _ = report_sender.send(report);
-                                return Some(true);
-                            }
-                            GameInstanceReport::GameAborted(_, _) =>
-                            {
-                                tracing::trace!(game_id, "game instance process report: game aborted");
-                                let _ = report_sender.send(report);
-                                return Some(false);
-                            }
-                        }
-
-                        None
-                    },
-                    move ||
-                    {
-                        tracing::trace!(game_id, "game instance process report: game aborted (killed by critical error)");
-                        let _ = report_sender_clone.send(GameInstanceReport::GameAborted(game_id, "killed by critical error".into()));
-                    }
-                );
-
-                (process_handle, stdout_handle)
+                    kill_receiver,
+                    report_sender
+                )
             }
         );
 
         // return game instance
         // - we monitor the stdout reader instead of the process status because we want to wait for the game over report
         //   before terminating the instance
-        GameInstance::new(game_id, command_sender, command_receiver_clone, stdout_handle)
+        GameInstance::new(game_id, command_sender, command_receiver_clone, stdout_handle).with_kill_signal(kill_sender)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Send a game launch pack to a game instance process's `stdin`.
+/// - Returns `false` on failure.
+pub(crate) async fn send_launch_pack(
+    child_process : &mut tokio::process::Child,
+    protocol      : ChildProcessProtocol,
+    launch_pack   : &GameLaunchPack,
+) -> bool
+{
+    let game_id = launch_pack.game_id;
+    let bytes = match protocol.encode(launch_pack)
+    {
+        Ok(bytes) => bytes,
+        Err(err) =>
+        {
+            tracing::warn!(game_id, ?err, "failed serializing game launch pack for game instance process");
+            return false;
+        }
+    };
+    let Some(stdin) = child_process.stdin.as_mut() else { return false; };
+
+    if let Err(err) = stdin.write_all(&bytes).await
+    {
+        tracing::warn!(game_id, ?err, "failed sending launch pack to game instance process");
+        return false;
+    }
+    if let Err(err) = stdin.flush().await
+    {
+        tracing::warn!(game_id, ?err, "failed sending launch pack to game instance process");
+        return false;
+    }
+
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Manage a game instance process that was spawned by a game instance launcher.
+/// - Returns a handle to the process's `stdout` monitor.
+/// - The process is killed when a signal is received from `kill_receiver`.
+/// - Must be called inside a tokio task.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn manage_game_instance_process(
+    spawner          : enfync::builtin::native::TokioHandle,
+    game_id          : u64,
+    protocol         : ChildProcessProtocol,
+    child_process    : tokio::process::Child,
+    log              : ChildProcessLog,
+    command_receiver : IoReceiver<GameInstanceCommand>,
+    kill_receiver    : IoReceiver<()>,
+    report_sender    : IoSender<GameInstanceReport>,
+) -> enfync::PendingResult<bool>
+{
+    // manage the process
+    let report_sender_clone = report_sender.clone();
+    let (_process_handle, stdout_handle) = manage_child_process(
+        spawner,
+        game_id,
+        protocol,
+        child_process,
+        log.clone(),
+        command_receiver,
+        kill_receiver,
+        move |report: GameInstanceReport| -> Option<bool>
+        {
+            match &report
+            {
+                GameInstanceReport::GameStart(_, _) =>
+                {
+                    tracing::trace!(game_id, "game instance process report: game start");
+                    let _ = report_sender.send(report);
+                }
+                GameInstanceReport::GameOver(_, _) =>
+                {
+                    tracing::trace!(game_id, "game instance process report: game over");
+                    let _ = report_sender.send(report);
+                    return Some(true);
+                }
+                GameInstanceReport::GameAborted(_, _) =>
+                {
+                    tracing::trace!(game_id, "game instance process report: game aborted");
+                    let _ = report_sender.send(report);
+                    return Some(false);
+                }
+                GameInstanceReport::Heartbeat(_) =>
+                {
+                    let _ = report_sender.send(report);
+                }
+            }
+
+            None
+        },
+        move ||
+        {
+            tracing::trace!(game_id, "game instance process report: game aborted (killed by critical error)");
+            let tail = log.tail();
+            let reason = match tail.is_empty()
+            {
+                true  => String::from("killed by critical error"),
+                false => format!("killed by critical error, process output:\n{}", tail.join("\n")),
+            };
+            let _ = report_sender_clone.send(GameInstanceReport::GameAborted(game_id, reason));
+        }
+    );
+
+    stdout_handle
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Read a game launch pack from `stdin`.
+/// - Blocks until a message is available.
+fn read_launch_pack_from_stdin(protocol: ChildProcessProtocol) -> Option<GameLaunchPack>
+{
+    // note: `Stdin` is buffered internally, so messages after the launch pack will still be available to later readers
+    match protocol.read::<GameLaunchPack>(&mut std::io::stdin().lock())
+    {
+        Ok(Some(launch_pack)) => Some(launch_pack),
+        Ok(None) => { tracing::info!("stdin closed before receiving a game launch pack"); None }
+        Err(err) => { tracing::error!(?err, "failed reading game launch pack from stdin"); None }
     }
 }
 
@@ -128,10 +264,14 @@ impl GameInstanceLauncherImpl for GameInstanceLauncherProcess
 /// Launch a game inside a standalone process.
 ///
 /// Reads [`GameInstanceCommand`]s from `stdin` and writes [`GameInstanceReport`]s to `stdout`.
+///
+/// If `args` doesn't contain a launch pack, the process will wait for one on `stdin`.
 pub fn inprocess_game_launcher(args: GameInstanceCli, game_factory: GameFactory)
 {
     // get game launch pack
-    let game_id = args.launch_pack.game_id;
+    let protocol = args.protocol;
+    let Some(launch_pack) = args.launch_pack.or_else(|| read_launch_pack_from_stdin(protocol)) else { return; };
+    let game_id = launch_pack.game_id;
     tracing::info!(game_id, "game instance process started");
 
     // prepare game app
@@ -140,7 +280,7 @@ pub fn inprocess_game_launcher(args: GameInstanceCli, game_factory: GameFactory)
 
     let app = game_instance_setup(
         game_factory,
-        args.launch_pack,
+        launch_pack,
         report_sender.clone(),
         command_receiver,
     ).expect("failed setting up game instance");
@@ -148,6 +288,7 @@ pub fn inprocess_game_launcher(args: GameInstanceCli, game_factory: GameFactory)
     // run the app
     run_app_in_child_process(
         game_id,
+        protocol,
         app,
         command_sender.clone(),
         report_receiver,
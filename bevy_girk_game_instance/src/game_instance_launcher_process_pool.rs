@@ -0,0 +1,349 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use enfync::Handle;
+use tokio::io::{AsyncBufReadExt, AsyncRead};
+
+//standard shortcuts
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn spawn_process(path: &str, protocol: ChildProcessProtocol) -> Option<tokio::process::Child>
+{
+    match tokio::process::Command::new(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .args(["--protocol", protocol.as_str()])
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child_process) => Some(child_process),
+        Err(err) =>
+        {
+            tracing::warn!(?err, "failed spawning idle game instance process");
+            None
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Writes lines from an idle process's output to its log until a stop signal is received or the output closes.
+/// - Returns the output so it can be handed off when the process is launched.
+async fn capture_idle_output<R: AsyncRead + Unpin>(
+    output            : R,
+    log               : ChildProcessLog,
+    mut stop_receiver : IoReceiver<()>,
+) -> R
+{
+    let mut reader = tokio::io::BufReader::new(output);
+    let mut line = Vec::<u8>::default();
+    loop
+    {
+        tokio::select!
+        {
+            // - partially read lines stay in `line` if the stop signal wins
+            result = reader.read_until(b'\n', &mut line) =>
+            {
+                match result
+                {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => { log.write_line(&String::from_utf8_lossy(&line)); line.clear(); }
+                }
+            }
+            Some(()) = stop_receiver.recv() => break,
+        }
+    }
+
+    // write out output that was read but not logged yet
+    // - Idle processes don't write reports, so everything buffered is plain output.
+    line.extend_from_slice(reader.buffer());
+    for text in String::from_utf8_lossy(&line).lines() { log.write_line(text); }
+
+    reader.into_inner()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An idle game instance process.
+/// - The process's `stdout` and `stderr` are captured in its log while it waits for a launch pack, so its output
+///   isn't lost and the process can't block on full pipes.
+#[derive(Debug)]
+struct IdleProcess
+{
+    child_process  : tokio::process::Child,
+    log            : ChildProcessLog,
+    stop_sender    : IoSender<()>,
+    stdout_capture : enfync::PendingResult<tokio::process::ChildStdout>,
+    stderr_capture : enfync::PendingResult<tokio::process::ChildStderr>,
+}
+
+impl IdleProcess
+{
+    /// Spawns a new idle process and starts capturing its output.
+    /// - Must be called inside a tokio task.
+    fn spawn(
+        spawner    : &enfync::builtin::native::TokioHandle,
+        path       : &str,
+        protocol   : ChildProcessProtocol,
+        log_config : &ChildProcessLogConfig,
+        name       : &str,
+    ) -> Option<Self>
+    {
+        let mut child_process = spawn_process(path, protocol)?;
+        let stdout = child_process.stdout.take()?;
+        let stderr = child_process.stderr.take()?;
+        let log = ChildProcessLog::new(log_config.clone(), name);
+        let (stop_sender, stop_receiver) = new_io_channel::<()>();
+
+        let stdout_capture = spawner.spawn(capture_idle_output(stdout, log.clone(), stop_receiver.clone()));
+        let stderr_capture = spawner.spawn(capture_idle_output(stderr, log.clone(), stop_receiver));
+
+        Some(Self{ child_process, log, stop_sender, stdout_capture, stderr_capture })
+    }
+
+    /// Checks if the process is still alive. Logs the exit status of dead processes.
+    fn is_alive(&mut self) -> bool
+    {
+        match self.child_process.try_wait()
+        {
+            Ok(None) => true,
+            Ok(Some(status)) => { tracing::warn!(?status, "idle game instance process died"); false }
+            Err(err) => { tracing::warn!(?err, "failed checking idle game instance process status"); false }
+        }
+    }
+
+    /// Stops capturing the process's output and renames its log to `name`.
+    /// - Returns the process with its `stdout` and `stderr` restored, and the process's log.
+    async fn activate(mut self, name: &str) -> Option<(tokio::process::Child, ChildProcessLog)>
+    {
+        // two signals, one for each capture task
+        let _ = self.stop_sender.send(());
+        let _ = self.stop_sender.send(());
+
+        self.child_process.stdout = Some(self.stdout_capture.extract().await.ok()?);
+        self.child_process.stderr = Some(self.stderr_capture.extract().await.ok()?);
+        self.log.rename(name);
+
+        Some((self.child_process, self.log))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Removes dead idle processes and spawns new ones until the pool is full.
+/// - New processes are spawned without holding the `idle` lock so launches aren't blocked by process startup.
+fn refill_pool(
+    spawner     : &enfync::builtin::native::TokioHandle,
+    path        : &str,
+    config      : &GameInstanceProcessPoolConfig,
+    idle        : &Mutex<VecDeque<IdleProcess>>,
+    num_spawned : &mut u64,
+)
+{
+    let num_missing = {
+        let mut idle = idle.lock().unwrap();
+        idle.retain_mut(IdleProcess::is_alive);
+        config.pool_size.saturating_sub(idle.len())
+    };
+
+    let mut new_processes = Vec::with_capacity(num_missing);
+    for _ in 0..num_missing
+    {
+        let name = format!("idle_{num_spawned}");
+        let Some(idle_process) = IdleProcess::spawn(spawner, path, config.protocol, &config.log_config, &name)
+        else { break; };
+        *num_spawned += 1;
+        new_processes.push(idle_process);
+    }
+
+    idle.lock().unwrap().extend(new_processes);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct GameInstanceProcessPoolConfig
+{
+    /// Number of idle processes to keep ready.
+    pub pool_size: usize,
+    /// Amount of time to wait between pool refills.
+    ///
+    /// Dead idle processes are replaced and launched processes are replenished on each refill.
+    pub refill_interval: Duration,
+    /// Wire format for `stdin`/`stdout` messages.
+    pub protocol: ChildProcessProtocol,
+    /// Config for capturing process output.
+    pub log_config: ChildProcessLogConfig,
+}
+
+impl Default for GameInstanceProcessPoolConfig
+{
+    fn default() -> Self
+    {
+        Self{
+            pool_size       : 2,
+            refill_interval : Duration::from_millis(500),
+            protocol        : ChildProcessProtocol::default(),
+            log_config      : ChildProcessLogConfig::default(),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Launch game instances in pre-warmed processes on the current machine.
+///
+/// The pool keeps idle game app processes ready so process startup costs are paid before games are requested.
+/// Idle processes block until they receive a [`GameLaunchPack`] on `stdin` (see [`inprocess_game_launcher()`]).
+/// - The pool is refilled by a background task, which also replaces idle processes that die.
+/// - If the pool is empty when a game is launched, a new process will be spawned for the game.
+/// - Idle processes are killed when the launcher is dropped.
+///
+/// Process output is captured and processes can be killed the same way as [`GameInstanceLauncherProcess`].
+/// Output is captured from the moment a process is spawned. Idle processes are logged as `idle_{n}` until they are
+/// launched, at which point the log is renamed to `game_{game id}`.
+#[derive(Debug)]
+pub struct GameInstanceLauncherProcessPool
+{
+    /// Path to the game app binary.
+    path: String,
+    /// Spawner for internal async tasks.
+    spawner: enfync::builtin::native::TokioHandle,
+    /// Config for capturing process output.
+    log_config: ChildProcessLogConfig,
+    /// Wire format for `stdin`/`stdout` messages.
+    protocol: ChildProcessProtocol,
+    /// Idle processes.
+    idle: Arc<Mutex<VecDeque<IdleProcess>>>,
+}
+
+impl GameInstanceLauncherProcessPool
+{
+    /// Make a new pool launcher.
+    /// - Spawns a background task for refilling the pool.
+    pub fn new(
+        path    : String,
+        spawner : enfync::builtin::native::TokioHandle,
+        config  : GameInstanceProcessPoolConfig,
+    ) -> Self
+    {
+        let idle = Arc::new(Mutex::new(VecDeque::with_capacity(config.pool_size)));
+
+        // refill the pool until the launcher is dropped
+        let weak_idle: Weak<Mutex<VecDeque<IdleProcess>>> = Arc::downgrade(&idle);
+        let refill_spawner = spawner.clone();
+        let refill_path = path.clone();
+        let refill_config = config.clone();
+        spawner.spawn(
+            async move
+            {
+                let mut num_spawned = 0u64;
+                loop
+                {
+                    let Some(idle) = weak_idle.upgrade() else { return; };
+                    refill_pool(&refill_spawner, &refill_path, &refill_config, &idle, &mut num_spawned);
+                    drop(idle);
+                    tokio::time::sleep(refill_config.refill_interval).await;
+                }
+            }
+        );
+
+        Self{ path, spawner, log_config: config.log_config, protocol: config.protocol, idle }
+    }
+
+    /// Get the number of idle processes that are currently ready.
+    /// - May include processes that died since the last refill.
+    pub fn num_idle(&self) -> usize
+    {
+        self.idle.lock().unwrap().len()
+    }
+
+    /// Takes the next live idle process.
+    fn take_idle(&self) -> Option<IdleProcess>
+    {
+        let mut idle = self.idle.lock().unwrap();
+        while let Some(mut idle_process) = idle.pop_front()
+        {
+            if idle_process.is_alive() { return Some(idle_process); }
+        }
+        None
+    }
+}
+
+impl GameInstanceLauncherImpl for GameInstanceLauncherProcessPool
+{
+    fn launch(
+        &self,
+        launch_pack: GameLaunchPack,
+        report_sender: IoSender<GameInstanceReport>,
+    ) -> GameInstance
+    {
+        // prepare command channel
+        let (command_sender, command_receiver) = new_io_channel::<GameInstanceCommand>();
+        let command_receiver_clone = command_receiver.clone();
+        let (kill_sender, kill_receiver) = new_io_channel::<()>();
+
+        // launch game in an idle process
+        let game_id = launch_pack.game_id;
+        let protocol = self.protocol;
+        let idle_process = self.take_idle();
+        let spawner = self.spawner.clone();
+        let log_name = format!("game_{game_id}");
+        let stdout_handle = self.spawner.0.block_on(
+            async move {
+                let (mut child_process, log) = match idle_process
+                {
+                    Some(idle_process) =>
+                    {
+                        let Some(activated) = idle_process.activate(&log_name).await
+                        else
+                        {
+                            tracing::warn!(game_id, "failed taking over idle game instance process output");
+                            return enfync::PendingResult::make_ready(false);
+                        };
+                        activated
+                    }
+                    None =>
+                    {
+                        tracing::warn!(game_id, "no idle game instance processes, spawning a new process");
+                        let Some(child_process) = spawn_process(&self.path, protocol)
+                        else { return enfync::PendingResult::make_ready(false); };
+                        (child_process, ChildProcessLog::new(self.log_config.clone(), &log_name))
+                    }
+                };
+
+                // send the launch pack
+                if !send_launch_pack(&mut child_process, protocol, &launch_pack).await
+                {
+                    let _ = child_process.kill().await;
+                    return enfync::PendingResult::make_ready(false);
+                }
+
+                manage_game_instance_process(
+                    spawner,
+                    game_id,
+                    protocol,
+                    child_process,
+                    log,
+                    command_receiver,
+                    kill_receiver,
+                    report_sender
+                )
+            }
+        );
+
+        // return game instance
+        GameInstance::new(game_id, command_sender, command_receiver_clone, stdout_handle).with_kill_signal(kill_sender)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -73,6 +73,9 @@ pub enum GameInstanceReport
     GameOver(u64, GameOverReport),
     /// Includes (game id, reason for aborting).
     GameAborted(u64, String),
+    /// Sent periodically while the game app is running.
+    /// - See [`GameInstanceHeartbeatConfig`](crate::GameInstanceHeartbeatConfig).
+    Heartbeat(u64),
 }
 
 impl GameInstanceReport
@@ -84,6 +87,7 @@ impl GameInstanceReport
             GameInstanceReport::GameStart(id, _) => *id,
             GameInstanceReport::GameOver(id, _)  => *id,
             GameInstanceReport::GameAborted(id, _)  => *id,
+            GameInstanceReport::Heartbeat(id)  => *id,
         }
     }
 }
@@ -14,6 +14,8 @@ mod types;
 mod game_instance_launcher_local_native;
 #[cfg(all(feature = "process", not(target_family = "wasm")))]
 mod game_instance_launcher_process;
+#[cfg(all(feature = "process", not(target_family = "wasm")))]
+mod game_instance_launcher_process_pool;
 
 #[cfg(target_family = "wasm")]
 mod game_instance_launcher_local_wasm;
@@ -32,6 +34,8 @@ pub use types::*;
 pub use game_instance_launcher_local_native::*;
 #[cfg(all(feature = "process", not(target_family = "wasm")))]
 pub use game_instance_launcher_process::*;
+#[cfg(all(feature = "process", not(target_family = "wasm")))]
+pub use game_instance_launcher_process_pool::*;
 
 #[cfg(target_family = "wasm")]
 pub use game_instance_launcher_local_wasm::*;
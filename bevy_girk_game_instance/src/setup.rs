@@ -43,6 +43,45 @@ fn try_collect_game_over_report(
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Sends a heartbeat report every `interval_ticks` game framework ticks.
+/// - Heartbeats are driven by [`GameFwTick`], not by app updates. If the game framework stops advancing its tick
+///   (e.g. the game app hangs inside a tick), no more heartbeats will be sent.
+fn send_heartbeat(
+    mut last_tick    : Local<Option<u32>>,
+    game_fw_tick     : Res<GameFwTick>,
+    heartbeat_config : Res<GameInstanceHeartbeatConfig>,
+    runner_state     : Res<GameRunnerState>,
+){
+    // only send one heartbeat per tick, in case the app updates without advancing the tick
+    let tick: u32 = ***game_fw_tick;
+    if *last_tick == Some(tick) { return; }
+    *last_tick = Some(tick);
+    if !tick.is_multiple_of(heartbeat_config.interval_ticks.max(1)) { return; }
+
+    if let Err(_) = runner_state.report_sender.send(GameInstanceReport::Heartbeat(runner_state.game_id))
+    { tracing::error!(runner_state.game_id, "failed sending heartbeat message"); }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Config for game instance heartbeats.
+///
+/// Game instances send a [`GameInstanceReport::Heartbeat`] every `interval_ticks` [`GameFwTicks`](GameFwTick) so
+/// their owners can detect game apps that stopped ticking.
+/// - Heartbeats depend on [`GameFwTick`] advancing, not on the app updating. A game app that keeps updating without
+///   advancing its tick (e.g. because the game framework is paused) will be considered unresponsive.
+/// - Insert this into the game app in your [`GameFactory`] to override the default interval (one heartbeat per
+///   second of ticks, based on [`GameFwConfig::ticks_per_sec`]).
+#[derive(Resource, Debug, Copy, Clone)]
+pub struct GameInstanceHeartbeatConfig
+{
+    /// Number of ticks between heartbeats.
+    pub interval_ticks: u32,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Resource)]
 pub(crate) struct GameRunnerState
 {
@@ -59,6 +98,7 @@ pub(crate) struct GameRunnerState
 /// Sets up a game app for a game instance.
 /// - Makes a new game app configured for use in a game instance. Depends on `GameFwConfig`.
 /// - When you run the app, it will continue updating until a game over report appears.
+/// - While the app runs, it will send heartbeat reports (see [`GameInstanceHeartbeatConfig`]).
 pub fn game_instance_setup(
     game_factory: GameFactory,
     launch_pack: GameLaunchPack,
@@ -79,6 +119,13 @@ pub fn game_instance_setup(
     // set app runner
     set_game_app_runner(&mut game_app);
 
+    // set heartbeat interval
+    if !game_app.world().contains_resource::<GameInstanceHeartbeatConfig>()
+    {
+        let ticks_per_sec = game_app.world().resource::<GameFwConfig>().ticks_per_sec();
+        game_app.insert_resource(GameInstanceHeartbeatConfig{ interval_ticks: ticks_per_sec });
+    }
+
     // make runner state
     let runner_state = GameRunnerState{ game_id, report_sender, command_receiver };
 
@@ -86,7 +133,7 @@ pub fn game_instance_setup(
     game_app
         .insert_resource(runner_state)
         .add_systems(First, handle_command_incoming)
-        .add_systems(Last, try_collect_game_over_report);
+        .add_systems(Last, (send_heartbeat, try_collect_game_over_report));
 
     // return the app
     Ok(game_app)
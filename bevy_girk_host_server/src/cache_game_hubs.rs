@@ -1,4 +1,5 @@
 //local shortcuts
+use crate::*;
 
 //third-party shortcuts
 use bevy::prelude::*;
@@ -10,7 +11,9 @@ use std::collections::hash_set::Drain;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-/// Capacity of a game hub equals the number of new games it can launch without over-subscribing its CPU.
+/// Capacity of a game hub equals the total cost of new games it can launch without over-subscribing its CPU.
+///
+/// See [`GameCostEstimator`].
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct GameHubCapacity(pub u16);
 
@@ -19,7 +22,7 @@ pub struct GameHubCapacity(pub u16);
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
 struct SortableHubCapacity
 {
-    /// last reported hub capacity - number pending game requests
+    /// last reported hub capacity - cost of pending game requests
     estimated_capacity: i32,
     /// hub id
     id: u128
@@ -30,7 +33,7 @@ impl SortableHubCapacity
     fn from_state(state: &GameHubState, id: u128) -> SortableHubCapacity
     {
         SortableHubCapacity{
-                estimated_capacity: (state.capacity.0 as i32) - (state.pending.len() as i32),
+                estimated_capacity: (state.capacity.0 as i32) - (state.pending_cost() as i32),
                 id
             }
     }
@@ -38,15 +41,55 @@ impl SortableHubCapacity
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Summary of a game hub's state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameHubInfo
+{
+    /// Hub id.
+    pub id: u128,
+    /// Last reported hub capacity.
+    pub capacity: GameHubCapacity,
+    /// Number of pending game requests.
+    pub num_pending: usize,
+    /// Total cost of pending game requests.
+    pub pending_cost: u32,
+    /// Number of games currently running on the hub.
+    pub num_games: usize,
+    /// Metadata advertised by the hub.
+    pub metadata: GameHubMetadata,
+}
+
+impl GameHubInfo
+{
+    /// Estimated capacity available for new games (last reported capacity - cost of pending games).
+    pub fn available_capacity(&self) -> i32
+    {
+        (self.capacity.0 as i32) - (self.pending_cost as i32)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Default, Debug)]
 struct GameHubState
 {
     /// hub capacity
     capacity: GameHubCapacity,
-    /// [ lobby ids ] of pending game requests
-    pending: HashSet<u64>,
+    /// [ lobby id : game cost ] of pending game requests
+    pending: HashMap<u64, u16>,
     /// [ lobby ids ] of games currently running on the hub
     games: HashSet<u64>,
+    /// metadata advertised by the hub
+    metadata: GameHubMetadata,
+}
+
+impl GameHubState
+{
+    /// total cost of pending game requests
+    fn pending_cost(&self) -> u32
+    {
+        self.pending.values().map(|cost| *cost as u32).sum()
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -58,7 +101,9 @@ pub struct GameHubsCache
     /// [ hub id : hub state ]
     hubs: HashMap<u128, GameHubState>,
     /// hubs sorted by capacity (the id is baked in so hubs with equal capacity can coexist in the set)
-    sorted_capacity: BTreeSet<SortableHubCapacity>
+    sorted_capacity: BTreeSet<SortableHubCapacity>,
+    /// whether available hub capacity may have increased since the flag was last taken
+    capacity_freed: bool,
 }
 
 impl GameHubsCache
@@ -85,6 +130,9 @@ impl GameHubsCache
         // access hub
         let Some(game_hub_state) = self.hubs.get_mut(&hub_id) else { return Err(()); };
 
+        // track freed capacity
+        if game_hub_state.capacity < capacity { self.capacity_freed = true; }
+
         // remove existing hub capacity
         if game_hub_state.capacity != capacity
         {
@@ -98,6 +146,17 @@ impl GameHubsCache
         Ok(())
     }
 
+    /// set a hub's metadata
+    /// - returns `Err(())` if the hub doesn't exist
+    pub fn set_hub_metadata(&mut self, hub_id: u128, metadata: GameHubMetadata) -> Result<(), ()>
+    {
+        let Some(game_hub_state) = self.hubs.get_mut(&hub_id) else { return Err(()); };
+        game_hub_state.metadata = metadata;
+        self.capacity_freed = true;
+
+        Ok(())
+    }
+
     /// remove a hub if it exists
     /// - returns `Err(())` on failure
     pub fn remove_hub(&mut self, hub_id: u128) -> Result<(), ()>
@@ -110,27 +169,24 @@ impl GameHubsCache
     }
 
     /// add pending game
+    /// - `cost` is the game's capacity cost (see [`GameCostEstimator`])
     /// - returns `Ok(())` if the game was added to pending
     /// - returns `Err(())` if the hub doesn't exist or the game already exists
-    pub fn add_pending_game(&mut self, hub_id: u128, lobby_id: u64) -> Result<(), ()>
+    pub fn add_pending_game(&mut self, hub_id: u128, lobby_id: u64, cost: u16) -> Result<(), ()>
     {
-        tracing::trace!(hub_id, lobby_id, "add pending game");
+        tracing::trace!(hub_id, lobby_id, cost, "add pending game");
 
         // access game hub
         let Some(game_hub_state) = self.hubs.get_mut(&hub_id) else { return Err(()); };
 
         // check if the game exists
-        if game_hub_state.pending.contains(&lobby_id) { return Err(()); }
+        if game_hub_state.pending.contains_key(&lobby_id) { return Err(()); }
         if game_hub_state.games.contains(&lobby_id) { return Err(()); }
 
         // insert to pending
         let prev_sortable = SortableHubCapacity::from_state(game_hub_state, hub_id);
         if !self.sorted_capacity.remove(&prev_sortable) { return Err(()); }
-        if !game_hub_state.pending.insert(lobby_id)
-        {
-            self.sorted_capacity.insert(prev_sortable);
-            return Err(());
-        }
+        game_hub_state.pending.insert(lobby_id, cost);
 
         // update sortable hub
         let _ = self.sorted_capacity.insert(SortableHubCapacity::from_state(game_hub_state, hub_id));
@@ -151,7 +207,7 @@ impl GameHubsCache
         // remove from pending
         let prev_sortable = SortableHubCapacity::from_state(game_hub_state, hub_id);
         if !self.sorted_capacity.remove(&prev_sortable) { return Err(()); }
-        if !game_hub_state.pending.remove(&lobby_id)
+        if game_hub_state.pending.remove(&lobby_id).is_none()
         {
             self.sorted_capacity.insert(prev_sortable);
             return Err(());
@@ -159,6 +215,7 @@ impl GameHubsCache
 
         // update sortable hub
         let _ = self.sorted_capacity.insert(SortableHubCapacity::from_state(game_hub_state, hub_id));
+        self.capacity_freed = true;
 
         // insert to games
         if !game_hub_state.games.insert(lobby_id) { return Err(()); }
@@ -166,6 +223,23 @@ impl GameHubsCache
         Ok(())
     }
 
+    /// add game that is already running on the hub
+    /// - used to restore games the hub is known to be running (e.g. after a host server restart)
+    /// - returns `Err(())` if the hub doesn't exist or the game already exists
+    pub fn add_game(&mut self, hub_id: u128, lobby_id: u64) -> Result<(), ()>
+    {
+        tracing::trace!(hub_id, lobby_id, "add game");
+
+        // access game hub
+        let Some(game_hub_state) = self.hubs.get_mut(&hub_id) else { return Err(()); };
+
+        // check if the game exists
+        if game_hub_state.pending.contains_key(&lobby_id) { return Err(()); }
+        if !game_hub_state.games.insert(lobby_id) { return Err(()); }
+
+        Ok(())
+    }
+
     /// remove pending game
     /// - returns `Err(())` if the hub or game doesn't exist
     pub fn remove_pending_game(&mut self, hub_id: u128, lobby_id: u64) -> Result<(), ()>
@@ -178,7 +252,7 @@ impl GameHubsCache
         // remove from pending
         let prev_sortable = SortableHubCapacity::from_state(game_hub_state, hub_id);
         if !self.sorted_capacity.remove(&prev_sortable) { return Err(()); }
-        if !game_hub_state.pending.remove(&lobby_id)
+        if game_hub_state.pending.remove(&lobby_id).is_none()
         {
             self.sorted_capacity.insert(prev_sortable);
             return Err(());
@@ -186,6 +260,7 @@ impl GameHubsCache
 
         // update sortable hub
         let _ = self.sorted_capacity.insert(SortableHubCapacity::from_state(game_hub_state, hub_id));
+        self.capacity_freed = true;
 
         Ok(())
     }
@@ -244,7 +319,7 @@ impl GameHubsCache
     pub fn has_pending_game(&self, hub_id: u128, lobby_id: u64) -> bool
     {
         let Some(game_hub_state) = self.hubs.get(&hub_id) else { return false; };
-        game_hub_state.pending.contains(&lobby_id)
+        game_hub_state.pending.contains_key(&lobby_id)
     }
 
     /// check if the specified hub has a given game
@@ -253,6 +328,30 @@ impl GameHubsCache
         let Some(game_hub_state) = self.hubs.get(&hub_id) else { return false; };
         game_hub_state.games.contains(&lobby_id)
     }
+
+    /// take the capacity-freed flag
+    /// - returns `true` if available hub capacity may have increased since the last call (a hub's capacity increased,
+    ///   its metadata changed, or one of its pending games was removed or started)
+    pub fn take_capacity_freed(&mut self) -> bool
+    {
+        std::mem::take(&mut self.capacity_freed)
+    }
+
+    /// iterate over summaries of all hubs
+    pub fn hubs(&self) -> impl Iterator<Item = GameHubInfo> + '_
+    {
+        self.hubs.iter().map(
+                |(id, state)|
+                GameHubInfo{
+                    id           : *id,
+                    capacity     : state.capacity,
+                    num_pending  : state.pending.len(),
+                    pending_cost : state.pending_cost(),
+                    num_games    : state.games.len(),
+                    metadata     : state.metadata.clone(),
+                }
+            )
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,217 @@
+//local shortcuts
+use bevy_girk_backend_public::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Trait for ordering lobbies in the game launch queue.
+pub trait LaunchPrioritizer: Debug + Send + Sync + 'static
+{
+    /// Get the launch priority of a lobby.
+    /// - Lobbies with higher priority launch first. Lobbies with equal priority launch in FIFO order.
+    fn launch_priority(&self, lobby: &LobbyData) -> i32;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct GameLaunchQueueConfig
+{
+    /// Max amount of time a lobby may wait in the queue before its launch is aborted.
+    pub queue_timeout: Duration,
+    /// Launch prioritizer (optional: lobbies launch in FIFO order if `None`).
+    pub prioritizer: Option<Box<dyn LaunchPrioritizer>>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct QueuedLaunch
+{
+    /// id of the queued lobby
+    lobby_id: u64,
+    /// launch priority
+    priority: i32,
+    /// registration timestamp
+    birth_time: Duration,
+    /// queue position most recently reported to the lobby's members
+    reported_position: Option<u32>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks fully-acked pending lobbies that are waiting for game hub capacity.
+///
+/// Lobbies launch strictly in queue order. A lobby that no game hub can accept blocks the lobbies behind it until it
+/// launches, leaves the queue, or times out.
+#[derive(Resource)]
+pub struct GameLaunchQueueCache
+{
+    /// config
+    config: GameLaunchQueueConfig,
+    /// cache timer
+    timer: Instant,
+    /// [ queued launch ]
+    /// - sorted by priority (highest first), then from oldest to youngest
+    queue: Vec<QueuedLaunch>,
+    /// timestamp of the most recent launch out of the queue
+    prev_launch: Option<Duration>,
+    /// smoothed time between launches out of the queue
+    launch_interval: Option<Duration>,
+    /// lobby at the front of the queue that most recently failed to launch
+    blocked_lobby: Option<u64>,
+}
+
+impl GameLaunchQueueCache
+{
+    /// Make a new cache.
+    pub fn new(config: GameLaunchQueueConfig) -> GameLaunchQueueCache
+    {
+        GameLaunchQueueCache{
+                config,
+                timer           : Instant::now(),
+                queue           : Vec::default(),
+                prev_launch     : None,
+                launch_interval : None,
+                blocked_lobby   : None,
+            }
+    }
+
+    /// Add a lobby to the queue.
+    /// - Returns `false` if the lobby is already queued.
+    pub fn add_lobby(&mut self, lobby: &LobbyData) -> bool
+    {
+        if self.has_lobby(lobby.id) { return false; }
+        tracing::trace!(lobby.id, "add queued launch");
+
+        let priority = self.config.prioritizer.as_ref().map(|p| p.launch_priority(lobby)).unwrap_or_default();
+        let idx = self.queue.iter().position(|queued| queued.priority < priority).unwrap_or(self.queue.len());
+        self.queue.insert(idx, QueuedLaunch{
+                lobby_id          : lobby.id,
+                priority,
+                birth_time        : self.timer.elapsed(),
+                reported_position : None,
+            });
+
+        true
+    }
+
+    /// Remove a lobby from the queue without launching it.
+    /// - Returns `Err` if the lobby is not queued.
+    pub fn remove_lobby(&mut self, lobby_id: u64) -> Result<(), ()>
+    {
+        let Some(idx) = self.queue.iter().position(|queued| queued.lobby_id == lobby_id) else { return Err(()); };
+        tracing::trace!(lobby_id, "remove queued launch");
+        self.queue.remove(idx);
+
+        Ok(())
+    }
+
+    /// Remove a lobby from the queue because its game was launched.
+    /// - Updates the launch interval used for wait estimates.
+    /// - Returns `Err` if the lobby is not queued.
+    pub fn remove_launched_lobby(&mut self, lobby_id: u64) -> Result<(), ()>
+    {
+        self.remove_lobby(lobby_id)?;
+
+        let now = self.timer.elapsed();
+        if let Some(prev_launch) = self.prev_launch
+        {
+            let sample = now.saturating_sub(prev_launch);
+            self.launch_interval = Some(match self.launch_interval
+            {
+                Some(interval) => (interval * 3 + sample) / 4,
+                None           => sample,
+            });
+        }
+        self.prev_launch = Some(now);
+
+        Ok(())
+    }
+
+    /// Remove lobbies that fail the predicate.
+    pub fn retain_lobbies(&mut self, mut predicate: impl FnMut(u64) -> bool)
+    {
+        self.queue.retain(|queued| predicate(queued.lobby_id));
+    }
+
+    /// Check if a lobby is queued.
+    pub fn has_lobby(&self, lobby_id: u64) -> bool
+    {
+        self.queue.iter().any(|queued| queued.lobby_id == lobby_id)
+    }
+
+    /// Current number of queued lobbies.
+    pub fn num_queued(&self) -> usize
+    {
+        self.queue.len()
+    }
+
+    /// Iterate over queued lobby ids in launch order.
+    pub fn lobby_ids(&self) -> impl Iterator<Item = u64> + '_
+    {
+        self.queue.iter().map(|queued| queued.lobby_id)
+    }
+
+    /// Get the lobby at the front of the queue that most recently failed to launch.
+    pub fn blocked_lobby(&self) -> Option<u64>
+    {
+        self.blocked_lobby
+    }
+
+    /// Record the lobby at the front of the queue that failed to launch (`None` if the queue was fully launched).
+    pub fn set_blocked_lobby(&mut self, lobby_id: Option<u64>)
+    {
+        self.blocked_lobby = lobby_id;
+    }
+
+    /// Estimate how long a lobby at the given queue position will wait.
+    /// - Returns `None` if not enough lobbies have launched out of the queue to make an estimate.
+    pub fn estimated_wait(&self, position: u32) -> Option<Duration>
+    {
+        self.launch_interval.map(|interval| interval * position)
+    }
+
+    /// Collect queue positions that changed since they were last collected.
+    /// - Returns `[ (lobby id, queue position) ]`. Positions start at 1.
+    pub fn take_position_updates(&mut self) -> Vec<(u64, u32)>
+    {
+        let mut updates = Vec::default();
+        for (idx, queued) in self.queue.iter_mut().enumerate()
+        {
+            let position = idx as u32 + 1;
+            if queued.reported_position == Some(position) { continue; }
+            queued.reported_position = Some(position);
+            updates.push((queued.lobby_id, position));
+        }
+        updates
+    }
+
+    /// Drain lobbies that have waited longer than the queue timeout.
+    pub fn drain_expired(&mut self) -> Vec<u64>
+    {
+        let current_timestamp = self.timer.elapsed();
+        let queue_timeout     = self.config.queue_timeout;
+
+        //todo: use .extract_if once stabilized
+        let mut extracted = Vec::default();
+        self.queue.retain(
+            |queued| -> bool
+            {
+                if queued.birth_time.saturating_add(queue_timeout) >= current_timestamp { return true; }
+                tracing::trace!(queued.lobby_id, "removing expired queued launch");
+                extracted.push(queued.lobby_id);
+                false
+            }
+        );
+        extracted
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -23,6 +23,13 @@ pub struct LobbiesCacheConfig
     /// Used to validate new/inserted lobbies and new lobby members. All member-insertion rules are at the discretion of
     /// the lobby checker, including password checks.
     pub lobby_checker: Box<dyn LobbyChecker>,
+    /// Policy for keeping lobbies alive when their owners leave.
+    pub owner_succession: OwnerSuccession,
+
+    /// Lobby searcher (optional).
+    ///
+    /// Used to filter and sort lobbies for [`LobbySearchRequest::Filtered`] requests.
+    pub lobby_searcher: Option<Box<dyn LobbySearcher>>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -55,7 +62,7 @@ impl LobbiesCache
         owner_data  : LobbyMemberData,
         password    : String,
         custom_data : Vec<u8>,
-    ) -> Result<u64, ()>
+    ) -> Result<u64, HostRequestError>
     {
         tracing::trace!(owner_id, "new lobby");
         self.generated_count += 1;
@@ -65,10 +72,10 @@ impl LobbiesCache
         let mut lobby = Lobby::new(lobby_id, owner_id, password.clone(), custom_data);
 
         // check lobby is valid
-        if !self.config.lobby_checker.check_lobby(&lobby) { return Err(()); }
+        if !self.config.lobby_checker.check_lobby(&lobby) { return Err(HostRequestError::Rejected); }
 
         // check lobby owner can be added as a member to the lobby
-        if !self.config.lobby_checker.allow_new_member(&lobby, owner_id, owner_data, &password) { return Err(()); }
+        self.config.lobby_checker.allow_new_member(&lobby, owner_id, owner_data, &password)?;
 
         // add lobby owner as a member
         lobby.add_member(owner_id, owner_data);
@@ -103,27 +110,123 @@ impl LobbiesCache
     }
 
     /// Add a member to the lobby.
-    /// - Returns false if unable to add member (lobby doesn't exist, rejected by lobby checker).
+    /// - Returns `Err` if unable to add member (lobby doesn't exist, lobby is private, member is banned, rejected by
+    ///   lobby checker).
     pub fn try_add_member(
         &mut self,
         lobby_id      : u64,
         new_member_id : u128,
         member_data   : LobbyMemberData,
         password      : &String
-    ) -> bool
+    ) -> Result<(), HostRequestError>
     {
         // check lobby exists
-        let Some(lobby_ref) = self.lobby_ref(lobby_id) else { return false; };
+        let Some(lobby_ref) = self.lobby_ref(lobby_id) else { return Err(HostRequestError::LobbyNotFound); };
+
+        // check if lobby can only be joined with an invite
+        if lobby_ref.is_private() { return Err(HostRequestError::InviteOnly); }
+
+        // check if member is banned
+        if lobby_ref.is_banned(new_member_id) { return Err(HostRequestError::Banned); }
 
         // check if member can be added to the lobby
-        if !self.config.lobby_checker.allow_new_member(lobby_ref, new_member_id, member_data, &password) { return false; }
+        self.config.lobby_checker.allow_new_member(lobby_ref, new_member_id, member_data, &password)?;
 
         // add member
-        let Some(lobby_ref) = self.lobby_ref_mut(lobby_id) else { return false; };
+        let Some(lobby_ref) = self.lobby_ref_mut(lobby_id) else { return Err(HostRequestError::LobbyNotFound); };
         lobby_ref.add_member(new_member_id, member_data);
 
         tracing::trace!(lobby_id, new_member_id, ?member_data, "add lobby member");
-        true
+        Ok(())
+    }
+
+    /// Add a member to the lobby using an invite that was already validated.
+    /// - The lobby's password is passed to the lobby checker on behalf of the new member.
+    /// - Returns `Err` if unable to add member (lobby doesn't exist, member is banned, rejected by lobby checker).
+    pub fn try_add_invited_member(
+        &mut self,
+        lobby_id      : u64,
+        new_member_id : u128,
+        member_data   : LobbyMemberData,
+    ) -> Result<(), HostRequestError>
+    {
+        // check lobby exists
+        let Some(lobby_ref) = self.lobby_ref(lobby_id) else { return Err(HostRequestError::LobbyNotFound); };
+
+        // check if member is banned
+        if lobby_ref.is_banned(new_member_id) { return Err(HostRequestError::Banned); }
+
+        // check if member can be added to the lobby
+        let password = lobby_ref.get_password();
+        self.config.lobby_checker.allow_new_member(lobby_ref, new_member_id, member_data, password)?;
+
+        // add member
+        let Some(lobby_ref) = self.lobby_ref_mut(lobby_id) else { return Err(HostRequestError::LobbyNotFound); };
+        lobby_ref.add_member(new_member_id, member_data);
+
+        tracing::trace!(lobby_id, new_member_id, ?member_data, "add invited lobby member");
+        Ok(())
+    }
+
+    /// Transfer lobby ownership to another member.
+    /// - Returns `Err` if the lobby doesn't exist, the new owner is not a member, or the lobby checker rejects the
+    ///   lobby with the new owner.
+    pub fn try_transfer_ownership(&mut self, lobby_id: u64, new_owner_id: u128) -> Result<(), HostRequestError>
+    {
+        let Some(lobby_ref) = self.lobbies.get_mut(&lobby_id) else { return Err(HostRequestError::LobbyNotFound); };
+        let prev_owner_id = lobby_ref.data.owner_id;
+
+        // set the new owner
+        if !lobby_ref.set_owner(new_owner_id) { return Err(HostRequestError::InvalidMember); }
+
+        // check lobby is still valid
+        if !self.config.lobby_checker.check_lobby(lobby_ref)
+        {
+            lobby_ref.set_owner(prev_owner_id);
+            return Err(HostRequestError::Rejected);
+        }
+
+        tracing::trace!(lobby_id, prev_owner_id, new_owner_id, "transferred lobby ownership");
+        Ok(())
+    }
+
+    /// Replace a lobby's custom data.
+    /// - Returns `Err` if the lobby doesn't exist or the lobby checker rejects the lobby with the new data.
+    pub fn try_update_custom_data(&mut self, lobby_id: u64, custom_data: Vec<u8>) -> Result<(), HostRequestError>
+    {
+        let Some(lobby_ref) = self.lobbies.get_mut(&lobby_id) else { return Err(HostRequestError::LobbyNotFound); };
+
+        // set the new data
+        let prev_data = std::mem::replace(&mut lobby_ref.data.serialized_custom_data, custom_data);
+
+        // check lobby is still valid
+        if !self.config.lobby_checker.check_lobby(lobby_ref)
+        {
+            lobby_ref.data.serialized_custom_data = prev_data;
+            return Err(HostRequestError::Rejected);
+        }
+
+        tracing::trace!(lobby_id, "updated lobby custom data");
+        Ok(())
+    }
+
+    /// Try to promote a member to replace the lobby's owner, according to the owner succession policy.
+    /// - Returns the new owner's id, or `None` if the lobby should be disbanded.
+    pub fn try_promote_successor(&mut self, lobby_id: u64) -> Option<u128>
+    {
+        let lobby_ref = self.lobby_ref(lobby_id)?;
+        let candidates: Vec<u128> = match self.config.owner_succession
+        {
+            OwnerSuccession::Disband      => return None,
+            OwnerSuccession::OldestMember => lobby_ref.members_by_age().collect(),
+            OwnerSuccession::LobbyChecker => self.config.lobby_checker.select_new_owner(lobby_ref).into_iter().collect(),
+        };
+        let owner_id = lobby_ref.data.owner_id;
+
+        candidates
+            .into_iter()
+            .filter(|candidate_id| *candidate_id != owner_id)
+            .find(|candidate_id| self.try_transfer_ownership(lobby_id, *candidate_id).is_ok())
     }
 
     /// Access a specific lobby.
@@ -144,6 +247,12 @@ impl LobbiesCache
         self.config.lobby_checker.borrow()
     }
 
+    /// Get lobby searcher.
+    pub fn lobby_searcher(&self) -> Option<&dyn LobbySearcher>
+    {
+        self.config.lobby_searcher.as_deref()
+    }
+
     /// Extract a specific lobby.
     pub fn extract_lobby(&mut self, lobby_id: u64) -> Option<Lobby>
     {
@@ -157,6 +266,44 @@ impl LobbiesCache
         self.config.max_request_size
     }
 
+    /// Replace the cache config.
+    /// - Existing lobbies are not re-validated against the new config.
+    pub fn set_config(&mut self, config: LobbiesCacheConfig)
+    {
+        tracing::trace!(?config, "set lobbies cache config");
+        self.config = config;
+    }
+
+    /// Generate an id for a lobby that will be created outside this cache (e.g. by the matchmaker).
+    /// - The id will not collide with lobbies currently in this cache.
+    pub fn generate_lobby_id(&mut self) -> u64
+    {
+        self.generated_count += 1;
+        while self.lobbies.contains_key(&self.generated_count) { self.generated_count += 1; }
+
+        self.generated_count
+    }
+
+    /// Number of lobby ids generated so far.
+    pub fn generated_count(&self) -> u64
+    {
+        self.generated_count
+    }
+
+    /// Raise the number of lobby ids generated so far.
+    /// - New lobbies will have ids greater than `min_count`.
+    /// - Does nothing if `min_count` is lower than the current count.
+    pub fn raise_generated_count(&mut self, min_count: u64)
+    {
+        self.generated_count = std::cmp::max(self.generated_count, min_count);
+    }
+
+    /// Iterate over all cached lobbies.
+    pub fn lobbies(&self) -> impl Iterator<Item = &Lobby> + '_
+    {
+        self.lobbies.values()
+    }
+
     /// Get a reference to the lobby map.
     fn lobbies_ref(&self) -> &BTreeMap<u64, Lobby>
     {
@@ -166,8 +313,88 @@ impl LobbiesCache
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Private lobbies are hidden from lobby searches.
+fn is_public(entry: &(&u64, &Lobby)) -> bool
+{
+    !entry.1.is_private()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sort order of lobbies that pass a search filter (by ascending sort key, then from newest to oldest).
+fn filtered_order(cursor: &LobbySearchCursor) -> (i64, std::cmp::Reverse<u64>)
+{
+    (cursor.sort_key, std::cmp::Reverse(cursor.id))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Get a page of lobbies that pass a search filter.
+/// - Returns (lobbies, num lobbies sorted before the page, total passing lobbies, cursor of the last lobby).
+fn get_filtered_lobbies(
+    lobbies_cache : &LobbiesCache,
+    filter        : &LobbySearchFilter,
+    after         : Option<LobbySearchCursor>,
+    num           : u16,
+) -> (Vec<LobbyData>, usize, usize, Option<LobbySearchCursor>)
+{
+    // prepare the custom filter
+    // - the custom filter is only deserialized once per request
+    let custom_filter = match lobbies_cache.lobby_searcher()
+    {
+        Some(searcher) =>
+        {
+            let Some(custom_filter) = searcher.make_filter(&filter.custom)
+            else { tracing::trace!("invalid custom lobby search filter"); return (Vec::default(), 0, 0, None); };
+            Some(custom_filter)
+        }
+        None => None,
+    };
+
+    // collect passing lobbies
+    let mut passing: Vec<(LobbySearchCursor, &Lobby)> = lobbies_cache.lobbies_ref()
+        .values()
+        .filter(|lobby| !lobby.is_private())
+        .filter(|lobby| filter.min_members.is_none_or(|min| lobby.num_members() >= min as usize))
+        .filter(|lobby| filter.max_members.is_none_or(|max| lobby.num_members() <= max as usize))
+        .filter(|lobby| filter.has_password.is_none_or(|has_pwd| lobby.get_password().is_empty() != has_pwd))
+        .filter_map(
+            |lobby|
+            {
+                let sort_key = match &custom_filter
+                {
+                    Some(custom_filter) => custom_filter.sort_key(&lobby.data)?,
+                    None                => 0,
+                };
+                Some((LobbySearchCursor{ sort_key, id: lobby.data.id }, lobby))
+            }
+        )
+        .collect();
+
+    // sort by key, then from newest to oldest
+    passing.sort_unstable_by_key(|(cursor, _)| filtered_order(cursor));
+
+    // find the start of the page
+    // - the page starts after the cursor, even if the cursor's lobby no longer exists
+    let total = passing.len();
+    let start = after.map_or(0,
+            |after| passing.partition_point(|(cursor, _)| filtered_order(cursor) <= filtered_order(&after))
+        );
+
+    // collect the lobbies for this page
+    let num  = std::cmp::min(num, lobbies_cache.max_request_size()) as usize;
+    let page = &passing[start..std::cmp::min(start + num, total)];
+    let next_cursor = page.last().map(|(cursor, _)| *cursor);
+    let result = page.iter().map(|(_, lobby)| lobby.data.clone()).collect();
+
+    (result, start, total, next_cursor)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Get requested lobbies.
 /// - The returned lobbies are sorted from newest to oldest.
+/// - Private lobbies are excluded.
 pub fn get_searched_lobbies(lobbies_cache: &LobbiesCache, req: LobbySearchRequest) -> LobbySearchResult
 {
     tracing::trace!(?req, "get searched lobbies from LobbiesCache");
@@ -176,10 +403,10 @@ pub fn get_searched_lobbies(lobbies_cache: &LobbiesCache, req: LobbySearchReques
         LobbySearchRequest::LobbyId(id) =>
         'r: {
             // count the number of lobbies younger than the requested lobby
-            let num_younger = lobbies_cache.lobbies_ref().range((Excluded(&id), Unbounded)).count();
+            let num_younger = lobbies_cache.lobbies_ref().range((Excluded(&id), Unbounded)).filter(is_public).count();
 
             // get the lobby if it exists
-            let Some(lobby_ref) = lobbies_cache.lobby_ref(id)
+            let Some(lobby_ref) = lobbies_cache.lobby_ref(id).filter(|lobby| !lobby.is_private())
             else { break 'r (Vec::default(), num_younger); };
 
             (
@@ -193,7 +420,7 @@ pub fn get_searched_lobbies(lobbies_cache: &LobbiesCache, req: LobbySearchReques
             num = std::cmp::min(num, lobbies_cache.max_request_size());
 
             // iterate up from our start lobby (i.e. toward newer lobbies)
-            let mut page_it = lobbies_cache.lobbies_ref().range((Included(&oldest_id), Unbounded));
+            let mut page_it = lobbies_cache.lobbies_ref().range((Included(&oldest_id), Unbounded)).filter(is_public);
 
             // collect the lobbies for this page
             let mut result = Vec::with_capacity(num as usize);
@@ -216,16 +443,25 @@ pub fn get_searched_lobbies(lobbies_cache: &LobbiesCache, req: LobbySearchReques
 
             (
                 result,
-                lobbies_cache.lobbies_ref().range((Excluded(&counter_id), Unbounded)).count()
+                lobbies_cache.lobbies_ref().range((Excluded(&counter_id), Unbounded)).filter(is_public).count()
             )
         }
+        LobbySearchRequest::Filtered{ ref filter, after, num } =>
+        {
+            let (lobbies, num_younger, total, next_cursor) = get_filtered_lobbies(lobbies_cache, filter, after, num);
+
+            return LobbySearchResult{ req, lobbies, num_younger, total, next_cursor };
+        }
         LobbySearchRequest::PageOlder{ youngest_id, mut num } =>
         {
             // clamp the number of lobbies requested
             num = std::cmp::min(num, lobbies_cache.max_request_size());
 
             // iterate down from our start lobby (i.e. toward older lobbies)
-            let mut page_reverse_it = lobbies_cache.lobbies_ref().range((Unbounded, Included(&youngest_id))).rev();
+            let mut page_reverse_it = lobbies_cache.lobbies_ref()
+                .range((Unbounded, Included(&youngest_id)))
+                .rev()
+                .filter(is_public);
 
             // collect the lobbies for this page
             let mut result = Vec::with_capacity(num as usize);
@@ -245,7 +481,7 @@ pub fn get_searched_lobbies(lobbies_cache: &LobbiesCache, req: LobbySearchReques
 
             (
                 result,
-                lobbies_cache.lobbies_ref().range((Excluded(&counter_id), Unbounded)).count()
+                lobbies_cache.lobbies_ref().range((Excluded(&counter_id), Unbounded)).filter(is_public).count()
             )
         }
     };
@@ -254,7 +490,8 @@ pub fn get_searched_lobbies(lobbies_cache: &LobbiesCache, req: LobbySearchReques
             req,
             lobbies,
             num_younger,
-            total: lobbies_cache.lobbies_ref().len(),
+            total       : lobbies_cache.lobbies_ref().iter().filter(is_public).count(),
+            next_cursor : None,
         }
 }
 
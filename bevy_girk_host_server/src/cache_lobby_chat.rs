@@ -0,0 +1,102 @@
+//local shortcuts
+use bevy_girk_backend_public::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Filters lobby chat messages before they are relayed to lobby members.
+pub trait LobbyChatFilter: Debug + Send + Sync
+{
+    /// Filter a chat message.
+    /// - Returns `None` if the message should be dropped.
+    /// - Returns the text to relay otherwise (e.g. with profanity removed).
+    fn filter(&self, sender_id: u128, lobby: &LobbyData, text: String) -> Option<String>;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct LobbyChatConfig
+{
+    /// Max length of a chat message in bytes.
+    pub max_message_len: usize,
+    /// Max number of chat messages a user may send within each rate limit period.
+    pub max_messages_per_period: u16,
+    /// Duration of the rate limit period.
+    pub rate_limit_period: Duration,
+
+    /// Chat filter (optional).
+    pub filter: Option<Box<dyn LobbyChatFilter>>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks recent lobby chat messages for rate limiting.
+#[derive(Resource, Debug)]
+pub struct LobbyChatCache
+{
+    /// config
+    config: LobbyChatConfig,
+    /// cache timer
+    timer: Instant,
+    /// [ user id : [ message timestamps ] ]
+    /// - timestamps are sorted from oldest to youngest
+    history: HashMap<u128, VecDeque<Duration>>,
+}
+
+impl LobbyChatCache
+{
+    /// Make a new cache.
+    pub fn new(config: LobbyChatConfig) -> LobbyChatCache
+    {
+        LobbyChatCache{ config, timer: Instant::now(), history: HashMap::default() }
+    }
+
+    /// Try to record a new chat message from a user.
+    /// - Returns `false` if the user has sent too many messages recently.
+    pub fn try_record_message(&mut self, user_id: u128) -> bool
+    {
+        let current_time   = self.timer.elapsed();
+        let min_birth_time = current_time.saturating_sub(self.config.rate_limit_period);
+        let timestamps     = self.history.entry(user_id).or_default();
+
+        // discard timestamps outside the rate limit period
+        while timestamps.front().is_some_and(|birth_time| *birth_time < min_birth_time)
+        {
+            timestamps.pop_front();
+        }
+
+        // check the rate limit
+        if timestamps.len() >= self.config.max_messages_per_period as usize
+        { tracing::trace!(user_id, "lobby chat message rate limited"); return false; }
+
+        timestamps.push_back(current_time);
+        true
+    }
+
+    /// Filter a chat message.
+    /// - Returns `None` if the message is too long or rejected by the chat filter.
+    pub fn filter_message(&self, sender_id: u128, lobby: &LobbyData, text: String) -> Option<String>
+    {
+        if text.len() > self.config.max_message_len
+        { tracing::trace!(sender_id, lobby.id, "lobby chat message too long"); return None; }
+
+        let Some(filter) = &self.config.filter else { return Some(text); };
+        filter.filter(sender_id, lobby, text)
+    }
+
+    /// Remove a user's chat history.
+    pub fn remove_user(&mut self, user_id: u128)
+    {
+        self.history.remove(&user_id);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,169 @@
+//local shortcuts
+use bevy_girk_backend_public::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Characters used in invite codes (ambiguous characters are omitted).
+const INVITE_CODE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn gen_invite_code(len: usize) -> String
+{
+    let mut rand = gen_rand128();
+    let mut code = String::with_capacity(len);
+
+    for _ in 0..len
+    {
+        if rand == 0 { rand = gen_rand128(); }
+        code.push(INVITE_CODE_CHARS[(rand % INVITE_CODE_CHARS.len() as u128) as usize] as char);
+        rand /= INVITE_CODE_CHARS.len() as u128;
+    }
+
+    code
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct LobbyInvitesConfig
+{
+    /// Amount of time an invite code is valid.
+    pub invite_duration: Duration,
+    /// Number of characters in each invite code.
+    pub code_len: usize,
+    /// Max number of invite codes that may exist for a lobby at once.
+    pub max_invites_per_lobby: usize,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct LobbyInvite
+{
+    /// the invited lobby
+    lobby_id: u64,
+    /// whether the invite is consumed when used
+    single_use: bool,
+    /// registration timestamp
+    birth_time: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks invite codes for lobbies.
+#[derive(Resource, Debug)]
+pub struct LobbyInvitesCache
+{
+    /// config
+    config: LobbyInvitesConfig,
+    /// cache timer
+    timer: Instant,
+    /// [ invite code : invite ]
+    invites: HashMap<String, LobbyInvite>,
+}
+
+impl LobbyInvitesCache
+{
+    /// Make a new cache.
+    pub fn new(config: LobbyInvitesConfig) -> LobbyInvitesCache
+    {
+        LobbyInvitesCache{ config, timer: Instant::now(), invites: HashMap::default() }
+    }
+
+    /// Make a new invite code for a lobby.
+    /// - Returns `Err` if the lobby has too many invites.
+    pub fn new_invite(&mut self, lobby_id: u64, single_use: bool) -> Result<String, HostRequestError>
+    {
+        tracing::trace!(lobby_id, single_use, "new lobby invite");
+
+        // check the lobby's invite limit
+        let current_time = self.timer.elapsed();
+        let num_invites = self.invites
+            .values()
+            .filter(|invite| invite.lobby_id == lobby_id && !self.is_expired(invite, current_time))
+            .count();
+        if num_invites >= self.config.max_invites_per_lobby
+        { tracing::debug!(lobby_id, "lobby has too many invites"); return Err(HostRequestError::LimitReached); }
+
+        // generate a unique code
+        let mut code = gen_invite_code(self.config.code_len);
+        while self.invites.contains_key(&code) { code = gen_invite_code(self.config.code_len); }
+
+        self.invites.insert(code.clone(), LobbyInvite{ lobby_id, single_use, birth_time: current_time });
+
+        Ok(code)
+    }
+
+    /// Revoke an invite code.
+    /// - Returns `Err` if the invite doesn't exist or is for a different lobby.
+    pub fn revoke_invite(&mut self, lobby_id: u64, code: &str) -> Result<(), HostRequestError>
+    {
+        tracing::trace!(lobby_id, code, "revoke lobby invite");
+        if !self.invites.get(code).is_some_and(|invite| invite.lobby_id == lobby_id)
+        { return Err(HostRequestError::InvalidInvite); }
+        self.invites.remove(code);
+
+        Ok(())
+    }
+
+    /// Revoke all invite codes for a lobby.
+    pub fn revoke_lobby_invites(&mut self, lobby_id: u64)
+    {
+        self.invites.retain(|_, invite| invite.lobby_id != lobby_id);
+    }
+
+    /// Get the lobby an invite code is for.
+    /// - Returns `None` if the invite doesn't exist or expired.
+    pub fn invite_lobby(&self, code: &str) -> Option<u64>
+    {
+        let invite = self.invites.get(code)?;
+        if self.is_expired(invite, self.timer.elapsed()) { return None; }
+
+        Some(invite.lobby_id)
+    }
+
+    /// Consume an invite code after it was used successfully.
+    /// - Single-use invites are removed.
+    pub fn consume_invite(&mut self, code: &str)
+    {
+        if !self.invites.get(code).is_some_and(|invite| invite.single_use) { return; }
+        tracing::trace!(code, "consumed single-use lobby invite");
+        self.invites.remove(code);
+    }
+
+    /// Amount of time an invite code is valid.
+    pub fn invite_duration(&self) -> Duration
+    {
+        self.config.invite_duration
+    }
+
+    /// Current number of invites.
+    pub fn num_invites(&self) -> usize
+    {
+        self.invites.len()
+    }
+
+    /// Remove expired invites.
+    pub fn clear_expired(&mut self)
+    {
+        let current_time    = self.timer.elapsed();
+        let invite_duration = self.config.invite_duration;
+        self.invites.retain(|_, invite| invite.birth_time.saturating_add(invite_duration) >= current_time);
+    }
+
+    fn is_expired(&self, invite: &LobbyInvite, current_time: Duration) -> bool
+    {
+        invite.birth_time.saturating_add(self.config.invite_duration) < current_time
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
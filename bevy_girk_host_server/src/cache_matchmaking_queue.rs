@@ -0,0 +1,173 @@
+//local shortcuts
+use bevy_girk_backend_public::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct MatchmakingQueueConfig
+{
+    /// Max number of users that can wait in the queue at once.
+    pub max_queue_size: usize,
+    /// Number of ticks to wait between each matchmaking pass.
+    pub matchmaking_period_ticks: u64,
+
+    /// Matchmaker.
+    ///
+    /// Used to group queued users into new lobbies.
+    pub matchmaker: Box<dyn Matchmaker>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks users waiting to be matched into lobbies.
+#[derive(Resource)]
+pub struct MatchmakingQueueCache
+{
+    /// config
+    config: MatchmakingQueueConfig,
+    /// cache timer
+    timer: Instant,
+    /// [ (queued user, registration timestamp) ]
+    /// - sorted from oldest to youngest
+    queue: Vec<(QueuedUser, Duration)>,
+}
+
+impl MatchmakingQueueCache
+{
+    /// Make a new cache.
+    pub fn new(config: MatchmakingQueueConfig) -> MatchmakingQueueCache
+    {
+        MatchmakingQueueCache{ config, timer: Instant::now(), queue: Vec::default() }
+    }
+
+    /// Add a user to the queue.
+    /// - Returns `Err` if the user is already queued or the queue is full.
+    pub fn add_user(
+        &mut self,
+        user_id     : u128,
+        member_data : LobbyMemberData,
+        custom_data : Vec<u8>,
+    ) -> Result<(), HostRequestError>
+    {
+        tracing::trace!(user_id, "add queued user");
+
+        if self.has_user(user_id)
+        { tracing::debug!(user_id, "user is already queued"); return Err(HostRequestError::NotIdle); }
+        if self.queue.len() >= self.config.max_queue_size
+        { tracing::debug!(user_id, "matchmaking queue is full"); return Err(HostRequestError::LimitReached); }
+
+        let queued_user = QueuedUser{
+            user_id,
+            member_data,
+            serialized_custom_data: custom_data,
+            wait_time: Duration::default(),
+        };
+        self.queue.push((queued_user, self.timer.elapsed()));
+
+        Ok(())
+    }
+
+    /// Remove a user from the queue.
+    /// - Returns `Err` if the user is not queued.
+    pub fn remove_user(&mut self, user_id: u128) -> Result<QueuedUser, ()>
+    {
+        tracing::trace!(user_id, "remove queued user");
+
+        let Some(idx) = self.queue.iter().position(|(queued, _)| queued.user_id == user_id)
+        else { return Err(()); };
+
+        Ok(self.queue.remove(idx).0)
+    }
+
+    /// Check if a user is queued.
+    pub fn has_user(&self, user_id: u128) -> bool
+    {
+        self.queue.iter().any(|(queued, _)| queued.user_id == user_id)
+    }
+
+    /// Access a queued user.
+    pub fn queued_user(&self, user_id: u128) -> Option<&QueuedUser>
+    {
+        self.queue.iter().find(|(queued, _)| queued.user_id == user_id).map(|(queued, _)| queued)
+    }
+
+    /// Current number of queued users.
+    pub fn num_queued(&self) -> usize
+    {
+        self.queue.len()
+    }
+
+    /// Number of ticks to wait between each matchmaking pass.
+    pub fn matchmaking_period_ticks(&self) -> u64
+    {
+        self.config.matchmaking_period_ticks
+    }
+
+    /// Get matchmaker.
+    pub fn matchmaker(&self) -> &dyn Matchmaker
+    {
+        self.config.matchmaker.borrow()
+    }
+
+    /// Ask the matchmaker for the next match.
+    /// - Users in `skipped` are hidden from the matchmaker.
+    /// - If the matchmaker returns an invalid match, the queued users in that match are added to `skipped` and the
+    ///   matchmaker is asked again.
+    /// - Returns `None` if the matchmaker could not find a match, or if it selected users who aren't queued.
+    /// - Duplicate members are removed from the match.
+    /// - Matched users are NOT removed from the queue.
+    pub fn try_next_match(&mut self, skipped: &mut HashSet<u128>) -> Option<MatchmakerMatch>
+    {
+        // refresh wait times
+        let current_time = self.timer.elapsed();
+        for (queued, birth_time) in self.queue.iter_mut()
+        {
+            queued.wait_time = current_time.saturating_sub(*birth_time);
+        }
+
+        loop
+        {
+            let queued: Vec<QueuedUser> = self.queue
+                .iter()
+                .filter(|(queued, _)| !skipped.contains(&queued.user_id))
+                .map(|(queued, _)| queued.clone())
+                .collect();
+            if queued.is_empty() { return None; }
+
+            // try to make a match
+            let mut matched = self.config.matchmaker.try_make_match(&queued)?;
+
+            // remove duplicate members
+            let mut members = HashSet::with_capacity(matched.members.len());
+            matched.members.retain(|user_id| members.insert(*user_id));
+
+            // validate the match
+            let error = if !members.contains(&matched.owner_id)
+            {
+                "discarding match, owner is not a member"
+            }
+            else if matched.members.iter().any(|user_id| !queued.iter().any(|queued| queued.user_id == *user_id))
+            {
+                "discarding match, member is not queued"
+            }
+            else { return Some(matched); };
+            tracing::warn!(?matched, "{error}");
+
+            // skip the match's queued users so the matchmaker can't select them again this pass
+            // - if none of the matched users are available then the matchmaker is misbehaving
+            let num_skipped = skipped.len();
+            skipped.extend(members.iter().filter(|user_id| queued.iter().any(|queued| queued.user_id == **user_id)));
+            if skipped.len() == num_skipped { return None; }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
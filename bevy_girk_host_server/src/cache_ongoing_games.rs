@@ -183,6 +183,12 @@ impl OngoingGamesCache
         Some(&ongoing_game.start_infos)
     }
 
+    /// Iterate over all ongoing games.
+    pub fn games(&self) -> impl Iterator<Item = &OngoingGame> + '_
+    {
+        self.games.values().map(|(ongoing_game, _)| ongoing_game)
+    }
+
     /// Drain expired games.
     /// - Iterates over all ongoing games (may be inefficient).
     pub fn drain_expired(&mut self) -> impl IntoIterator<Item = OngoingGame> + '_
@@ -22,13 +22,30 @@ pub struct PendingLobbiesConfig
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Policy for retrying game starts that are aborted by game hubs.
+///
+/// When a hub aborts a pending game, the game is re-requested on a hub that has not failed to start it yet. The
+/// pending lobby is aborted once `max_attempts` hubs have failed or no other hubs are available.
+#[derive(Resource, Debug, Copy, Clone)]
+pub struct GameStartRetryPolicy
+{
+    /// Maximum number of game hubs that may be asked to start a lobby's game.
+    pub max_attempts: u16,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug)]
 struct PendingLobby
 {
     /// the pending lobby
     lobby: Lobby,
     /// [ ids of acked users ]
-    user_acks: HashSet<u128>
+    user_acks: HashSet<u128>,
+    /// [ ids of game hubs that failed to start the lobby's game ]
+    failed_hubs: Vec<u128>,
+    /// the lobby is waiting in the game launch queue
+    queued: bool,
 }
 
 impl PendingLobby
@@ -38,7 +55,9 @@ impl PendingLobby
     {
         PendingLobby{
                 lobby,
-                user_acks: HashSet::default(),
+                user_acks   : HashSet::default(),
+                failed_hubs : Vec::default(),
+                queued      : false,
             }
     }
 
@@ -162,9 +181,70 @@ impl PendingLobbiesCache
         Some(&pending_lobby.lobby.data)
     }
 
+    /// Record that a game hub failed to start a pending lobby's game.
+    /// - returns `Err(())` if the lobby isn't registered
+    pub fn add_failed_hub(&mut self, lobby_id: u64, hub_id: u128) -> Result<(), ()>
+    {
+        tracing::trace!(lobby_id, hub_id, "add failed hub");
+        let Some((pending_lobby, _)) = self.pending_lobbies.get_mut(&lobby_id)
+        else { tracing::debug!(lobby_id, hub_id, "lobby missing for failed hub"); return Err(()); };
+
+        if !pending_lobby.failed_hubs.contains(&hub_id) { pending_lobby.failed_hubs.push(hub_id); }
+
+        Ok(())
+    }
+
+    /// Access the game hubs that failed to start a pending lobby's game.
+    /// - returns an empty slice if the lobby isn't registered
+    pub fn failed_hubs(&self, lobby_id: u64) -> &[u128]
+    {
+        self.pending_lobbies.get(&lobby_id).map(|(pending_lobby, _)| pending_lobby.failed_hubs.as_slice()).unwrap_or(&[])
+    }
+
+    /// Mark a pending lobby as waiting in the game launch queue.
+    /// - Queued lobbies don't expire.
+    /// - When a lobby leaves the queue, its game-start buffer restarts.
+    /// - returns `Err(())` if the lobby isn't registered
+    pub fn set_lobby_queued(&mut self, lobby_id: u64, queued: bool) -> Result<(), ()>
+    {
+        tracing::trace!(lobby_id, queued, "set lobby queued");
+        let current_timestamp = self.timer.elapsed();
+        let Some((pending_lobby, birth_time)) = self.pending_lobbies.get_mut(&lobby_id)
+        else { tracing::debug!(lobby_id, "lobby missing for set queued"); return Err(()); };
+
+        if pending_lobby.queued && !queued
+        {
+            *birth_time = current_timestamp.saturating_sub(self.config.ack_timeout);
+        }
+        pending_lobby.queued = queued;
+
+        Ok(())
+    }
+
+    /// Access a pending lobby.
+    pub fn lobby_ref(&self, lobby_id: u64) -> Option<&Lobby>
+    {
+        self.pending_lobbies.get(&lobby_id).map(|(pending_lobby, _)| &pending_lobby.lobby)
+    }
+
+    /// Replace the cache config.
+    /// - The new timeouts apply to existing pending lobbies.
+    pub fn set_config(&mut self, config: PendingLobbiesConfig)
+    {
+        tracing::trace!(?config, "set pending lobbies cache config");
+        self.config = config;
+    }
+
+    /// Iterate over all pending lobbies.
+    pub fn lobbies(&self) -> impl Iterator<Item = &Lobby> + '_
+    {
+        self.pending_lobbies.values().map(|(pending_lobby, _)| &pending_lobby.lobby)
+    }
+
     /// Drain expired pending lobbies.
     /// - if lobby reached ack timeout and insufficient acks
     /// - if lobby has acks but reached the end of the game-start buffer
+    /// - lobbies waiting in the game launch queue are ignored
     pub fn drain_expired(&mut self) -> impl IntoIterator<Item = Lobby> + '_
     {
         let current_timestamp = self.timer.elapsed();
@@ -176,6 +256,9 @@ impl PendingLobbiesCache
         self.pending_lobbies.retain(
             |lobby_id, (pending_lobby, birth_time)| -> bool
             {
+                // keep: if lobby is waiting in the game launch queue
+                if pending_lobby.queued { return true; }
+
                 // remove: if lobby has exceeded max lifetime
                 if birth_time.saturating_add(max_lifetime) < current_timestamp
                 {
@@ -0,0 +1,297 @@
+//local shortcuts
+use bevy_girk_backend_public::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Categories of user requests and messages that are rate limited separately.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum UserRequestCategory
+{
+    /// [`UserToHostRequest::LobbySearch`].
+    Search,
+    /// Requests that create, join, leave, or modify lobbies, and matchmaking queue requests.
+    Lobby,
+    /// Requests that launch games or connect to games.
+    Game,
+    /// [`UserToHostMsg::LobbyChat`].
+    Message,
+}
+
+impl UserRequestCategory
+{
+    /// Get the category of a user request.
+    pub fn from_request(request: &UserToHostRequest) -> Self
+    {
+        match request
+        {
+            UserToHostRequest::LobbySearch(_)            => Self::Search,
+            UserToHostRequest::MakeLobby{ .. }           |
+            UserToHostRequest::JoinLobby{ .. }           |
+            UserToHostRequest::JoinLobbyWithInvite{ .. } |
+            UserToHostRequest::LeaveLobby{ .. }          |
+            UserToHostRequest::KickMember{ .. }          |
+            UserToHostRequest::TransferOwnership{ .. }   |
+            UserToHostRequest::UpdateLobbyData{ .. }     |
+            UserToHostRequest::SetLobbyPrivate{ .. }     |
+            UserToHostRequest::MakeLobbyInvite{ .. }     |
+            UserToHostRequest::RevokeLobbyInvite{ .. }   |
+            UserToHostRequest::EnterQueue{ .. }          |
+            UserToHostRequest::LeaveQueue                => Self::Lobby,
+            UserToHostRequest::LaunchLobbyGame{ .. }     |
+            UserToHostRequest::GetConnectToken{ .. }     => Self::Game,
+        }
+    }
+
+    /// Get the category of a user message.
+    /// - Returns `None` for messages that are not rate limited. Pending lobby acks/nacks are exempt so a user's
+    ///   chat can't prevent their games from starting.
+    pub fn from_msg(msg: &UserToHostMsg) -> Option<Self>
+    {
+        match msg
+        {
+            UserToHostMsg::AckPendingLobby{ .. }  |
+            UserToHostMsg::NackPendingLobby{ .. } => None,
+            UserToHostMsg::LobbyChat{ .. }        => Some(Self::Message),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Token bucket config.
+#[derive(Debug, Copy, Clone)]
+pub struct TokenBucketConfig
+{
+    /// Max number of tokens in the bucket (i.e. the max burst size).
+    pub capacity: u32,
+    /// Amount of time it takes to refill one token.
+    pub refill_period: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Per-user rate limits for requests and messages.
+///
+/// Each user has one token bucket per [`UserRequestCategory`]. Requests that arrive when the bucket is empty are
+/// rejected with [`HostRequestError::RateLimited`] (messages are dropped). Users that repeatedly exceed their limits
+/// are disconnected.
+///
+/// Rate limits of disconnected users are retained until their buckets would have refilled and their violations would
+/// have expired, so users can't reset their limits by reconnecting.
+#[derive(Debug, Copy, Clone)]
+pub struct UserRateLimitConfig
+{
+    /// Limit for [`UserRequestCategory::Search`].
+    pub search: TokenBucketConfig,
+    /// Limit for [`UserRequestCategory::Lobby`].
+    pub lobby: TokenBucketConfig,
+    /// Limit for [`UserRequestCategory::Game`].
+    pub game: TokenBucketConfig,
+    /// Limit for [`UserRequestCategory::Message`].
+    pub message: TokenBucketConfig,
+
+    /// Max number of rate limit violations allowed within the violation window.
+    /// - A user will be disconnected when they exceed this.
+    pub max_violations: u32,
+    /// Duration of the violation window.
+    pub violation_window: Duration,
+}
+
+impl UserRateLimitConfig
+{
+    /// Amount of time after which a user's rate limit state is equivalent to a fresh state.
+    fn retention_duration(&self) -> Duration
+    {
+        [self.search, self.lobby, self.game, self.message]
+            .iter()
+            .map(|bucket| bucket.refill_period.saturating_mul(bucket.capacity))
+            .fold(self.violation_window, Duration::max)
+    }
+
+    fn bucket_config(&self, category: UserRequestCategory) -> TokenBucketConfig
+    {
+        match category
+        {
+            UserRequestCategory::Search  => self.search,
+            UserRequestCategory::Lobby   => self.lobby,
+            UserRequestCategory::Game    => self.game,
+            UserRequestCategory::Message => self.message,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Result of recording a rate-limited user request.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum UserRateLimitResult
+{
+    /// The request is allowed.
+    Allowed,
+    /// The request should be rejected.
+    Limited,
+    /// The request should be rejected and the user should be disconnected.
+    Disconnect,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct TokenBucket
+{
+    /// current number of tokens
+    tokens: u32,
+    /// last time the bucket was refilled
+    last_refill: Duration,
+}
+
+impl TokenBucket
+{
+    fn new(config: &TokenBucketConfig, current_time: Duration) -> Self
+    {
+        Self{ tokens: config.capacity, last_refill: current_time }
+    }
+
+    fn try_consume(&mut self, config: &TokenBucketConfig, current_time: Duration) -> bool
+    {
+        // refill the bucket
+        let elapsed = current_time.saturating_sub(self.last_refill);
+        let refill_nanos = config.refill_period.as_nanos().max(1);
+        let num_refills = (elapsed.as_nanos() / refill_nanos).min(config.capacity as u128) as u32;
+
+        if self.tokens.saturating_add(num_refills) >= config.capacity
+        {
+            self.tokens = config.capacity;
+            self.last_refill = current_time;
+        }
+        else if num_refills > 0
+        {
+            self.tokens += num_refills;
+            self.last_refill += config.refill_period * num_refills;
+        }
+
+        // consume a token
+        if self.tokens == 0 { return false; }
+        self.tokens -= 1;
+        true
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct UserRateLimits
+{
+    /// [ category : bucket ]
+    buckets: HashMap<UserRequestCategory, TokenBucket>,
+    /// rate limit violation timestamps
+    /// - timestamps are sorted from oldest to youngest
+    violations: VecDeque<Duration>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks per-user rate limits.
+#[derive(Resource, Debug)]
+pub struct UserRateLimitsCache
+{
+    /// config
+    config: UserRateLimitConfig,
+    /// cache timer
+    timer: Instant,
+    /// [ user id : rate limits ]
+    users: HashMap<u128, UserRateLimits>,
+    /// disconnect buffer
+    /// [ user id : (disconnect time, rate limits) ]
+    disconnected: HashMap<u128, (Duration, UserRateLimits)>,
+}
+
+impl UserRateLimitsCache
+{
+    /// Make a new cache.
+    pub fn new(config: UserRateLimitConfig) -> UserRateLimitsCache
+    {
+        UserRateLimitsCache{
+                config,
+                timer        : Instant::now(),
+                users        : HashMap::default(),
+                disconnected : HashMap::default(),
+            }
+    }
+
+    /// Record a request from a user.
+    pub fn record_request(&mut self, user_id: u128, category: UserRequestCategory) -> UserRateLimitResult
+    {
+        let current_time  = self.timer.elapsed();
+        let bucket_config = self.config.bucket_config(category);
+
+        // reclaim the user's rate limits if they reconnected
+        if let Some((_, user)) = self.disconnected.remove(&user_id)
+        {
+            tracing::trace!(user_id, "reclaiming rate limits of reconnected user");
+            self.users.insert(user_id, user);
+        }
+        let user = self.users.entry(user_id).or_default();
+
+        // consume a token
+        let bucket = user.buckets
+            .entry(category)
+            .or_insert_with(|| TokenBucket::new(&bucket_config, current_time));
+        if bucket.try_consume(&bucket_config, current_time) { return UserRateLimitResult::Allowed; }
+        tracing::debug!(user_id, ?category, "user request rate limited");
+
+        // record the violation
+        let min_birth_time = current_time.saturating_sub(self.config.violation_window);
+        while user.violations.front().is_some_and(|birth_time| *birth_time < min_birth_time)
+        {
+            user.violations.pop_front();
+        }
+        user.violations.push_back(current_time);
+
+        if user.violations.len() > self.config.max_violations as usize
+        {
+            tracing::debug!(user_id, "user exceeded max rate limit violations");
+            return UserRateLimitResult::Disconnect;
+        }
+
+        UserRateLimitResult::Limited
+    }
+
+    /// Buffer a disconnected user's rate limits.
+    /// - The rate limits will be reclaimed if the user reconnects before they expire.
+    pub fn disconnect_user(&mut self, user_id: u128)
+    {
+        let Some(user) = self.users.remove(&user_id) else { return; };
+        self.disconnected.insert(user_id, (self.timer.elapsed(), user));
+    }
+
+    /// Remove a user's rate limits.
+    pub fn remove_user(&mut self, user_id: u128)
+    {
+        self.users.remove(&user_id);
+        self.disconnected.remove(&user_id);
+    }
+
+    /// Check if the cache has rate limits for a user (connected or disconnected).
+    pub fn has_user(&self, user_id: u128) -> bool
+    {
+        self.users.contains_key(&user_id) || self.disconnected.contains_key(&user_id)
+    }
+
+    /// Remove buffered rate limits of disconnected users that have expired.
+    /// - iterates over all buffered users (may be inefficient)
+    pub fn clean_disconnected(&mut self)
+    {
+        let min_disconnect_time = self.timer.elapsed().saturating_sub(self.config.retention_duration());
+        self.disconnected.retain(|_, (disconnect_time, _)| *disconnect_time >= min_disconnect_time);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -18,13 +18,22 @@ pub struct UserInfo
     connection: ConnectionType,
     /// this user's state
     user_state: UserState,
+    /// this user's account id (set if the user was authenticated)
+    account_id: Option<u128>,
 }
 
 impl UserInfo
 {
     pub fn new(env_type: bevy_simplenet::EnvType, connection: ConnectionType) -> Self
     {
-        Self{ env_type, connection, user_state: UserState::default() }
+        Self{ env_type, connection, user_state: UserState::default(), account_id: None }
+    }
+
+    /// Set the user's account id.
+    pub fn with_account_id(mut self, account_id: Option<u128>) -> Self
+    {
+        self.account_id = account_id;
+        self
     }
 
     pub fn env_type(&self) -> bevy_simplenet::EnvType
@@ -42,6 +51,13 @@ impl UserInfo
         self.user_state
     }
 
+    /// The user's stable account id.
+    /// - Returns `None` if the host server doesn't have a user authenticator.
+    pub fn account_id(&self) -> Option<u128>
+    {
+        self.account_id
+    }
+
     /// Constructs self for testing.
     pub fn test() -> Self
     {
@@ -49,6 +65,7 @@ impl UserInfo
             env_type: bevy_simplenet::EnvType::Native,
             connection: ConnectionType::Native,
             user_state: UserState::default(),
+            account_id: None,
         }
     }
 }
@@ -60,6 +77,7 @@ pub enum UserState
 {
     #[default]
     Idle,
+    InQueue,
     InLobby(u64),
     InPendingLobby(u64),
     InGame(u64),
@@ -109,6 +127,36 @@ impl UsersCache
         Ok(())
     }
 
+    /// Update a user's connection details.
+    /// - Used when a user reconnects to a registered user entry (e.g. one restored from a snapshot).
+    /// - The account id is replaced with the one resolved when the new connection was authenticated.
+    /// - Returns `Err(())` if the user does not exist.
+    pub fn update_user_connection(
+        &mut self,
+        user_id    : u128,
+        env_type   : bevy_simplenet::EnvType,
+        connection : ConnectionType,
+        account_id : Option<u128>,
+    ) -> Result<(), ()>
+    {
+        tracing::trace!(user_id, ?env_type, ?connection, ?account_id, "update user connection");
+
+        // try to access the target user
+        let Some(user) = self.users.get_mut(&user_id)
+        else { tracing::error!(user_id, "tried to update user that doesn't exist"); return Err(()); };
+
+        // update the connection
+        user.env_type   = env_type;
+        user.connection = match connection
+        {
+            ConnectionType::Memory => ConnectionType::Native,
+            connection             => connection,
+        };
+        user.account_id = account_id;
+
+        Ok(())
+    }
+
     /// Update user to have a new state.
     /// - Returns `Err(())` if the user does not exist.
     pub fn update_user_state(&mut self, user_id: u128, new_state: UserState) -> Result<(), ()>
@@ -160,6 +208,12 @@ impl UsersCache
     {
         self.users.contains_key(&user_id)
     }
+
+    /// Iterate over all users.
+    pub fn users(&self) -> impl Iterator<Item = (u128, &UserInfo)> + '_
+    {
+        self.users.iter().map(|(user_id, user_info)| (*user_id, user_info))
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -38,11 +38,34 @@ pub enum HubToHostMsg
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Metadata a game hub advertises to the host server when it connects.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct GameHubMetadata
+{
+    /// The region the hub is running in (e.g. `"us-east"`).
+    pub region: Option<String>,
+    /// Tags describing the hub (e.g. hardware or game-mode support).
+    pub tags: Vec<String>,
+    /// Custom metadata.
+    pub custom: Vec<u8>,
+}
+
+impl GameHubMetadata
+{
+    /// Check if the metadata has a tag.
+    pub fn has_tag(&self, tag: &str) -> bool
+    {
+        self.tags.iter().any(|t| t == tag)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug, Clone)]
 pub struct HostHubChannel;
 impl bevy_simplenet::ChannelPack for HostHubChannel
 {
-    type ConnectMsg = ();
+    type ConnectMsg = GameHubMetadata;
     type ServerMsg = HostToHubMsg;
     type ServerResponse = ();
     type ClientMsg = HubToHostMsg;
@@ -53,7 +76,7 @@ impl bevy_simplenet::ChannelPack for HostHubChannel
 
 /// SERVER
 pub type HostHubServer       = bevy_simplenet::Server<HostHubChannel>;
-pub type HostHubServerReport = bevy_simplenet::ServerReport<()>;
+pub type HostHubServerReport = bevy_simplenet::ServerReport<GameHubMetadata>;
 pub type HostHubServerEvent  = bevy_simplenet::ServerEventFrom<HostHubChannel>;
 
 /// server factory
@@ -15,11 +15,13 @@ pub(crate) fn clean_pending_lobbies(
     mut pending_lobbies_cache : ResMut<PendingLobbiesCache>,
     mut lobbies_cache         : ResMut<LobbiesCache>,
     mut users_cache           : ResMut<UsersCache>,
+    mut metrics               : Option<ResMut<HostServerMetrics>>,
     user_server               : Res<HostUserServer>,
 ){
     for lobby in pending_lobbies_cache.drain_expired()
     {
         tracing::trace!(lobby.data.id, "removing expired pending lobby");
+        if let Some(metrics) = &mut metrics { metrics.pending_lobby_expirations += 1; }
 
         // handle ack failure
         handle_ack_failure(lobby, &mut lobbies_cache, &mut users_cache, &user_server);
@@ -34,6 +36,7 @@ pub(crate) fn clean_ongoing_games(
     mut game_hubs_cache     : ResMut<GameHubsCache>,
     mut ongoing_games_cache : ResMut<OngoingGamesCache>,
     mut users_cache         : ResMut<UsersCache>,
+    mut metrics             : Option<ResMut<HostServerMetrics>>,
     hub_server              : Res<HostHubServer>,
     user_server             : Res<HostUserServer>,
 ){
@@ -42,6 +45,7 @@ pub(crate) fn clean_ongoing_games(
         let game_id     = ongoing_game.game_id;
         let game_hub_id = ongoing_game.game_hub_id;
         tracing::warn!(game_id, "removed expired ongoing game");
+        if let Some(metrics) = &mut metrics { metrics.ongoing_game_expirations += 1; }
 
         // send game aborted message to users and update their states to idle
         send_game_abort_messages_and_update_states(
@@ -76,3 +80,34 @@ pub(crate) fn clean_game_hub_dc_buffer(world: &mut World)
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Unregister disconnected users that have expired in the dc buffer.
+pub(crate) fn clean_user_dc_buffer(world: &mut World)
+{
+    let disconnected_users: Vec<u128> = world.resource_mut::<UserDisconnectBuffer>().drain_expired().into_iter().collect();
+    for disconnected_user_id in disconnected_users
+    {
+        tracing::trace!(disconnected_user_id, "unregistering expired disconnected user");
+
+        // unregister the user
+        world.syscall(disconnected_user_id, unregister_user);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Remove expired rate limits of disconnected users.
+pub(crate) fn clean_user_rate_limits(mut rate_limits_cache: ResMut<UserRateLimitsCache>)
+{
+    rate_limits_cache.clean_disconnected();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Remove expired lobby invites.
+pub(crate) fn clean_lobby_invites(mut invites_cache: ResMut<LobbyInvitesCache>)
+{
+    invites_cache.clear_expired();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,113 @@
+//local shortcuts
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct UserDisconnectBufferConfig
+{
+    /// Amount of time a user may remain in the buffer before it expires.
+    pub expiry_duration: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Buffer of registered users that are not connected.
+/// Users restored from a host server snapshot sit in this buffer until they reconnect, so they can reclaim their place
+/// in a lobby. Users that don't reconnect before the buffer expires are unregistered.
+#[derive(Resource, Debug)]
+pub struct UserDisconnectBuffer
+{
+    /// config
+    config: UserDisconnectBufferConfig,
+    /// timer
+    timer: Instant,
+    /// [ user id : birth time ]
+    buffer: HashMap<u128, Duration>,
+}
+
+impl UserDisconnectBuffer
+{
+    /// Make a new buffer.
+    pub fn new(config: UserDisconnectBufferConfig) -> UserDisconnectBuffer
+    {
+        UserDisconnectBuffer{
+                config,
+                timer  : Instant::now(),
+                buffer : HashMap::default(),
+            }
+    }
+
+    /// Buffer a disconnected user.
+    /// - returns `Err(())` if there is already a user with the given id
+    pub fn add_user(&mut self, user_id: u128) -> Result<(), ()>
+    {
+        tracing::trace!(user_id, "buffer a disconnected user");
+
+        // verify the user doesn't already exist
+        if self.has_user(user_id) { return Err(()); }
+
+        // insert the buffered user
+        if let Some(_) = self.buffer.insert(user_id, self.timer.elapsed())
+        { tracing::error!("buffer insertion error"); }
+
+        Ok(())
+    }
+
+    /// Try to extract the buffered user.
+    /// - returns `Err(())` if the user doesn't exist
+    pub fn remove_user(&mut self, user_id: u128) -> Result<(), ()>
+    {
+        tracing::trace!(user_id, "remove buffered user");
+        let Some(_) = self.buffer.remove(&user_id) else { return Err(()); };
+
+        Ok(())
+    }
+
+    /// Check if the buffer has a user with the given id.
+    pub fn has_user(&self, user_id: u128) -> bool
+    {
+        self.buffer.contains_key(&user_id)
+    }
+
+    /// Current number of buffered users.
+    pub fn num_buffered(&self) -> usize
+    {
+        self.buffer.len()
+    }
+
+    /// Drain expired buffered users.
+    /// - iterates over all buffered users (may be inefficient)
+    pub fn drain_expired(&mut self) -> impl IntoIterator<Item = u128> + '_
+    {
+        // min birth time = current time - expiry duration
+        let elapsed         = self.timer.elapsed();
+        let expiry_duration = self.config.expiry_duration;
+        let min_birth_time  = elapsed.saturating_sub(expiry_duration);
+
+        // retain buffered users that have not expired
+        //todo: use .extract_if once stabilized
+        let mut extracted = Vec::default();
+        self.buffer.retain(
+            | user_id, birth_time |
+            {
+                // retain: user is not expired
+                if *birth_time >= min_birth_time { return true; }
+
+                // remove: erase the expired user
+                tracing::trace!(user_id, "removing expired buffered user");
+                extracted.push(*user_id);
+                false
+            }
+        );
+        extracted
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,51 @@
+//local shortcuts
+use bevy_girk_backend_public::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Trait for estimating how much game hub capacity a game uses.
+///
+/// Game hubs and the host server should use the same estimator, so the host's capacity estimates match the capacity
+/// reported by hubs.
+pub trait GameCostEstimator: Debug + Send + Sync + 'static
+{
+    /// Get the capacity cost of a game.
+    /// - A cost of zero is treated as 1.
+    fn game_cost(&self, request: &GameStartRequest) -> u16;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`GameCostEstimator`] where every game costs one unit of capacity. This is the default estimator.
+#[derive(Debug, Default)]
+pub struct UnitGameCost;
+
+impl GameCostEstimator for UnitGameCost
+{
+    fn game_cost(&self, _request: &GameStartRequest) -> u16
+    {
+        1
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource, Debug)]
+pub(crate) struct HostGameCostEstimator(pub(crate) Box<dyn GameCostEstimator>);
+
+impl HostGameCostEstimator
+{
+    /// Get the capacity cost of a game.
+    pub(crate) fn cost(&self, request: &GameStartRequest) -> u16
+    {
+        self.0.game_cost(request).max(1)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
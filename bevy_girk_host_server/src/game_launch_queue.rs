@@ -0,0 +1,114 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_backend_public::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Remove queued launches whose pending lobbies no longer exist (e.g. a member nacked the lobby).
+fn clean_game_launch_queue(
+    mut launch_queue      : ResMut<GameLaunchQueueCache>,
+    pending_lobbies_cache : Res<PendingLobbiesCache>,
+){
+    launch_queue.retain_lobbies(|lobby_id| pending_lobbies_cache.lobby_ref(lobby_id).is_some());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Abort queued launches that waited too long for a game hub.
+fn abort_expired_game_launches(world: &mut World)
+{
+    let expired = world.resource_mut::<GameLaunchQueueCache>().drain_expired();
+
+    for lobby_id in expired
+    {
+        let Some(lobby) = world.resource::<PendingLobbiesCache>().lobby_ref(lobby_id)
+        else { tracing::error!(lobby_id, "expired queued launch has no pending lobby"); continue; };
+        let owner_id = lobby.data.owner_id;
+
+        tracing::warn!(lobby_id, "queued game launch timed out; aborting pending lobby");
+        if !world.syscall((owner_id, Some(lobby_id)), force_nack_pending_lobby)
+        { tracing::error!(owner_id, lobby_id, "failed aborting pending lobby"); }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Try to launch queued games in queue order while game hubs have capacity.
+/// - Stops at the first lobby that can't launch, so lobbies never launch ahead of lobbies queued in front of them.
+/// - Only runs when game hub capacity was freed or the front of the queue changed, since otherwise the front lobby
+///   would fail to launch again.
+fn launch_queued_games(world: &mut World)
+{
+    // check if launches should be retried
+    let capacity_freed = world.resource_mut::<GameHubsCache>().take_capacity_freed();
+    let launch_queue   = world.resource::<GameLaunchQueueCache>();
+    if !capacity_freed && launch_queue.lobby_ids().next() == launch_queue.blocked_lobby() { return; }
+
+    let queued: Vec<u64> = launch_queue.lobby_ids().collect();
+    let mut blocked_lobby = None;
+
+    for lobby_id in queued
+    {
+        // stop if there are no game hubs with available capacity
+        if !world.resource::<GameHubsCache>().hubs().any(|hub| hub.available_capacity() > 0)
+        { blocked_lobby = Some(lobby_id); break; }
+
+        let Some(lobby) = world.resource::<PendingLobbiesCache>().lobby_ref(lobby_id)
+        else { tracing::error!(lobby_id, "queued launch has no pending lobby"); continue; };
+        let owner_id = lobby.data.owner_id;
+
+        // try to start the game
+        // - the lobby stays queued if no game hub will accept it
+        attempt_game_start_request(world, owner_id, lobby_id);
+
+        // stop if the lobby is still waiting for a game hub
+        // - note: lobbies that failed to launch were aborted, so we can move past them
+        if world.resource::<GameLaunchQueueCache>().has_lobby(lobby_id)
+            && world.resource::<PendingLobbiesCache>().lobby_ref(lobby_id).is_some()
+        { blocked_lobby = Some(lobby_id); break; }
+    }
+
+    world.resource_mut::<GameLaunchQueueCache>().set_blocked_lobby(blocked_lobby);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Notify users when their lobby's position in the launch queue changes.
+fn send_game_launch_queue_positions(
+    mut launch_queue      : ResMut<GameLaunchQueueCache>,
+    pending_lobbies_cache : Res<PendingLobbiesCache>,
+    user_server           : Res<HostUserServer>,
+){
+    for (lobby_id, position) in launch_queue.take_position_updates()
+    {
+        let Some(lobby) = pending_lobbies_cache.lobby_ref(lobby_id)
+        else { tracing::error!(lobby_id, "queued launch has no pending lobby"); continue; };
+        let estimated_wait = launch_queue.estimated_wait(position);
+
+        for (user_id, _) in lobby.data.members.iter()
+        {
+            user_server.send(*user_id, HostToUserMsg::GameLaunchQueued{ id: lobby_id, position, estimated_wait });
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Update the game launch queue.
+pub(crate) fn update_game_launch_queue(world: &mut World)
+{
+    world.syscall((), clean_game_launch_queue);
+    world.syscall((), abort_expired_game_launches);
+    world.syscall((), launch_queued_games);
+    world.syscall((), send_game_launch_queue_positions);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,192 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+use bevy_cobweb::prelude::*;
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn command_list_lobbies(
+    lobbies_cache         : Res<LobbiesCache>,
+    pending_lobbies_cache : Res<PendingLobbiesCache>,
+) -> Vec<HostServerLobbyInfo>
+{
+    lobbies_cache.lobbies()
+        .map(|lobby| HostServerLobbyInfo{ lobby: lobby.clone(), pending: false })
+        .chain(
+            pending_lobbies_cache.lobbies()
+                .map(|lobby| HostServerLobbyInfo{ lobby: lobby.clone(), pending: true })
+        )
+        .collect()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn command_close_lobby(
+    In(lobby_id)              : In<u64>,
+    mut lobbies_cache         : ResMut<LobbiesCache>,
+    mut pending_lobbies_cache : ResMut<PendingLobbiesCache>,
+    mut users_cache           : ResMut<UsersCache>,
+    invites_cache             : Option<ResMut<LobbyInvitesCache>>,
+    user_server               : Res<HostUserServer>,
+) -> Result<(), HostServerCommandError>
+{
+    // extract the lobby
+    // - if a game hub is starting a game for a pending lobby, the game will be aborted when the hub reports it
+    let lobby = match lobbies_cache.extract_lobby(lobby_id)
+    {
+        Some(lobby) => lobby,
+        None =>
+        {
+            let Ok(lobby) = pending_lobbies_cache.remove_lobby(lobby_id)
+            else { return Err(HostServerCommandError::LobbyNotFound); };
+            lobby
+        }
+    };
+
+    // tell users they have been removed from the lobby
+    send_lobby_leave_messages_and_update_states(&lobby.data, &mut users_cache, &user_server);
+
+    // clean up the lobby's invites
+    if let Some(mut invites_cache) = invites_cache
+    {
+        invites_cache.revoke_lobby_invites(lobby_id);
+    }
+
+    tracing::info!(lobby_id, "closed lobby");
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn command_abort_game(
+    In(game_id)             : In<u64>,
+    mut game_hubs_cache     : ResMut<GameHubsCache>,
+    mut ongoing_games_cache : ResMut<OngoingGamesCache>,
+    mut users_cache         : ResMut<UsersCache>,
+    hub_server              : Res<HostHubServer>,
+    user_server             : Res<HostUserServer>,
+) -> Result<(), HostServerCommandError>
+{
+    // remove game from ongoing games record
+    let Ok(ongoing_game) = ongoing_games_cache.remove_ongoing_game(game_id)
+    else { return Err(HostServerCommandError::GameNotFound); };
+    let game_hub_id = ongoing_game.game_hub_id;
+
+    // send game aborted message to users and update their states to idle
+    send_game_abort_messages_and_update_states(
+            game_id,
+            &ongoing_game.start_infos,
+            &mut users_cache,
+            &user_server
+        );
+
+    // tell game hub to abort the game
+    hub_server.send(game_hub_id, HostToHubMsg::Abort{ id: game_id });
+
+    // remove game from game hub
+    if let Err(_) = game_hubs_cache.remove_game(game_hub_id, game_id)
+    { tracing::error!(game_hub_id, game_id, "failed removing aborted game from hub cache"); }
+
+    tracing::info!(game_hub_id, game_id, "aborted ongoing game");
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn command_disconnect_user(
+    In(user_id) : In<u128>,
+    users_cache : Res<UsersCache>,
+    dc_buffer   : Res<UserDisconnectBuffer>,
+    user_server : Res<HostUserServer>,
+) -> Result<(), HostServerCommandError>
+{
+    if !users_cache.has_user(user_id) { return Err(HostServerCommandError::UserNotFound); }
+    if dc_buffer.has_user(user_id) { return Err(HostServerCommandError::UserNotConnected); }
+
+    tracing::info!(user_id, "disconnecting user");
+    user_server.disconnect_client(user_id, None);
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn command_disconnect_game_hub(
+    In(game_hub_id) : In<u128>,
+    game_hubs_cache : Res<GameHubsCache>,
+    dc_buffer       : Res<GameHubDisconnectBuffer>,
+    hub_server      : Res<HostHubServer>,
+) -> Result<(), HostServerCommandError>
+{
+    if !game_hubs_cache.has_hub(game_hub_id) { return Err(HostServerCommandError::GameHubNotFound); }
+    if dc_buffer.has_game_hub(game_hub_id) { return Err(HostServerCommandError::GameHubNotConnected); }
+
+    tracing::info!(game_hub_id, "disconnecting game hub");
+    hub_server.disconnect_client(game_hub_id, None);
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn handle_commands(world: &mut World)
+{
+    while let Some(command) = world.resource::<Receiver<HostServerCommand>>().try_recv()
+    {
+        // note: responses are dropped if the command sender is no longer listening
+        match command
+        {
+            HostServerCommand::ListUsers(response) =>
+            {
+                let users = world.resource::<UsersCache>().users().map(|(id, info)| (id, *info)).collect();
+                let _ = response.send(users);
+            }
+            HostServerCommand::ListLobbies(response) =>
+            {
+                let _ = response.send(world.syscall((), command_list_lobbies));
+            }
+            HostServerCommand::ListOngoingGames(response) =>
+            {
+                let _ = response.send(world.resource::<OngoingGamesCache>().games().cloned().collect());
+            }
+            HostServerCommand::ListGameHubs(response) =>
+            {
+                let _ = response.send(world.resource::<GameHubsCache>().hubs().collect());
+            }
+            HostServerCommand::CloseLobby{ id, response } =>
+            {
+                let _ = response.send(world.syscall(id, command_close_lobby));
+            }
+            HostServerCommand::AbortGame{ id, response } =>
+            {
+                let _ = response.send(world.syscall(id, command_abort_game));
+            }
+            HostServerCommand::DisconnectUser{ id, response } =>
+            {
+                let _ = response.send(world.syscall(id, command_disconnect_user));
+            }
+            HostServerCommand::DisconnectGameHub{ id, response } =>
+            {
+                let _ = response.send(world.syscall(id, command_disconnect_game_hub));
+            }
+            HostServerCommand::SetLobbiesCacheConfig{ config, response } =>
+            {
+                world.resource_mut::<LobbiesCache>().set_config(config);
+                let _ = response.send(());
+            }
+            HostServerCommand::SetPendingLobbiesConfig{ config, response } =>
+            {
+                world.resource_mut::<PendingLobbiesCache>().set_config(config);
+                let _ = response.send(());
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
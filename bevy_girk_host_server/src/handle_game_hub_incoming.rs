@@ -15,8 +15,8 @@ fn handle_game_hub_connection_report(world: &mut World, game_hub_id: u128, repor
 {
     match report
     {
-        HostHubServerReport::Connected(_, ()) => world.syscall(game_hub_id, connected_game_hub),
-        HostHubServerReport::Disconnected     => world.syscall(game_hub_id, disconnected_game_hub),
+        HostHubServerReport::Connected(_, metadata) => world.syscall((game_hub_id, metadata), connected_game_hub),
+        HostHubServerReport::Disconnected           => world.syscall(game_hub_id, disconnected_game_hub),
     }
 }
 
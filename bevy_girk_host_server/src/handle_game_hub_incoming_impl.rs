@@ -14,9 +14,9 @@ use bevy_cobweb::prelude::*;
 //-------------------------------------------------------------------------------------------------------------------
 
 pub(crate) fn connected_game_hub(
-    In(game_hub_id)        : In<u128>,
-    mut game_hubs_cache    : ResMut<GameHubsCache>,
-    mut game_hub_dc_buffer : ResMut<GameHubDisconnectBuffer>,
+    In((game_hub_id, metadata)) : In<(u128, GameHubMetadata)>,
+    mut game_hubs_cache         : ResMut<GameHubsCache>,
+    mut game_hub_dc_buffer      : ResMut<GameHubDisconnectBuffer>,
 ){
     // remove hub from disconnect buffer (if it exists there)
     let mut dc_buffered = false;
@@ -34,6 +34,11 @@ pub(crate) fn connected_game_hub(
         (true, true)   => tracing::error!(game_hub_id, "connected game hub was in dc buffer but not in hubs cache"),
         (false, false) => tracing::warn!(game_hub_id, "failed registering connected game hub"),
     }
+
+    // save the hub's metadata
+    // - note: do this even if reconnecting in case the hub's metadata changed
+    if let Err(_) = game_hubs_cache.set_hub_metadata(game_hub_id, metadata)
+    { tracing::error!(game_hub_id, "failed setting metadata of connected game hub"); }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -134,6 +139,7 @@ pub(crate) fn hub_update_capacity(
 pub(crate) fn hub_abort_game(In((game_hub_id, lobby_id)): In<(u128, u64)>, world: &mut World)
 {
     tracing::trace!(game_hub_id, lobby_id, "received abort game from game hub");
+    if let Some(mut metrics) = world.get_resource_mut::<HostServerMetrics>() { metrics.hub_aborts += 1; }
 
     // try to abort pending game
     if world.syscall((game_hub_id, lobby_id), try_abort_hub_pending_game)
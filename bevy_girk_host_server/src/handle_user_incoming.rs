@@ -16,10 +16,14 @@ fn handle_user_connection_report(world: &mut World, user_id: u128, report: HostU
 {
     match report
     {
-        HostUserServerReport::Connected(env_type, connect_msg) => world.syscall(
-            (user_id, UserInfo::new(env_type, connect_msg.connection_type)),
-            register_user
-        ),
+        HostUserServerReport::Connected(env_type, connect_msg) =>
+        {
+            let Ok(account_id) = world.syscall((user_id, connect_msg.auth), try_authenticate_user) else { return; };
+            world.syscall(
+                (user_id, UserInfo::new(env_type, connect_msg.connection_type).with_account_id(account_id)),
+                register_user
+            );
+        }
         HostUserServerReport::Disconnected => world.syscall(user_id, unregister_user),
     }
 }
@@ -33,6 +37,7 @@ fn handle_user_message(world: &mut World, user_id: u128, user_msg: UserToHostMsg
     {
         UserToHostMsg::NackPendingLobby{ id } => world.syscall((user_id, id), user_nack_pending_lobby),
         UserToHostMsg::AckPendingLobby{ id }  => world.syscall((user_id, id), user_ack_pending_lobby),
+        UserToHostMsg::LobbyChat{ id, text }  => world.syscall((user_id, id, text), user_lobby_chat),
     }
 }
 
@@ -46,9 +51,36 @@ fn handle_user_request(world: &mut World, token: bevy_simplenet::RequestToken, u
         UserToHostRequest::LobbySearch(query)             => world.syscall((token, query), user_get_lobby),
         UserToHostRequest::MakeLobby{ mcolor, pwd, data } => world.syscall((token, mcolor, pwd, data), user_make_lobby),
         UserToHostRequest::JoinLobby{ id, mcolor, pwd }   => world.syscall((token, id, mcolor, pwd), user_join_lobby),
+        UserToHostRequest::JoinLobbyWithInvite{ code, mcolor } =>
+        {
+            world.syscall((token, code, mcolor), user_join_lobby_with_invite)
+        }
         UserToHostRequest::LeaveLobby{ id }               => world.syscall((token, id), user_leave_lobby),
         UserToHostRequest::LaunchLobbyGame{ id }          => world.syscall((token, id), user_launch_lobby_game),
         UserToHostRequest::GetConnectToken{ id }          => world.syscall((token, id), user_get_connect_token),
+        UserToHostRequest::KickMember{ lobby_id, member_id, ban } =>
+        {
+            world.syscall((token, lobby_id, member_id, ban), user_kick_member)
+        }
+        UserToHostRequest::TransferOwnership{ lobby_id, new_owner_id } =>
+        {
+            world.syscall((token, lobby_id, new_owner_id), user_transfer_ownership)
+        }
+        UserToHostRequest::UpdateLobbyData{ id, data }    => world.syscall((token, id, data), user_update_lobby_data),
+        UserToHostRequest::SetLobbyPrivate{ id, private } =>
+        {
+            world.syscall((token, id, private), user_set_lobby_private)
+        }
+        UserToHostRequest::MakeLobbyInvite{ id, single_use } =>
+        {
+            world.syscall((token, id, single_use), user_make_lobby_invite)
+        }
+        UserToHostRequest::RevokeLobbyInvite{ id, code } =>
+        {
+            world.syscall((token, id, code), user_revoke_lobby_invite)
+        }
+        UserToHostRequest::EnterQueue{ mcolor, data }     => world.syscall((token, mcolor, data), user_enter_queue),
+        UserToHostRequest::LeaveQueue                     => world.syscall(token, user_leave_queue),
     }
 }
 
@@ -60,12 +92,38 @@ pub(crate) fn handle_user_incoming(world: &mut World)
     // handle user events
     while let Some((user_id, server_event)) = world.resource_mut::<HostUserServer>().next()
     {
+        // ignore messages and requests from users that aren't registered (e.g. rejected by the user authenticator)
+        // - dropping a request token rejects the request
+        if !matches!(server_event, HostUserServerEvent::Report(_))
+            && !world.resource::<UsersCache>().has_user(user_id)
+        { tracing::trace!(user_id, "ignoring message or request from unregistered user"); continue; }
+
         // handle the event
         match server_event
         {
-            HostUserServerEvent::Report(report)      => handle_user_connection_report(world, user_id, report),
-            HostUserServerEvent::Msg(msg)            => handle_user_message(world, user_id, msg),
-            HostUserServerEvent::Request(token, req) => handle_user_request(world, token, req),
+            HostUserServerEvent::Report(report) => handle_user_connection_report(world, user_id, report),
+            HostUserServerEvent::Msg(msg) =>
+            {
+                // drop rate-limited messages
+                if let Some(category) = UserRequestCategory::from_msg(&msg)
+                {
+                    if !world.syscall((user_id, category), check_user_rate_limit) { continue; }
+                }
+                handle_user_message(world, user_id, msg);
+            }
+            HostUserServerEvent::Request(token, req) =>
+            {
+                // reject rate-limited requests
+                if !world.syscall((user_id, UserRequestCategory::from_request(&req)), check_user_rate_limit)
+                {
+                    world.resource::<HostUserServer>().respond(
+                        token,
+                        HostToUserResponse::Error(HostRequestError::RateLimited)
+                    );
+                    continue;
+                }
+                handle_user_request(world, token, req);
+            }
         }
     }
 }
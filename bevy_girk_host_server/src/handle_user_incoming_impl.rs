@@ -13,6 +13,10 @@ use bevy_cobweb::prelude::*;
 
 pub(crate) fn register_user(In((user_id, user_info)): In<(u128, UserInfo)>, world: &mut World)
 {
+    // reclaim user if they are already registered but disconnected (e.g. restored from a snapshot)
+    if world.syscall((user_id, user_info), try_reclaim_disconnected_user)
+    { tracing::trace!("reclaimed disconnected user (id={user_id}, info={user_info:?})"); return; }
+
     // register user
     if world.syscall((user_id, user_info), try_register_user)
     { tracing::trace!("registered user (id={user_id}, info={user_info:?})"); }
@@ -28,6 +32,20 @@ pub(crate) fn register_user(In((user_id, user_info)): In<(u128, UserInfo)>, worl
 
 pub(crate) fn unregister_user(In(user_id): In<u128>, world: &mut World)
 {
+    // ignore users that were never registered (e.g. rejected by the user authenticator)
+    // - make sure the user doesn't leave rate limits behind
+    if !world.resource::<UsersCache>().has_user(user_id)
+    {
+        if let Some(mut rate_limits_cache) = world.get_resource_mut::<UserRateLimitsCache>()
+        { rate_limits_cache.remove_user(user_id); }
+        tracing::trace!(user_id, "ignoring disconnect from unregistered user");
+        return;
+    }
+
+    // remove user from matchmaking queue
+    if world.syscall(user_id, try_remove_user_from_queue)
+    { tracing::trace!(user_id, "removed user from matchmaking queue while unregistering user"); }
+
     // nack pending lobby the user is in
     if world.syscall((user_id, None), force_nack_pending_lobby)
     { tracing::trace!(user_id, "force nacked pending lobby while unregistering user"); }
@@ -36,6 +54,14 @@ pub(crate) fn unregister_user(In(user_id): In<u128>, world: &mut World)
     if world.syscall((user_id, None), try_remove_user_from_lobby)
     { tracing::trace!(user_id, "removed user from lobby while unregistering user"); }
 
+    // clear the user's chat history
+    if let Some(mut lobby_chat_cache) = world.get_resource_mut::<LobbyChatCache>()
+    { lobby_chat_cache.remove_user(user_id); }
+
+    // buffer the user's rate limits in case they reconnect
+    if let Some(mut rate_limits_cache) = world.get_resource_mut::<UserRateLimitsCache>()
+    { rate_limits_cache.disconnect_user(user_id); }
+
     // unregister the user
     if world.syscall(user_id, try_remove_user_from_cache)
     { tracing::trace!(user_id, "unregistered user"); }
@@ -73,7 +99,12 @@ pub(crate) fn user_make_lobby(
     // verify user is not already in a lobby
     let user_id = token.client_id();
     let Some(UserState::Idle) = users_cache.get_user_state(user_id)
-    else { tracing::trace!(user_id, "could not make lobby, user is not idle"); return; };
+    else
+    {
+        tracing::trace!(user_id, "could not make lobby, user is not idle");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::NotIdle));
+        return;
+    };
 
     // assemble lobby member data
     let Some(user_info) = users_cache.get_user_info(user_id)
@@ -81,8 +112,16 @@ pub(crate) fn user_make_lobby(
     let member_data = LobbyMemberData{ connection: user_info.connection(), color: member_color };
 
     // make lobby
-    let Ok(lobby_id) = lobbies_cache.new_lobby(user_id, member_data, password, custom_data)
-    else { tracing::trace!(user_id, ?member_data, "failed making new lobby"); return; };
+    let lobby_id = match lobbies_cache.new_lobby(user_id, member_data, password, custom_data)
+    {
+        Ok(lobby_id) => lobby_id,
+        Err(err) =>
+        {
+            tracing::trace!(user_id, ?member_data, ?err, "failed making new lobby");
+            user_server.respond(token, HostToUserResponse::Error(err));
+            return;
+        }
+    };
     tracing::trace!(lobby_id, user_id, ?member_data, "created new lobby");
 
     // access the lobby
@@ -109,7 +148,12 @@ pub(crate) fn user_join_lobby(
     // verify user is not already in a lobby
     let user_id = token.client_id();
     let Some(UserState::Idle) = users_cache.get_user_state(user_id)
-    else { tracing::trace!(lobby_id, user_id, "could not join lobby, user is not idle"); return; };
+    else
+    {
+        tracing::trace!(lobby_id, user_id, "could not join lobby, user is not idle");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::NotIdle));
+        return;
+    };
 
     // assemble lobby member data
     let Some(user_info) = users_cache.get_user_info(user_id)
@@ -117,8 +161,12 @@ pub(crate) fn user_join_lobby(
     let member_data = LobbyMemberData{ connection: user_info.connection(), color: member_color };
 
     // try to join the lobby
-    if !lobbies_cache.try_add_member(lobby_id, user_id, member_data, &password)
-    { tracing::trace!(lobby_id, user_id, ?member_data, "could not join lobby, join request rejected"); return; };
+    if let Err(err) = lobbies_cache.try_add_member(lobby_id, user_id, member_data, &password)
+    {
+        tracing::trace!(lobby_id, user_id, ?member_data, ?err, "could not join lobby, join request rejected");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    };
 
     // try to get the lobby
     let Some(lobby_ref) = lobbies_cache.lobby_ref_mut(lobby_id)
@@ -134,6 +182,71 @@ pub(crate) fn user_join_lobby(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+pub(crate) fn user_join_lobby_with_invite(
+    In((
+        token,
+        code,
+        member_color,
+    ))                : In<(bevy_simplenet::RequestToken, String, LobbyMemberColor)>,
+    mut lobbies_cache : ResMut<LobbiesCache>,
+    mut users_cache   : ResMut<UsersCache>,
+    invites_cache     : Option<ResMut<LobbyInvitesCache>>,
+    user_server       : Res<HostUserServer>,
+){
+    // verify user is not already in a lobby
+    let user_id = token.client_id();
+    let Some(UserState::Idle) = users_cache.get_user_state(user_id)
+    else
+    {
+        tracing::trace!(user_id, "could not join lobby with invite, user is not idle");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::NotIdle));
+        return;
+    };
+
+    // validate the invite
+    let Some(mut invites_cache) = invites_cache
+    else
+    {
+        tracing::trace!(user_id, "could not join lobby with invite, invites are disabled");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::Disabled));
+        return;
+    };
+    let Some(lobby_id) = invites_cache.invite_lobby(&code)
+    else
+    {
+        tracing::trace!(user_id, code, "could not join lobby with invite, invite is invalid");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::InvalidInvite));
+        return;
+    };
+
+    // assemble lobby member data
+    let Some(user_info) = users_cache.get_user_info(user_id)
+    else { tracing::error!(user_id, "failed getting user env"); return; };
+    let member_data = LobbyMemberData{ connection: user_info.connection(), color: member_color };
+
+    // try to join the lobby
+    if let Err(err) = lobbies_cache.try_add_invited_member(lobby_id, user_id, member_data)
+    {
+        tracing::trace!(lobby_id, user_id, ?member_data, ?err, "could not join lobby with invite, join request rejected");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    };
+    invites_cache.consume_invite(&code);
+
+    // try to get the lobby
+    let Some(lobby_ref) = lobbies_cache.lobby_ref(lobby_id)
+    else { tracing::error!(lobby_id, user_id, "could not join lobby with invite, lobby doesn't exist"); return; };
+
+    // send join message and update state
+    tracing::trace!(lobby_id, user_id, "user joined lobby with invite");
+    send_lobby_join_message_and_update_state(token, &lobby_ref.data, &mut users_cache, &user_server);
+
+    // notify lobby members of new lobby state
+    send_lobby_state_messages(&lobby_ref.data, &user_server);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(crate) fn user_leave_lobby(In((token, lobby_id)): In<(bevy_simplenet::RequestToken, u64)>, world: &mut World)
 {
     // get initial user state
@@ -151,7 +264,16 @@ pub(crate) fn user_leave_lobby(In((token, lobby_id)): In<(bevy_simplenet::Reques
 
     // send request ack if we actually left a lobby
     let Some(final_user_state) = world.resource::<UsersCache>().get_user_state(user_id) else { return; };
-    if initial_user_state == final_user_state { return; };
+    if initial_user_state == final_user_state
+    {
+        let err = match initial_user_state
+        {
+            UserState::InLobby(id) | UserState::InPendingLobby(id) if id == lobby_id => HostRequestError::Rejected,
+            _ => HostRequestError::NotInLobby,
+        };
+        world.resource::<HostUserServer>().respond(token, HostToUserResponse::Error(err));
+        return;
+    };
     let _ = world.resource::<HostUserServer>().ack(token);
 }
 
@@ -162,27 +284,45 @@ pub(crate) fn user_launch_lobby_game(
     mut pending_lobbies_cache : ResMut<PendingLobbiesCache>,
     mut lobbies_cache         : ResMut<LobbiesCache>,
     mut users_cache           : ResMut<UsersCache>,
+    invites_cache             : Option<ResMut<LobbyInvitesCache>>,
     user_server               : Res<HostUserServer>,
 ){
     // get id of lobby the user is in
     let user_id = token.client_id();
     let Some(UserState::InLobby(users_lobby_id)) = users_cache.get_user_state(user_id)
-    else { tracing::trace!(user_id, "failed launching game, user is not in the lobby"); return; };
+    else
+    {
+        tracing::trace!(user_id, "failed launching game, user is not in the lobby");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::NotInLobby));
+        return;
+    };
 
     // validate lobby id
     if users_lobby_id != lobby_id
-    { tracing::trace!(user_id, users_lobby_id, lobby_id, "failed launching game, user is in a different lobby"); return; };
+    {
+        tracing::trace!(user_id, users_lobby_id, lobby_id, "failed launching game, user is in a different lobby");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::NotInLobby));
+        return;
+    };
 
     // check if user is owner of the lobby
     let Some(lobby_ref) = lobbies_cache.lobby_ref(lobby_id)
     else { tracing::error!(user_id, lobby_id, "user's lobby is missing"); return; };
 
     if !lobby_ref.is_owner(user_id)
-    { tracing::trace!(user_id, lobby_id, "failed launching game, user is not lobby owner"); return; }
+    {
+        tracing::trace!(user_id, lobby_id, "failed launching game, user is not lobby owner");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::NotOwner));
+        return;
+    }
 
     // check if lobby can be launched
     if !lobbies_cache.lobby_checker().can_launch(lobby_ref)
-    { tracing::trace!(user_id, lobby_id, "failed launching game, lobby has insufficient players"); return; }
+    {
+        tracing::trace!(user_id, lobby_id, "failed launching game, lobby has insufficient players");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::CannotLaunch));
+        return;
+    }
 
     // send ack requests to users and update states to 'in pending lobby'
     send_pending_lobby_ack_requests_and_update_states(&lobby_ref.data, &mut users_cache, &user_server);
@@ -192,6 +332,13 @@ pub(crate) fn user_launch_lobby_game(
     let Some(lobby) = lobbies_cache.extract_lobby(lobby_id)
     else { tracing::error!(user_id, "extract lobby error"); return; };
 
+    // clean up the lobby's invites
+    // - members can't join a pending lobby
+    if let Some(mut invites_cache) = invites_cache
+    {
+        invites_cache.revoke_lobby_invites(lobby_id);
+    }
+
     // move lobby to pending
     // - warning: failure here is a critical error
     if let Err(_) = pending_lobbies_cache.add_lobby(lobby) { tracing::error!("insert pending lobby error"); }
@@ -215,11 +362,20 @@ pub(crate) fn user_get_connect_token(
 
     // get id of game the user is in
     let Some(UserState::InGame(users_game_id)) = users_cache.get_user_state(user_id)
-    else { tracing::trace!(user_id, lobby_id, "failed getting connect token, user is not in a game"); return; };
+    else
+    {
+        tracing::trace!(user_id, lobby_id, "failed getting connect token, user is not in a game");
+        user_server.respond(req_token, HostToUserResponse::Error(HostRequestError::NotInGame));
+        return;
+    };
 
     // check the request
     if lobby_id != users_game_id
-    { tracing::trace!(user_id, lobby_id, users_game_id, "failed getting connect token for invalid game"); return; };
+    {
+        tracing::trace!(user_id, lobby_id, users_game_id, "failed getting connect token for invalid game");
+        user_server.respond(req_token, HostToUserResponse::Error(HostRequestError::NotInGame));
+        return;
+    };
 
     // get a connect token for the user for the game
     let Some((game_id, token)) = ongoing_games_cache.get_user_connect_token(user_id, user_info)
@@ -231,6 +387,302 @@ pub(crate) fn user_get_connect_token(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+pub(crate) fn user_kick_member(
+    In((token, lobby_id, member_id, ban)) : In<(bevy_simplenet::RequestToken, u64, u128, bool)>,
+    world                                 : &mut World,
+){
+    // check if user owns the lobby
+    let user_id = token.client_id();
+    if let Err(err) = check_user_owns_lobby(
+        user_id,
+        lobby_id,
+        world.resource::<UsersCache>(),
+        world.resource::<LobbiesCache>()
+    )
+    {
+        tracing::trace!(user_id, lobby_id, ?err, "failed kicking member, user is not lobby owner");
+        world.resource::<HostUserServer>().respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // check the target is a member other than the owner
+    let lobbies_cache = world.resource::<LobbiesCache>();
+    if member_id == user_id || !lobbies_cache.lobby_ref(lobby_id).is_some_and(|lobby| lobby.has_member(member_id))
+    {
+        tracing::trace!(user_id, lobby_id, member_id, "failed kicking member, target is not a kickable member");
+        world.resource::<HostUserServer>().respond(token, HostToUserResponse::Error(HostRequestError::InvalidMember));
+        return;
+    }
+
+    // ban the member
+    if ban
+    {
+        let mut lobbies_cache = world.resource_mut::<LobbiesCache>();
+        if !lobbies_cache.lobby_ref_mut(lobby_id).is_some_and(|lobby| lobby.ban_user(member_id))
+        { tracing::error!(lobby_id, member_id, "failed banning lobby member"); }
+    }
+
+    // remove the member from the lobby
+    if !world.syscall((member_id, Some(lobby_id)), try_remove_user_from_lobby)
+    { tracing::error!(user_id, lobby_id, member_id, "failed removing kicked member from lobby"); return; }
+    tracing::trace!(user_id, lobby_id, member_id, ban, "kicked lobby member");
+
+    // send request ack
+    let _ = world.resource::<HostUserServer>().ack(token);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn user_transfer_ownership(
+    In((token, lobby_id, new_owner_id)) : In<(bevy_simplenet::RequestToken, u64, u128)>,
+    mut lobbies_cache                   : ResMut<LobbiesCache>,
+    users_cache                         : Res<UsersCache>,
+    user_server                         : Res<HostUserServer>,
+){
+    // check if user owns the lobby
+    let user_id = token.client_id();
+    if let Err(err) = check_user_owns_lobby(user_id, lobby_id, &users_cache, &lobbies_cache)
+    {
+        tracing::trace!(user_id, lobby_id, ?err, "failed transferring ownership, user is not lobby owner");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // transfer ownership
+    if new_owner_id == user_id
+    {
+        tracing::trace!(user_id, lobby_id, "failed transferring ownership, user already owns the lobby");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::InvalidMember));
+        return;
+    }
+    if let Err(err) = lobbies_cache.try_transfer_ownership(lobby_id, new_owner_id)
+    {
+        tracing::trace!(user_id, lobby_id, new_owner_id, ?err, "failed transferring ownership, new owner rejected");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // notify members of new lobby state
+    let Some(lobby_ref) = lobbies_cache.lobby_ref(lobby_id)
+    else { tracing::error!(user_id, lobby_id, "user's lobby is missing"); return; };
+    send_lobby_state_messages(&lobby_ref.data, &user_server);
+
+    // send request ack
+    let _ = user_server.ack(token);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn user_update_lobby_data(
+    In((token, lobby_id, custom_data)) : In<(bevy_simplenet::RequestToken, u64, Vec<u8>)>,
+    mut lobbies_cache                  : ResMut<LobbiesCache>,
+    users_cache                        : Res<UsersCache>,
+    user_server                        : Res<HostUserServer>,
+){
+    // check if user owns the lobby
+    let user_id = token.client_id();
+    if let Err(err) = check_user_owns_lobby(user_id, lobby_id, &users_cache, &lobbies_cache)
+    {
+        tracing::trace!(user_id, lobby_id, ?err, "failed updating lobby data, user is not lobby owner");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // update the lobby
+    if let Err(err) = lobbies_cache.try_update_custom_data(lobby_id, custom_data)
+    {
+        tracing::trace!(user_id, lobby_id, ?err, "failed updating lobby data, new data rejected");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // notify members of new lobby state
+    let Some(lobby_ref) = lobbies_cache.lobby_ref(lobby_id)
+    else { tracing::error!(user_id, lobby_id, "user's lobby is missing"); return; };
+    send_lobby_state_messages(&lobby_ref.data, &user_server);
+
+    // send request ack
+    let _ = user_server.ack(token);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn user_set_lobby_private(
+    In((token, lobby_id, private)) : In<(bevy_simplenet::RequestToken, u64, bool)>,
+    mut lobbies_cache              : ResMut<LobbiesCache>,
+    users_cache                    : Res<UsersCache>,
+    user_server                    : Res<HostUserServer>,
+){
+    // check if user owns the lobby
+    let user_id = token.client_id();
+    if let Err(err) = check_user_owns_lobby(user_id, lobby_id, &users_cache, &lobbies_cache)
+    {
+        tracing::trace!(user_id, lobby_id, ?err, "failed setting lobby privacy, user is not lobby owner");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // update the lobby
+    let Some(lobby_ref) = lobbies_cache.lobby_ref_mut(lobby_id)
+    else { tracing::error!(user_id, lobby_id, "user's lobby is missing"); return; };
+    lobby_ref.set_private(private);
+    tracing::trace!(user_id, lobby_id, private, "set lobby privacy");
+
+    // send request ack
+    let _ = user_server.ack(token);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn user_make_lobby_invite(
+    In((token, lobby_id, single_use)) : In<(bevy_simplenet::RequestToken, u64, bool)>,
+    lobbies_cache                     : Res<LobbiesCache>,
+    users_cache                       : Res<UsersCache>,
+    invites_cache                     : Option<ResMut<LobbyInvitesCache>>,
+    user_server                       : Res<HostUserServer>,
+){
+    // check if user owns the lobby
+    let user_id = token.client_id();
+    if let Err(err) = check_user_owns_lobby(user_id, lobby_id, &users_cache, &lobbies_cache)
+    {
+        tracing::trace!(user_id, lobby_id, ?err, "failed making lobby invite, user is not lobby owner");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // make the invite
+    let Some(mut invites_cache) = invites_cache
+    else
+    {
+        tracing::trace!(user_id, lobby_id, "failed making lobby invite, invites are disabled");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::Disabled));
+        return;
+    };
+    let code = match invites_cache.new_invite(lobby_id, single_use)
+    {
+        Ok(code) => code,
+        Err(err) =>
+        {
+            tracing::trace!(user_id, lobby_id, ?err, "failed making lobby invite, invite rejected");
+            user_server.respond(token, HostToUserResponse::Error(err));
+            return;
+        }
+    };
+
+    // send invite to user
+    let expires_in = invites_cache.invite_duration();
+    user_server.respond(token, HostToUserResponse::LobbyInvite{ id: lobby_id, code, expires_in });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn user_revoke_lobby_invite(
+    In((token, lobby_id, code)) : In<(bevy_simplenet::RequestToken, u64, String)>,
+    lobbies_cache               : Res<LobbiesCache>,
+    users_cache                 : Res<UsersCache>,
+    invites_cache               : Option<ResMut<LobbyInvitesCache>>,
+    user_server                 : Res<HostUserServer>,
+){
+    // check if user owns the lobby
+    let user_id = token.client_id();
+    if let Err(err) = check_user_owns_lobby(user_id, lobby_id, &users_cache, &lobbies_cache)
+    {
+        tracing::trace!(user_id, lobby_id, ?err, "failed revoking lobby invite, user is not lobby owner");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // revoke the invite
+    let Some(mut invites_cache) = invites_cache
+    else
+    {
+        tracing::trace!(user_id, lobby_id, "failed revoking lobby invite, invites are disabled");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::Disabled));
+        return;
+    };
+    if let Err(err) = invites_cache.revoke_invite(lobby_id, &code)
+    {
+        tracing::trace!(user_id, lobby_id, code, ?err, "failed revoking lobby invite, invite doesn't exist");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // send request ack
+    let _ = user_server.ack(token);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn user_enter_queue(
+    In((
+        token,
+        member_color,
+        custom_data,
+    ))                : In<(bevy_simplenet::RequestToken, LobbyMemberColor, Vec<u8>)>,
+    mut users_cache   : ResMut<UsersCache>,
+    matchmaking_queue : Option<ResMut<MatchmakingQueueCache>>,
+    user_server       : Res<HostUserServer>,
+){
+    // verify user is idle
+    let user_id = token.client_id();
+    let Some(UserState::Idle) = users_cache.get_user_state(user_id)
+    else
+    {
+        tracing::trace!(user_id, "could not enter queue, user is not idle");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::NotIdle));
+        return;
+    };
+
+    // verify matchmaking is enabled
+    let Some(mut matchmaking_queue) = matchmaking_queue
+    else
+    {
+        tracing::trace!(user_id, "could not enter queue, matchmaking is disabled");
+        user_server.respond(token, HostToUserResponse::Error(HostRequestError::Disabled));
+        return;
+    };
+
+    // assemble lobby member data
+    let Some(user_info) = users_cache.get_user_info(user_id)
+    else { tracing::error!(user_id, "failed getting user env"); return; };
+    let member_data = LobbyMemberData{ connection: user_info.connection(), color: member_color };
+
+    // add user to the queue
+    if let Err(err) = matchmaking_queue.add_user(user_id, member_data, custom_data)
+    {
+        tracing::trace!(user_id, ?member_data, ?err, "could not enter queue, queue rejected user");
+        user_server.respond(token, HostToUserResponse::Error(err));
+        return;
+    }
+
+    // update user state
+    if let Err(_) = users_cache.update_user_state(user_id, UserState::InQueue)
+    { tracing::error!(user_id, "failed updating user state to in-queue"); }
+
+    // send request ack
+    let _ = user_server.ack(token);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn user_leave_queue(In(token): In<bevy_simplenet::RequestToken>, world: &mut World)
+{
+    // remove user from the queue
+    let user_id = token.client_id();
+    if !world.syscall(user_id, try_remove_user_from_queue)
+    {
+        tracing::trace!(user_id, "could not leave queue, user is not queued");
+        world.resource::<HostUserServer>().respond(token, HostToUserResponse::Error(HostRequestError::NotInQueue));
+        return;
+    }
+
+    // send request ack
+    tracing::trace!(user_id, "removed user from matchmaking queue");
+    let _ = world.resource::<HostUserServer>().ack(token);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(crate) fn user_nack_pending_lobby(In((user_id, lobby_id)): In<(u128, u64)>, world: &mut World)
 {
     // nack pending lobby the user is in
@@ -254,3 +706,44 @@ pub(crate) fn user_ack_pending_lobby(In((user_id, lobby_id)): In<(u128, u64)>, w
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn user_lobby_chat(
+    In((user_id, lobby_id, text)) : In<(u128, u64, String)>,
+    lobbies_cache                 : Res<LobbiesCache>,
+    pending_lobbies_cache         : Res<PendingLobbiesCache>,
+    users_cache                   : Res<UsersCache>,
+    lobby_chat_cache              : Option<ResMut<LobbyChatCache>>,
+    user_server                   : Res<HostUserServer>,
+){
+    // verify lobby chat is enabled
+    let Some(mut lobby_chat_cache) = lobby_chat_cache
+    else { tracing::trace!(user_id, lobby_id, "dropping lobby chat message, lobby chat is disabled"); return; };
+
+    // get the user's lobby
+    let lobby_ref = match users_cache.get_user_state(user_id)
+    {
+        Some(UserState::InLobby(users_lobby_id)) if users_lobby_id == lobby_id => lobbies_cache.lobby_ref(lobby_id),
+        Some(UserState::InPendingLobby(users_lobby_id)) if users_lobby_id == lobby_id =>
+        {
+            pending_lobbies_cache.lobby_ref(lobby_id)
+        }
+        _ => { tracing::trace!(user_id, lobby_id, "dropping lobby chat message, user is not in lobby"); return; }
+    };
+    let Some(lobby_ref) = lobby_ref
+    else { tracing::error!(user_id, lobby_id, "user's lobby is missing"); return; };
+
+    // rate limit
+    if !lobby_chat_cache.try_record_message(user_id) { return; }
+
+    // filter the message
+    let Some(text) = lobby_chat_cache.filter_message(user_id, &lobby_ref.data, text)
+    else { tracing::trace!(user_id, lobby_id, "dropping filtered lobby chat message"); return; };
+
+    // relay the message to lobby members
+    for (member_id, _) in lobby_ref.data.members.iter()
+    {
+        user_server.send(*member_id, HostToUserMsg::LobbyChat{ id: lobby_id, sender: user_id, text: text.clone() });
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
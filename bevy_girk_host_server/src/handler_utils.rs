@@ -32,6 +32,33 @@ pub(crate) fn _game_hub_is_registered(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Check if a user is in the given lobby and owns it.
+pub(crate) fn check_user_owns_lobby(
+    user_id       : u128,
+    lobby_id      : u64,
+    users_cache   : &UsersCache,
+    lobbies_cache : &LobbiesCache,
+) -> Result<(), HostRequestError>
+{
+    let Some(UserState::InLobby(users_lobby_id)) = users_cache.get_user_state(user_id)
+    else { tracing::trace!(user_id, lobby_id, "user is not in a lobby"); return Err(HostRequestError::NotInLobby); };
+
+    if users_lobby_id != lobby_id
+    {
+        tracing::trace!(user_id, users_lobby_id, lobby_id, "user is in a different lobby");
+        return Err(HostRequestError::NotInLobby);
+    }
+
+    let Some(lobby_ref) = lobbies_cache.lobby_ref(lobby_id)
+    else { tracing::error!(user_id, lobby_id, "user's lobby is missing"); return Err(HostRequestError::LobbyNotFound); };
+
+    if !lobby_ref.is_owner(user_id) { return Err(HostRequestError::NotOwner); }
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(crate) fn send_lobby_join_message_and_update_state(
     token       : bevy_simplenet::RequestToken,
     lobby       : &LobbyData,
@@ -214,6 +241,95 @@ pub(crate) fn try_register_user(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// check if a user request is within the user's rate limits
+/// - returns `false` if the request should be rejected
+/// - disconnects users that exceed their max rate limit violations
+pub(crate) fn check_user_rate_limit(
+    In((user_id, category)) : In<(u128, UserRequestCategory)>,
+    rate_limits_cache       : Option<ResMut<UserRateLimitsCache>>,
+    user_server             : Res<HostUserServer>,
+) -> bool
+{
+    let Some(mut rate_limits_cache) = rate_limits_cache else { return true; };
+
+    match rate_limits_cache.record_request(user_id, category)
+    {
+        UserRateLimitResult::Allowed => true,
+        UserRateLimitResult::Limited => false,
+        UserRateLimitResult::Disconnect =>
+        {
+            tracing::debug!(user_id, "disconnecting user for exceeding rate limits");
+            user_server.disconnect_client(user_id, None);
+            false
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// try to authenticate a connecting user
+/// - returns the user's account id (`None` if there is no authenticator)
+/// - disconnects the user and returns `Err` if they fail authentication
+pub(crate) fn try_authenticate_user(
+    In((user_id, auth)) : In<(u128, Vec<u8>)>,
+    authenticator       : Option<Res<HostUserAuthenticator>>,
+    user_server         : Res<HostUserServer>,
+) -> Result<Option<u128>, ()>
+{
+    let Some(authenticator) = authenticator else { return Ok(None); };
+
+    match authenticator.0.authenticate(user_id, &auth)
+    {
+        Ok(account_id) => Ok(Some(account_id)),
+        Err(reason) =>
+        {
+            tracing::debug!(user_id, reason, "user failed authentication, disconnecting");
+            user_server.disconnect_client(user_id, None);
+            Err(())
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn try_reclaim_disconnected_user(
+    In((user_id, user_info)) : In<(u128, UserInfo)>,
+    mut users_cache          : ResMut<UsersCache>,
+    mut user_dc_buffer       : ResMut<UserDisconnectBuffer>,
+    lobbies_cache            : Res<LobbiesCache>,
+    ongoing_games_cache      : Res<OngoingGamesCache>,
+    user_server              : Res<HostUserServer>,
+) -> bool
+{
+    // remove the user from the disconnect buffer
+    if let Err(_) = user_dc_buffer.remove_user(user_id) { return false; }
+
+    // update the user's connection (and the account id resolved for the new connection)
+    let (env_type, connection, account_id) = (user_info.env_type(), user_info.connection(), user_info.account_id());
+    if let Err(_) = users_cache.update_user_connection(user_id, env_type, connection, account_id)
+    { tracing::error!(user_id, "disconnected user is missing in users cache"); return false; }
+
+    // catch the user up on their current state
+    match users_cache.get_user_state(user_id)
+    {
+        Some(UserState::InLobby(lobby_id)) =>
+        {
+            let Some(lobby_ref) = lobbies_cache.lobby_ref(lobby_id)
+            else { tracing::error!(user_id, lobby_id, "reclaimed user's lobby is missing"); return true; };
+            user_server.send(user_id, HostToUserMsg::LobbyState{ lobby: lobby_ref.data.clone() });
+        }
+        Some(UserState::Idle) =>
+        {
+            let _ = try_connect_user_to_game(user_id, &mut users_cache, &ongoing_games_cache, &user_server);
+        }
+        _ => (),
+    }
+
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(crate) fn try_connect_user_to_game(
     user_id             : u128,
     users_cache         : &mut UsersCache,
@@ -294,6 +410,7 @@ pub(crate) fn try_nack_pending_lobby(
     mut pending_lobbies_cache : ResMut<PendingLobbiesCache>,
     mut lobbies_cache         : ResMut<LobbiesCache>,
     mut users_cache           : ResMut<UsersCache>,
+    metrics                   : Option<ResMut<HostServerMetrics>>,
     user_server               : Res<HostUserServer>,
 ) -> bool
 {
@@ -320,6 +437,7 @@ pub(crate) fn try_nack_pending_lobby(
 
     // handle ack failure
     handle_ack_failure(lobby, &mut lobbies_cache, &mut users_cache, &user_server);
+    if let Some(mut metrics) = metrics { metrics.pending_lobby_nacks += 1; }
 
     true
 }
@@ -332,6 +450,7 @@ pub(crate) fn force_nack_pending_lobby(
     mut pending_lobbies_cache : ResMut<PendingLobbiesCache>,
     mut lobbies_cache         : ResMut<LobbiesCache>,
     mut users_cache           : ResMut<UsersCache>,
+    metrics                   : Option<ResMut<HostServerMetrics>>,
     user_server               : Res<HostUserServer>,
 ) -> bool
 {
@@ -355,6 +474,7 @@ pub(crate) fn force_nack_pending_lobby(
 
     // handle ack failure
     handle_ack_failure(lobby, &mut lobbies_cache, &mut users_cache, &user_server);
+    if let Some(mut metrics) = metrics { metrics.pending_lobby_nacks += 1; }
 
     true
 }
@@ -365,6 +485,7 @@ pub(crate) fn try_remove_user_from_lobby(
     In((user_id, remove_id)) : In<(u128, Option<u64>)>,
     mut lobbies_cache       : ResMut<LobbiesCache>,
     mut users_cache         : ResMut<UsersCache>,
+    invites_cache           : Option<ResMut<LobbyInvitesCache>>,
     user_server             : Res<HostUserServer>
 ) -> bool
 {
@@ -382,11 +503,22 @@ pub(crate) fn try_remove_user_from_lobby(
         }
     }
 
+    // access the lobby
+    let Some(lobby_ref) = lobbies_cache.lobby_ref(lobby_id)
+    else { tracing::error!(user_id, "could not remove user from lobby, lobby doesn't exist"); return false; };
+
+    // if user is lobby owner, try to promote another member to owner
+    if lobby_ref.is_owner(user_id)
+    {
+        if let Some(new_owner_id) = lobbies_cache.try_promote_successor(lobby_id)
+        { tracing::trace!(lobby_id, user_id, new_owner_id, "promoted new lobby owner to replace leaving owner"); }
+    }
+
     // access the lobby
     let Some(lobby_ref) = lobbies_cache.lobby_ref_mut(lobby_id)
     else { tracing::error!(user_id, "could not remove user from lobby, lobby doesn't exist"); return false; };
 
-    // if user is lobby owner, we need to discard the lobby
+    // if user is still lobby owner, we need to discard the lobby
     // otherwise, we need to remove the user and send lobby state updates
     match lobby_ref.is_owner(user_id)
     {
@@ -398,6 +530,12 @@ pub(crate) fn try_remove_user_from_lobby(
             // remove the lobby
             if let None = lobbies_cache.extract_lobby(lobby_id)
             { tracing::error!(lobby_id, "failed to remove lobby from lobbies cache when owner is removed"); }
+
+            // clean up the lobby's invites
+            if let Some(mut invites_cache) = invites_cache
+            {
+                invites_cache.revoke_lobby_invites(lobby_id);
+            }
         }
         false =>
         {
@@ -422,6 +560,30 @@ pub(crate) fn try_remove_user_from_lobby(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+pub(crate) fn try_remove_user_from_queue(
+    In(user_id)       : In<u128>,
+    mut users_cache   : ResMut<UsersCache>,
+    matchmaking_queue : Option<ResMut<MatchmakingQueueCache>>,
+) -> bool
+{
+    // check if user is in the queue
+    let Some(UserState::InQueue) = users_cache.get_user_state(user_id) else { return false; };
+
+    // remove the user from the queue
+    let Some(mut matchmaking_queue) = matchmaking_queue
+    else { tracing::error!(user_id, "user is queued but there is no matchmaking queue"); return false; };
+    if let Err(_) = matchmaking_queue.remove_user(user_id)
+    { tracing::error!(user_id, "user not in matchmaking queue as expected"); }
+
+    // update user state to idle
+    if let Err(_) = users_cache.update_user_state(user_id, UserState::Idle)
+    { tracing::error!(user_id, "failed setting user state to idle"); }
+
+    true
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(crate) fn try_remove_user_from_cache(
     In(user_id)     : In<u128>,
     mut users_cache : ResMut<UsersCache>,
@@ -436,6 +598,7 @@ pub(crate) fn try_remove_user_from_cache(
     match user_state
     {
         UserState::Idle              => (),
+        UserState::InQueue           => return false,
         UserState::InLobby(_)        => return false,
         UserState::InPendingLobby(_) => return false,
         UserState::InGame(_)         => (),
@@ -450,17 +613,52 @@ pub(crate) fn try_remove_user_from_cache(
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// collect the game hubs that may be asked to start a game with the given cost
+/// - removes hubs that already failed to start the game, hubs ruled out by the lobby's hub affinity, and hubs without
+///   enough available capacity for the game
+/// - the returned hubs are sorted by id
+/// - returns `Err(())` if every hub that may host the game already failed to start it
+fn collect_candidate_hubs(
+    game_hubs_cache      : &GameHubsCache,
+    hub_affinity_checker : Option<&HostHubAffinityChecker>,
+    failed_hubs          : &[u128],
+    lobby_data           : &LobbyData,
+    cost                 : u16,
+) -> Result<Vec<GameHubInfo>, ()>
+{
+    let mut hubs: Vec<GameHubInfo> = game_hubs_cache.hubs().collect();
+    hubs.sort_unstable_by_key(|hub| hub.id);
+    hubs.retain(|hub| !failed_hubs.contains(&hub.id));
+    if let Some(hub_affinity_checker) = hub_affinity_checker
+    {
+        hub_affinity_checker.0.hub_affinity(lobby_data).filter_hubs(&mut hubs, cost);
+    }
+
+    // if no eligible hubs remain besides the failed hubs, then the game can't be started by waiting for capacity
+    if hubs.is_empty() && !failed_hubs.is_empty() { return Err(()); }
+    hubs.retain(|hub| hub.available_capacity() >= cost as i32);
+
+    Ok(hubs)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// try to ask a game hub to start a game for the given user's pending lobby
-/// - returns `Ok(true)` if successful
+/// - returns `Ok(true)` if successful (or if the lobby is waiting in the launch queue for a game hub)
 /// - returns `Ok(false)` if lobby is not ready to launch
-/// - returns `Err(())` if unable to launch a game due to an error (e.g. no game hubs available)
+/// - returns `Err(())` if unable to launch a game due to an error (e.g. no game hubs available, or all eligible game
+///   hubs already failed to start the game)
 pub(crate) fn try_request_game_start(
-    In((user_id, lobby_id)) : In<(u128, u64)>,
-    pending_lobbies_cache   : Res<PendingLobbiesCache>,
-    mut game_hubs_cache     : ResMut<GameHubsCache>,
-    users_cache             : Res<UsersCache>,
-    ongoing_games_cache     : Res<OngoingGamesCache>,
-    hub_server              : Res<HostHubServer>,
+    In((user_id, lobby_id))   : In<(u128, u64)>,
+    mut pending_lobbies_cache : ResMut<PendingLobbiesCache>,
+    mut launch_queue          : Option<ResMut<GameLaunchQueueCache>>,
+    mut game_hubs_cache       : ResMut<GameHubsCache>,
+    mut hub_selector          : ResMut<HostHubSelector>,
+    hub_affinity_checker      : Option<Res<HostHubAffinityChecker>>,
+    cost_estimator            : Res<HostGameCostEstimator>,
+    users_cache               : Res<UsersCache>,
+    ongoing_games_cache       : Res<OngoingGamesCache>,
+    hub_server                : Res<HostHubServer>,
 ) -> Result<bool, ()>
 {
     // get the user's pending lobby
@@ -483,8 +681,54 @@ pub(crate) fn try_request_game_start(
     else { tracing::trace!(user_id, lobby_id, "could not request game start, lobby is not fully acked"); return Ok(false); };
 
     // get game hub for requesting a game slot
-    let Some(game_hub_id) = game_hubs_cache.highest_nonzero_capacity_hub()
-    else { tracing::warn!(user_id, lobby_id, "could not request game start, no available game hubs"); return Err(()); };
+    let game_start_request = GameStartRequest{ lobby_data: lobby_data_ref.clone() };
+    let cost = cost_estimator.cost(&game_start_request);
+    let Ok(hubs) = collect_candidate_hubs(
+            &game_hubs_cache,
+            hub_affinity_checker.as_deref(),
+            pending_lobbies_cache.failed_hubs(lobby_id),
+            &game_start_request.lobby_data,
+            cost,
+        )
+    else
+    {
+        tracing::warn!(user_id, lobby_id, "could not request game start, all eligible game hubs already failed");
+        return Err(());
+    };
+
+    // lobbies that aren't queued yet must wait behind already-queued lobbies so lobbies launch in queue order
+    let wait_for_queue = launch_queue.as_ref().is_some_and(|q| q.num_queued() > 0 && !q.has_lobby(lobby_id));
+    let selected_hub_id = if wait_for_queue { None } else { hub_selector.0.select_hub(&game_start_request, &hubs) };
+    let Some(game_hub_id) = selected_hub_id
+    else
+    {
+        // wait in the launch queue for a game hub to free up
+        if let Some(launch_queue) = &mut launch_queue
+        {
+            if launch_queue.add_lobby(&game_start_request.lobby_data)
+            {
+                tracing::trace!(user_id, lobby_id, "game hubs busy or launches queued ahead, queued game launch");
+                if let Err(_) = pending_lobbies_cache.set_lobby_queued(lobby_id, true)
+                { tracing::error!(lobby_id, "failed marking pending lobby as queued"); }
+            }
+            return Ok(true);
+        }
+
+        tracing::warn!(user_id, lobby_id, "could not request game start, no available game hubs");
+        return Err(());
+    };
+    if !hubs.iter().any(|hub| hub.id == game_hub_id)
+    { tracing::error!(user_id, lobby_id, game_hub_id, "hub selector selected an ineligible game hub"); return Err(()); }
+
+    // leave the launch queue
+    if let Some(launch_queue) = &mut launch_queue
+    {
+        if let Ok(()) = launch_queue.remove_launched_lobby(lobby_id)
+        {
+            if let Err(_) = pending_lobbies_cache.set_lobby_queued(lobby_id, false)
+            { tracing::error!(lobby_id, "failed marking pending lobby as unqueued"); }
+        }
+    }
 
     // check if game hub already has this game
     // - this can happen if the lobby was started on this game hub then the lobby expired and was re-acked
@@ -496,11 +740,11 @@ pub(crate) fn try_request_game_start(
     { tracing::warn!(game_hub_id, lobby_id, "skipped sending game start request, hub already has game"); return Ok(true); };
 
     // send request to game hub
-    hub_server.send(game_hub_id, HostToHubMsg::StartGame(GameStartRequest{ lobby_data: lobby_data_ref.clone() }));
+    hub_server.send(game_hub_id, HostToHubMsg::StartGame(game_start_request));
 
     // add pending game to game hub
     // - we assume the game hub is running this lobby's game until the hub explicitly notifies us otherwise
-    if let Err(_) = game_hubs_cache.add_pending_game(game_hub_id, lobby_id)
+    if let Err(_) = game_hubs_cache.add_pending_game(game_hub_id, lobby_id, cost)
     { tracing::error!(game_hub_id, lobby_id, "game hubs cache: game insertion error"); return Err(()); }
 
     Ok(true)
@@ -541,9 +785,28 @@ pub(crate) fn try_abort_hub_pending_game(In((game_hub_id, game_id)) : In<(u128,
     // - we access lobby data in order to re-start the game on behalf of the lobby owner
     let Some(lobby_data_ref) = world.resource::<PendingLobbiesCache>().try_get_full_acked_lobby(game_id)
     else { tracing::warn!(game_hub_id, game_id, "aborted hub's pending game has no pending lobby"); return true; };
+    let (owner_id, lobby_id) = (lobby_data_ref.owner_id, lobby_data_ref.id);
+
+    // record the failed attempt so the game will be retried on a different hub
+    // - we give up if the lobby has run out of attempts
+    if let Some(retry_policy) = world.get_resource::<GameStartRetryPolicy>().copied()
+    {
+        let mut pending_lobbies_cache = world.resource_mut::<PendingLobbiesCache>();
+        if let Err(_) = pending_lobbies_cache.add_failed_hub(lobby_id, game_hub_id)
+        { tracing::error!(game_hub_id, lobby_id, "failed recording failed hub for pending lobby"); }
+
+        let num_attempts = pending_lobbies_cache.failed_hubs(lobby_id).len();
+        if num_attempts >= retry_policy.max_attempts as usize
+        {
+            tracing::warn!(game_hub_id, lobby_id, num_attempts, "game start attempts exhausted; aborting pending lobby");
+            if !world.syscall((owner_id, Some(lobby_id)), force_nack_pending_lobby)
+            { tracing::error!(owner_id, lobby_id, "failed aborting pending lobby"); }
+            return true;
+        }
+    }
 
     // try to start the game again (hopefully on a game hub with sufficient capacity)
-    attempt_game_start_request(world, lobby_data_ref.owner_id, lobby_data_ref.id);
+    attempt_game_start_request(world, owner_id, lobby_id);
 
     true
 }
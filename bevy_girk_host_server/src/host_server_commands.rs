@@ -0,0 +1,76 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Error returned by [`HostServerCommand`]s that target a specific user, lobby, game, or game hub.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HostServerCommandError
+{
+    UserNotFound,
+    LobbyNotFound,
+    GameNotFound,
+    GameHubNotFound,
+    /// The user is known but currently disconnected (it is waiting in the user disconnect buffer).
+    UserNotConnected,
+    /// The game hub is known but currently disconnected (it is waiting in the game hub disconnect buffer).
+    GameHubNotConnected,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A lobby listed by [`HostServerCommand::ListLobbies`].
+#[derive(Debug, Clone)]
+pub struct HostServerLobbyInfo
+{
+    /// The lobby.
+    pub lobby: Lobby,
+    /// Indicates if the lobby is waiting for its members to ack a game launch.
+    pub pending: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Commands for controlling a host server at runtime.
+///
+/// Each command carries a sender for its response. Responses are sent while the host server handles the command.
+#[derive(Debug)]
+pub enum HostServerCommand
+{
+    /// List all registered users.
+    ListUsers(Sender<Vec<(u128, UserInfo)>>),
+    /// List all open and pending lobbies.
+    ListLobbies(Sender<Vec<HostServerLobbyInfo>>),
+    /// List all ongoing games.
+    ListOngoingGames(Sender<Vec<OngoingGame>>),
+    /// List all registered game hubs.
+    ListGameHubs(Sender<Vec<GameHubInfo>>),
+    /// Close an open or pending lobby.
+    /// - Lobby members will be sent [`HostToUserMsg::LobbyLeave`].
+    CloseLobby{ id: u64, response: Sender<Result<(), HostServerCommandError>> },
+    /// Abort an ongoing game.
+    /// - Game participants will be sent [`HostToUserMsg::GameAborted`], and the game's hub will be told to abort the
+    ///   game.
+    AbortGame{ id: u64, response: Sender<Result<(), HostServerCommandError>> },
+    /// Disconnect a user.
+    /// - The user will be handled as if they disconnected on their own.
+    /// - Returns [`HostServerCommandError::UserNotConnected`] if the user is already disconnected.
+    DisconnectUser{ id: u128, response: Sender<Result<(), HostServerCommandError>> },
+    /// Disconnect a game hub.
+    /// - The hub will be handled as if it disconnected on its own.
+    /// - Returns [`HostServerCommandError::GameHubNotConnected`] if the hub is already disconnected.
+    DisconnectGameHub{ id: u128, response: Sender<Result<(), HostServerCommandError>> },
+    /// Replace the lobbies cache config.
+    SetLobbiesCacheConfig{ config: LobbiesCacheConfig, response: Sender<()> },
+    /// Replace the pending lobbies cache config.
+    SetPendingLobbiesConfig{ config: PendingLobbiesConfig, response: Sender<()> },
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,76 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_backend_public::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Game hub placement constraints for a lobby's game.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct HubAffinity
+{
+    /// The game may only be placed on hubs in one of these regions.
+    /// - Ignored if empty.
+    pub required_regions: Vec<String>,
+    /// The game should be placed on a hub in one of these regions if any of those hubs have available capacity.
+    pub preferred_regions: Vec<String>,
+    /// The game may only be placed on hubs that have all of these tags.
+    pub required_tags: Vec<String>,
+}
+
+impl HubAffinity
+{
+    /// Check if a hub satisfies the affinity's requirements.
+    pub fn allows(&self, metadata: &GameHubMetadata) -> bool
+    {
+        if !self.required_regions.is_empty() && !Self::in_regions(metadata, &self.required_regions) { return false; }
+        self.required_tags.iter().all(|tag| metadata.has_tag(tag))
+    }
+
+    /// Check if a hub is in one of the affinity's preferred regions.
+    pub fn prefers(&self, metadata: &GameHubMetadata) -> bool
+    {
+        Self::in_regions(metadata, &self.preferred_regions)
+    }
+
+    /// Filter a hub list down to the hubs a game with the given cost should be placed on.
+    /// - Hubs that don't satisfy the affinity's requirements are removed.
+    /// - If any preferred hubs have enough available capacity for the game, all non-preferred hubs are removed.
+    pub fn filter_hubs(&self, hubs: &mut Vec<GameHubInfo>, cost: u16)
+    {
+        hubs.retain(|hub| self.allows(&hub.metadata));
+
+        let min_capacity = (cost as i32).max(1);
+        if hubs.iter().any(|hub| self.prefers(&hub.metadata) && hub.available_capacity() >= min_capacity)
+        {
+            hubs.retain(|hub| self.prefers(&hub.metadata));
+        }
+    }
+
+    fn in_regions(metadata: &GameHubMetadata, regions: &[String]) -> bool
+    {
+        let Some(region) = &metadata.region else { return false; };
+        regions.iter().any(|r| r == region)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Trait for deriving game hub placement constraints from lobbies.
+pub trait HubAffinityChecker: Debug + Send + Sync + 'static
+{
+    /// Get the hub affinity of a lobby that is launching a game.
+    fn hub_affinity(&self, lobby: &LobbyData) -> HubAffinity;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource, Debug)]
+pub(crate) struct HostHubAffinityChecker(pub(crate) Box<dyn HubAffinityChecker>);
+
+//-------------------------------------------------------------------------------------------------------------------
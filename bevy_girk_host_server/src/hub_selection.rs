@@ -0,0 +1,94 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_backend_public::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Trait for choosing which game hub should host a new game.
+pub trait HubSelector: Debug + Send + Sync + 'static
+{
+    /// Select a hub for the given game start request.
+    /// - `hubs` contains the hubs that may host the game (e.g. hubs with enough available capacity for the game's
+    ///   cost), sorted by hub id.
+    /// - Returns `None` if no hub can host the game.
+    fn select_hub(&mut self, request: &GameStartRequest, hubs: &[GameHubInfo]) -> Option<u128>;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource, Debug)]
+pub(crate) struct HostHubSelector(pub(crate) Box<dyn HubSelector>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`HubSelector`] that selects the hub with the highest available capacity.
+///
+/// This spreads games evenly across hubs. It is the default hub selector.
+#[derive(Debug, Default)]
+pub struct GreedyHubSelector;
+
+impl HubSelector for GreedyHubSelector
+{
+    fn select_hub(&mut self, _request: &GameStartRequest, hubs: &[GameHubInfo]) -> Option<u128>
+    {
+        hubs.iter()
+            .filter(|hub| hub.available_capacity() > 0)
+            .max_by_key(|hub| (hub.available_capacity(), hub.id))
+            .map(|hub| hub.id)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`HubSelector`] that cycles through hubs with available capacity in order of hub id.
+#[derive(Debug, Default)]
+pub struct RoundRobinHubSelector
+{
+    /// id of the most recently selected hub
+    prev_hub: Option<u128>,
+}
+
+impl HubSelector for RoundRobinHubSelector
+{
+    fn select_hub(&mut self, _request: &GameStartRequest, hubs: &[GameHubInfo]) -> Option<u128>
+    {
+        let mut available = hubs.iter().filter(|hub| hub.available_capacity() > 0);
+        let first = available.clone().next()?;
+        let selected = match self.prev_hub
+        {
+            Some(prev_hub) => available.find(|hub| hub.id > prev_hub).unwrap_or(first),
+            None           => first,
+        };
+
+        self.prev_hub = Some(selected.id);
+        Some(selected.id)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`HubSelector`] that fills hubs before using new ones.
+///
+/// Selects the busy hub with the least available capacity that can still host the game (i.e. packs games by cost), so
+/// idle hubs stay idle and can be scaled down. Idle hubs are only used when no busy hub can host the game.
+#[derive(Debug, Default)]
+pub struct BinPackingHubSelector;
+
+impl HubSelector for BinPackingHubSelector
+{
+    fn select_hub(&mut self, _request: &GameStartRequest, hubs: &[GameHubInfo]) -> Option<u128>
+    {
+        hubs.iter()
+            .filter(|hub| hub.available_capacity() > 0)
+            .min_by_key(|hub| (hub.num_pending + hub.num_games == 0, hub.available_capacity(), hub.id))
+            .map(|hub| hub.id)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
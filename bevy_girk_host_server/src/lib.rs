@@ -2,34 +2,66 @@
 
 //module tree
 mod cache_game_hubs;
+mod cache_game_launch_queue;
 mod cache_lobbies;
+mod cache_lobby_chat;
+mod cache_lobby_invites;
+mod cache_matchmaking_queue;
 mod cache_ongoing_games;
 mod cache_pending_lobbies;
+mod cache_user_rate_limits;
 mod cache_users;
 mod channel_game_hub;
 mod channel_user;
 mod cleanup_handlers;
 mod dc_buffer_game_hubs;
+mod dc_buffer_users;
+mod game_cost;
+mod game_launch_queue;
+mod handle_commands;
 mod handle_game_hub_incoming;
 mod handle_game_hub_incoming_impl;
 mod handle_user_incoming;
 mod handle_user_incoming_impl;
 mod handler_utils;
+mod host_server_commands;
+mod hub_affinity;
+mod hub_selection;
+mod matchmaking;
+mod metrics;
+mod persistence;
 mod server_setup;
+mod user_authentication;
 
 //API exports
 pub use crate::cache_game_hubs::*;
+pub use crate::cache_game_launch_queue::*;
 pub use crate::cache_lobbies::*;
+pub use crate::cache_lobby_chat::*;
+pub use crate::cache_lobby_invites::*;
+pub use crate::cache_matchmaking_queue::*;
 pub use crate::cache_ongoing_games::*;
 pub use crate::cache_pending_lobbies::*;
+pub use crate::cache_user_rate_limits::*;
 pub use crate::cache_users::*;
 pub use crate::channel_game_hub::*;
 pub use crate::channel_user::*;
 pub(crate) use crate::cleanup_handlers::*;
 pub use crate::dc_buffer_game_hubs::*;
+pub use crate::dc_buffer_users::*;
+pub use crate::game_cost::*;
+pub(crate) use crate::game_launch_queue::*;
+pub(crate) use crate::handle_commands::*;
 pub(crate) use crate::handle_game_hub_incoming::*;
 pub(crate) use crate::handle_game_hub_incoming_impl::*;
 pub(crate) use crate::handle_user_incoming::*;
 pub(crate) use crate::handle_user_incoming_impl::*;
 pub(crate) use crate::handler_utils::*;
+pub use crate::host_server_commands::*;
+pub use crate::hub_affinity::*;
+pub use crate::hub_selection::*;
+pub(crate) use crate::matchmaking::*;
+pub use crate::metrics::*;
+pub use crate::persistence::*;
 pub use crate::server_setup::*;
+pub use crate::user_authentication::*;
@@ -0,0 +1,95 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_backend_public::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::collections::HashSet;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Assemble a lobby from a match.
+/// - Returns the users that should be skipped for the rest of the matchmaking pass if the lobby is rejected by the
+///   lobby checker.
+fn try_make_matched_lobby(
+    matched           : MatchmakerMatch,
+    matchmaking_queue : &MatchmakingQueueCache,
+    lobbies_cache     : &mut LobbiesCache,
+) -> Result<Lobby, Vec<u128>>
+{
+    // prepare lobby
+    // - matched lobbies have no password
+    // - the lobby id is only generated once the match is accepted
+    let mut lobby = Lobby::new(0u64, matched.owner_id, String::default(), matched.serialized_custom_data);
+
+    // check lobby is valid
+    let lobby_checker = lobbies_cache.lobby_checker();
+    if !lobby_checker.check_lobby(&lobby)
+    { tracing::warn!(?matched.members, "discarding match, lobby is invalid"); return Err(matched.members); }
+
+    // add members
+    for member_id in matched.members.iter()
+    {
+        let Some(queued_user) = matchmaking_queue.queued_user(*member_id)
+        else { tracing::error!(member_id, "discarding match, member is not queued"); return Err(matched.members); };
+
+        if let Err(err) = lobby_checker.allow_new_member(&lobby, *member_id, queued_user.member_data, lobby.get_password())
+        { tracing::warn!(member_id, ?err, "discarding match, member was rejected"); return Err(vec![*member_id]); }
+
+        lobby.add_member(*member_id, queued_user.member_data);
+    }
+
+    // check lobby can be launched
+    if !lobby_checker.can_launch(&lobby)
+    { tracing::warn!(?matched.members, "discarding match, lobby cannot be launched"); return Err(matched.members); }
+
+    // assign the lobby id
+    lobby.data.id = lobbies_cache.generate_lobby_id();
+
+    Ok(lobby)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Group queued users into lobbies and move the lobbies to the pending lobbies cache.
+/// - If a match is discarded, the offending users stay in the queue but are skipped for the rest of the pass.
+pub(crate) fn run_matchmaking(
+    mut matchmaking_queue     : ResMut<MatchmakingQueueCache>,
+    mut lobbies_cache         : ResMut<LobbiesCache>,
+    mut pending_lobbies_cache : ResMut<PendingLobbiesCache>,
+    mut users_cache           : ResMut<UsersCache>,
+    user_server               : Res<HostUserServer>,
+){
+    let mut skipped = HashSet::default();
+    while let Some(matched) = matchmaking_queue.try_next_match(&mut skipped)
+    {
+        // make a lobby for the match
+        let lobby = match try_make_matched_lobby(matched, &matchmaking_queue, &mut lobbies_cache)
+        {
+            Ok(lobby) => lobby,
+            Err(rejected) => { skipped.extend(rejected); continue; }
+        };
+        tracing::trace!(lobby.data.id, num_members = lobby.num_members(), "matched users into new lobby");
+
+        // remove matched users from the queue
+        for (member_id, _) in lobby.data.members.iter()
+        {
+            if let Err(_) = matchmaking_queue.remove_user(*member_id)
+            { tracing::error!(lobby.data.id, member_id, "failed removing matched user from queue"); }
+        }
+
+        // send lobby to users
+        send_lobby_state_messages(&lobby.data, &user_server);
+
+        // send ack requests to users and update states to 'in pending lobby'
+        send_pending_lobby_ack_requests_and_update_states(&lobby.data, &mut users_cache, &user_server);
+
+        // move lobby to pending
+        // - warning: failure here is a critical error
+        if let Err(_) = pending_lobbies_cache.add_lobby(lobby) { tracing::error!("insert pending lobby error"); }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
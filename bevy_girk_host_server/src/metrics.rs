@@ -0,0 +1,144 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Exports host server metrics.
+///
+/// Cache sizes are sampled periodically, and lifecycle events are counted as they happen. Metrics are served in
+/// Prometheus text format on a local HTTP listener (see [`MetricsConfig`]).
+#[derive(Resource, Debug)]
+pub struct HostServerMetrics
+{
+    /// metrics exporter
+    exporter: MetricsExporter,
+
+    /// number of pending lobbies that expired before their games started
+    pub(crate) pending_lobby_expirations: u64,
+    /// number of pending lobbies that were nacked
+    pub(crate) pending_lobby_nacks: u64,
+    /// number of games aborted by game hubs
+    pub(crate) hub_aborts: u64,
+    /// number of ongoing games that expired
+    pub(crate) ongoing_game_expirations: u64,
+}
+
+impl HostServerMetrics
+{
+    /// Make new metrics.
+    pub fn new(exporter: MetricsExporter) -> Self
+    {
+        Self{
+            exporter,
+            pending_lobby_expirations : 0,
+            pending_lobby_nacks       : 0,
+            hub_aborts                : 0,
+            ongoing_game_expirations  : 0,
+        }
+    }
+
+    /// Get the address metrics are served on.
+    pub fn local_addr(&self) -> std::net::SocketAddr
+    {
+        self.exporter.local_addr()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub(crate) fn sample_host_server_metrics(
+    metrics               : Res<HostServerMetrics>,
+    lobbies_cache         : Res<LobbiesCache>,
+    pending_lobbies_cache : Res<PendingLobbiesCache>,
+    ongoing_games_cache   : Res<OngoingGamesCache>,
+    users_cache           : Res<UsersCache>,
+    game_hubs_cache       : Res<GameHubsCache>,
+){
+    let mut writer = PrometheusWriter::default();
+
+    // caches
+    writer.gauge("girk_host_lobbies", "Number of open lobbies.", lobbies_cache.lobbies().count() as f64);
+    writer.gauge(
+            "girk_host_pending_lobbies",
+            "Number of lobbies waiting for game launch acks.",
+            pending_lobbies_cache.lobbies().count() as f64
+        );
+    writer.gauge(
+            "girk_host_ongoing_games",
+            "Number of ongoing games.",
+            ongoing_games_cache.games().count() as f64
+        );
+
+    let mut user_states = [0usize; 5];
+    for (_, user_info) in users_cache.users()
+    {
+        let index = match user_info.user_state()
+        {
+            UserState::Idle              => 0,
+            UserState::InQueue           => 1,
+            UserState::InLobby(_)        => 2,
+            UserState::InPendingLobby(_) => 3,
+            UserState::InGame(_)         => 4,
+        };
+        user_states[index] += 1;
+    }
+    writer.gauge_family(
+            "girk_host_users",
+            "Number of connected users by state.",
+            ["idle", "in_queue", "in_lobby", "in_pending_lobby", "in_game"]
+                .iter()
+                .zip(user_states.iter())
+                .map(|(state, count)| (format!("state=\"{state}\""), *count as f64))
+        );
+
+    // game hubs
+    writer.gauge("girk_host_game_hubs", "Number of registered game hubs.", game_hubs_cache.num_hubs() as f64);
+    writer.gauge_family(
+            "girk_host_game_hub_capacity",
+            "Last reported capacity of each game hub.",
+            game_hubs_cache.hubs().map(|hub| (format!("hub_id=\"{}\"", hub.id), hub.capacity.0 as f64))
+        );
+    writer.gauge_family(
+            "girk_host_game_hub_pending_games",
+            "Number of pending game requests on each game hub.",
+            game_hubs_cache.hubs().map(|hub| (format!("hub_id=\"{}\"", hub.id), hub.num_pending as f64))
+        );
+    writer.gauge_family(
+            "girk_host_game_hub_games",
+            "Number of games running on each game hub.",
+            game_hubs_cache.hubs().map(|hub| (format!("hub_id=\"{}\"", hub.id), hub.num_games as f64))
+        );
+
+    // lifecycle events
+    writer.counter(
+            "girk_host_pending_lobby_expirations_total",
+            "Number of pending lobbies that expired.",
+            metrics.pending_lobby_expirations
+        );
+    writer.counter(
+            "girk_host_pending_lobby_nacks_total",
+            "Number of pending lobbies that were nacked.",
+            metrics.pending_lobby_nacks
+        );
+    writer.counter(
+            "girk_host_hub_aborts_total",
+            "Number of games aborted by game hubs.",
+            metrics.hub_aborts
+        );
+    writer.counter(
+            "girk_host_ongoing_game_expirations_total",
+            "Number of ongoing games that expired.",
+            metrics.ongoing_game_expirations
+        );
+
+    metrics.exporter.publish(writer.finish());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
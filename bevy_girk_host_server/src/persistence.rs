@@ -0,0 +1,219 @@
+//local shortcuts
+use crate::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+use renet2_setup::ConnectionType;
+use serde::{Deserialize, Serialize};
+
+//standard shortcuts
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Serializable snapshot of host server state.
+///
+/// User states are not saved directly. Lobby members are re-registered from their lobbies when a snapshot is
+/// restored, and users in ongoing games are reconnected via the ongoing games cache when they reconnect.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct HostServerSnapshot
+{
+    /// Number of lobby ids generated so far.
+    /// - Used to prevent new lobbies from colliding with restored lobbies and ongoing games.
+    pub lobby_id_counter: u64,
+    /// Open lobbies.
+    /// - Pending lobbies are saved as open lobbies, since acks are not persisted.
+    pub lobbies: Vec<Lobby>,
+    /// Ongoing games.
+    pub ongoing_games: Vec<OngoingGame>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Trait for host server snapshot backends.
+pub trait HostServerPersistence: Debug + Send + Sync + 'static
+{
+    /// Load the most recently saved snapshot.
+    /// - Returns `None` if there is no snapshot available.
+    fn load(&mut self) -> Option<HostServerSnapshot>;
+
+    /// Save a snapshot, replacing the previous snapshot.
+    fn save(&mut self, snapshot: &HostServerSnapshot) -> Result<(), String>;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Saves host server snapshots to a local file.
+///
+/// Snapshots are written to a temporary file that is then moved to the target path, so a crash while saving won't
+/// corrupt the previous snapshot.
+#[derive(Debug)]
+pub struct HostServerSnapshotFile
+{
+    /// Path to the snapshot file.
+    path: PathBuf,
+}
+
+impl HostServerSnapshotFile
+{
+    pub fn new(path: impl Into<PathBuf>) -> Self
+    {
+        Self{ path: path.into() }
+    }
+}
+
+impl HostServerPersistence for HostServerSnapshotFile
+{
+    fn load(&mut self) -> Option<HostServerSnapshot>
+    {
+        let bytes = match std::fs::read(&self.path)
+        {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(err) =>
+            {
+                tracing::error!(?self.path, ?err, "failed reading host server snapshot");
+                return None;
+            }
+        };
+
+        let Some(snapshot) = deser_msg::<HostServerSnapshot>(&bytes)
+        else { tracing::error!(?self.path, "failed deserializing host server snapshot"); return None; };
+
+        Some(snapshot)
+    }
+
+    fn save(&mut self, snapshot: &HostServerSnapshot) -> Result<(), String>
+    {
+        let temp_path = self.path.with_extension("tmp");
+        std::fs::write(&temp_path, ser_msg(snapshot)).map_err(|err| format!("{err:?}"))?;
+        std::fs::rename(&temp_path, &self.path).map_err(|err| format!("{err:?}"))?;
+
+        Ok(())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub struct HostServerPersistenceConfig
+{
+    /// Number of ticks to wait between each snapshot.
+    pub snapshot_period_ticks: u64,
+    /// Amount of time a restored lobby member may take to reconnect before they are removed from their lobby.
+    pub restored_user_expiry: Duration,
+    /// Snapshot backend.
+    pub backend: Box<dyn HostServerPersistence>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Holds the snapshot backend.
+#[derive(Resource, Debug)]
+pub(crate) struct HostServerPersistenceBackend(pub(crate) Box<dyn HostServerPersistence>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Collect a snapshot of the host server's current state.
+pub(crate) fn collect_host_server_snapshot(world: &World) -> HostServerSnapshot
+{
+    let lobbies_cache         = world.resource::<LobbiesCache>();
+    let pending_lobbies_cache = world.resource::<PendingLobbiesCache>();
+    let ongoing_games_cache   = world.resource::<OngoingGamesCache>();
+
+    HostServerSnapshot{
+        lobby_id_counter: lobbies_cache.generated_count(),
+        lobbies: lobbies_cache.lobbies()
+            .chain(pending_lobbies_cache.lobbies())
+            .cloned()
+            .collect(),
+        ongoing_games: ongoing_games_cache.games().cloned().collect(),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Save a snapshot of the host server's current state.
+pub(crate) fn save_host_server_snapshot(world: &mut World)
+{
+    let snapshot = collect_host_server_snapshot(world);
+    let Some(mut backend) = world.get_resource_mut::<HostServerPersistenceBackend>() else { return; };
+
+    match backend.0.save(&snapshot)
+    {
+        Ok(())   => tracing::trace!(num_lobbies = snapshot.lobbies.len(), num_games = snapshot.ongoing_games.len(),
+            "saved host server snapshot"),
+        Err(err) => tracing::error!(?err, "failed saving host server snapshot"),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Restore host server state from a snapshot.
+/// - Lobby members are registered as disconnected users and placed in the [`UserDisconnectBuffer`].
+/// - Game hubs running ongoing games are registered as disconnected hubs and placed in the
+///   [`GameHubDisconnectBuffer`], so they can re-own their games when they reconnect.
+/// - Expiry timers for restored items start over.
+pub(crate) fn restore_host_server_snapshot(world: &mut World, snapshot: HostServerSnapshot)
+{
+    tracing::info!(num_lobbies = snapshot.lobbies.len(), num_games = snapshot.ongoing_games.len(),
+        "restoring host server snapshot");
+
+    // prepare lobby id generator
+    world.resource_mut::<LobbiesCache>().raise_generated_count(snapshot.lobby_id_counter);
+
+    // restore lobbies
+    for lobby in snapshot.lobbies
+    {
+        let lobby_id = lobby.id();
+        let members: Vec<(u128, ConnectionType)> = lobby.data.members
+            .iter()
+            .map(|(member_id, member_data)| (*member_id, member_data.connection))
+            .collect();
+
+        if let Err(_) = world.resource_mut::<LobbiesCache>().insert_lobby(lobby)
+        { tracing::warn!(lobby_id, "failed restoring lobby"); continue; }
+
+        // register lobby members as disconnected users
+        for (member_id, connection) in members
+        {
+            let user_info = UserInfo::new(bevy_simplenet::EnvType::Native, connection);
+            let mut users_cache = world.resource_mut::<UsersCache>();
+            if let Err(_) = users_cache.add_user(member_id, user_info)
+            { tracing::warn!(lobby_id, member_id, "failed restoring lobby member"); continue; }
+            if let Err(_) = users_cache.update_user_state(member_id, UserState::InLobby(lobby_id))
+            { tracing::error!(lobby_id, member_id, "failed updating restored user state to in-lobby"); }
+
+            if let Err(_) = world.resource_mut::<UserDisconnectBuffer>().add_user(member_id)
+            { tracing::error!(lobby_id, member_id, "failed buffering restored lobby member"); }
+        }
+    }
+
+    // restore ongoing games
+    for ongoing_game in snapshot.ongoing_games
+    {
+        let game_id     = ongoing_game.game_id;
+        let game_hub_id = ongoing_game.game_hub_id;
+
+        if let Err(_) = world.resource_mut::<OngoingGamesCache>().add_ongoing_game(ongoing_game)
+        { tracing::warn!(game_id, "failed restoring ongoing game"); continue; }
+
+        // register the game's hub as disconnected until it reconnects
+        if !world.resource::<GameHubsCache>().has_hub(game_hub_id)
+        {
+            if let Err(_) = world.resource_mut::<GameHubsCache>().insert_hub(game_hub_id)
+            { tracing::error!(game_hub_id, "failed restoring game hub"); }
+            if let Err(_) = world.resource_mut::<GameHubDisconnectBuffer>().add_game_hub(game_hub_id)
+            { tracing::error!(game_hub_id, "failed buffering restored game hub"); }
+        }
+
+        if let Err(_) = world.resource_mut::<GameHubsCache>().add_game(game_hub_id, game_id)
+        { tracing::error!(game_hub_id, game_id, "failed restoring game hub's game"); }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -7,7 +7,7 @@ use bevy::app::*;
 use bevy::prelude::*;
 
 //standard shortcuts
-
+use std::time::Duration;
 
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
@@ -34,6 +34,73 @@ fn init_resources(app: &mut App, startup_pack: HostServerStartupPack)
     app.insert_resource(OngoingGamesCache::new(startup_pack.ongoing_games_cache_config));
     app.insert_resource(UsersCache::default());
     app.insert_resource(GameHubDisconnectBuffer::new(startup_pack.game_hub_disconnect_buffer_config));
+    if let Some(metrics_config) = startup_pack.metrics_config
+    {
+        match MetricsExporter::new(metrics_config.listen_addr)
+        {
+            Ok(exporter) => { app.insert_resource(HostServerMetrics::new(exporter)); }
+            Err(err) => tracing::error!(?err, "failed making metrics exporter, metrics are disabled"),
+        }
+    }
+    if let Some(user_rate_limits) = startup_pack.host_server_config.user_rate_limits
+    {
+        app.insert_resource(UserRateLimitsCache::new(user_rate_limits));
+    }
+    if let Some(game_start_retries) = startup_pack.host_server_config.game_start_retries
+    {
+        app.insert_resource(game_start_retries);
+    }
+    if let Some(lobby_chat_config) = startup_pack.lobby_chat_config
+    {
+        app.insert_resource(LobbyChatCache::new(lobby_chat_config));
+    }
+    if let Some(lobby_invites_config) = startup_pack.lobby_invites_config
+    {
+        app.insert_resource(LobbyInvitesCache::new(lobby_invites_config));
+    }
+    if let Some(matchmaking_queue_config) = startup_pack.matchmaking_queue_config
+    {
+        app.insert_resource(MatchmakingQueueCache::new(matchmaking_queue_config));
+    }
+    if let Some(game_launch_queue_config) = startup_pack.game_launch_queue_config
+    {
+        app.insert_resource(GameLaunchQueueCache::new(game_launch_queue_config));
+    }
+    if let Some(user_authenticator) = startup_pack.user_authenticator
+    {
+        app.insert_resource(HostUserAuthenticator(user_authenticator));
+    }
+    if let Some(hub_affinity_checker) = startup_pack.hub_affinity_checker
+    {
+        app.insert_resource(HostHubAffinityChecker(hub_affinity_checker));
+    }
+    app.insert_resource(HostHubSelector(
+        startup_pack.hub_selector.unwrap_or_else(|| Box::new(GreedyHubSelector))
+    ));
+    app.insert_resource(HostGameCostEstimator(
+        startup_pack.game_cost_estimator.unwrap_or_else(|| Box::new(UnitGameCost))
+    ));
+
+    // restore persisted state
+    let Some(persistence_config) = startup_pack.persistence_config
+    else
+    {
+        app.insert_resource(UserDisconnectBuffer::new(
+            UserDisconnectBufferConfig{ expiry_duration: Duration::default() }
+        ));
+        return;
+    };
+
+    app.insert_resource(UserDisconnectBuffer::new(
+        UserDisconnectBufferConfig{ expiry_duration: persistence_config.restored_user_expiry }
+    ));
+    let mut backend = persistence_config.backend;
+    if let Some(snapshot) = backend.load()
+    {
+        restore_host_server_snapshot(app.world_mut(), snapshot);
+    }
+    else { tracing::info!("no host server snapshot to restore"); }
+    app.insert_resource(HostServerPersistenceBackend(backend));
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -46,6 +113,10 @@ pub struct HostServerConfig
     pub ticks_per_sec: Option<u16>,
     /// number of ticks to wait between each ongoing game cache purge
     pub ongoing_game_purge_period_ticks: u64,
+    /// per-user request rate limits (optional: user requests will not be rate limited if `None`)
+    pub user_rate_limits: Option<UserRateLimitConfig>,
+    /// game start retry policy (optional: games aborted by hubs will be retried on any hub without limit if `None`)
+    pub game_start_retries: Option<GameStartRetryPolicy>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -65,12 +136,37 @@ pub struct HostServerStartupPack
 
     /// config: game hub disconnect buffer
     pub game_hub_disconnect_buffer_config: GameHubDisconnectBufferConfig,
+
+    /// config: lobby chat (optional: lobby chat messages will be dropped if `None`)
+    pub lobby_chat_config: Option<LobbyChatConfig>,
+    /// config: lobby invites (optional: users cannot make lobby invites if `None`)
+    pub lobby_invites_config: Option<LobbyInvitesConfig>,
+    /// config: matchmaking queue (optional: users cannot enter the queue if `None`)
+    pub matchmaking_queue_config: Option<MatchmakingQueueConfig>,
+    /// config: game launch queue (optional: lobbies will fail to launch if no game hubs are available if `None`)
+    pub game_launch_queue_config: Option<GameLaunchQueueConfig>,
+
+    /// config: persistence (optional: state will not be saved or restored if `None`)
+    pub persistence_config: Option<HostServerPersistenceConfig>,
+    /// config: metrics (optional: metrics will not be exported if `None`)
+    pub metrics_config: Option<MetricsConfig>,
+
+    /// user authenticator (optional: all users will be accepted without an account id if `None`)
+    pub user_authenticator: Option<Box<dyn UserAuthenticator>>,
+    /// game hub selector (optional: [`GreedyHubSelector`] will be used if `None`)
+    pub hub_selector: Option<Box<dyn HubSelector>>,
+    /// game hub affinity checker (optional: games may be placed on any hub if `None`)
+    pub hub_affinity_checker: Option<Box<dyn HubAffinityChecker>>,
+    /// game cost estimator (optional: [`UnitGameCost`] will be used if `None`)
+    /// - Should match the estimator used by game hubs.
+    pub game_cost_estimator: Option<Box<dyn GameCostEstimator>>,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
 pub fn make_host_server(
     startup_pack     : HostServerStartupPack,
+    command_receiver : Receiver<HostServerCommand>,
     host_hub_server  : HostHubServer,
     host_user_server : HostUserServer,
 ) -> App
@@ -85,9 +181,13 @@ pub fn make_host_server(
     // set schedule runner
     let host_server_config = startup_pack.host_server_config;
     set_schedule_runner(&mut app, host_server_config.ticks_per_sec);
+    let matchmaking_period_ticks = startup_pack.matchmaking_queue_config.as_ref().map(|c| c.matchmaking_period_ticks);
+    let snapshot_period_ticks = startup_pack.persistence_config.as_ref().map(|c| c.snapshot_period_ticks);
+    let metrics_period_ticks = startup_pack.metrics_config.as_ref().map(|c| c.sample_period_ticks);
 
     // initialize server resources
     init_resources(&mut app, startup_pack);
+    app.insert_resource(command_receiver);
     app.insert_resource(host_hub_server);
     app.insert_resource(host_user_server);
 
@@ -95,6 +195,7 @@ pub fn make_host_server(
     app.add_systems(Main,
         (
             increment_tick_counter,
+            handle_commands,
             handle_user_incoming,
             handle_game_hub_incoming,
             clean_pending_lobbies,  //no purge period since cache should be relatively small
@@ -102,10 +203,53 @@ pub fn make_host_server(
                 on_tick_counter(host_server_config.ongoing_game_purge_period_ticks)
             ),
             clean_game_hub_dc_buffer,  //no purge period since cache should be relatively small
+            clean_user_dc_buffer,      //no purge period since cache should be relatively small
+            clean_user_rate_limits.run_if(resource_exists::<UserRateLimitsCache>),
+            clean_lobby_invites.run_if(resource_exists::<LobbyInvitesCache>),
         )
             .chain()
     );
 
+    // add matchmaking systems
+    if let Some(matchmaking_period_ticks) = matchmaking_period_ticks
+    {
+        app.add_systems(Main,
+            run_matchmaking
+                .run_if(on_tick_counter(matchmaking_period_ticks))
+                .after(handle_game_hub_incoming)
+                .before(clean_pending_lobbies)
+        );
+    }
+
+    // add game launch queue systems
+    app.add_systems(Main,
+        update_game_launch_queue
+            .run_if(resource_exists::<GameLaunchQueueCache>)
+            .after(handle_game_hub_incoming)
+            .before(clean_pending_lobbies)
+    );
+
+    // add persistence systems
+    if let Some(snapshot_period_ticks) = snapshot_period_ticks
+    {
+        app.add_systems(Main,
+            save_host_server_snapshot
+                .run_if(on_tick_counter(snapshot_period_ticks).or(on_event::<AppExit>))
+                .after(clean_user_dc_buffer)
+        );
+    }
+
+    // add metrics systems
+    if let Some(metrics_period_ticks) = metrics_period_ticks
+    {
+        app.add_systems(Main,
+            sample_host_server_metrics
+                .run_if(resource_exists::<HostServerMetrics>)
+                .run_if(on_tick_counter(metrics_period_ticks))
+                .after(clean_user_dc_buffer)
+        );
+    }
+
     app
 }
 
@@ -0,0 +1,114 @@
+//local shortcuts
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+//standard shortcuts
+use std::fmt::Debug;
+use std::time::{Duration, SystemTime};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Trait for authenticating users when they connect to the host server.
+pub trait UserAuthenticator: Debug + Send + Sync + 'static
+{
+    /// Authenticate a connecting user from the auth payload in their [`HostUserConnectMsg`].
+    /// - Returns the user's stable account id on success.
+    /// - Returns an error reason if the connection should be rejected.
+    ///
+    /// [`HostUserConnectMsg`]: bevy_girk_backend_public::HostUserConnectMsg
+    fn authenticate(&self, user_id: u128, auth: &[u8]) -> Result<u128, String>;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Resource, Debug)]
+pub(crate) struct HostUserAuthenticator(pub(crate) Box<dyn UserAuthenticator>);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Auth payload for [`HmacUserAuthenticator`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HmacAuthToken
+{
+    /// The user's account id.
+    pub account_id: u128,
+    /// Expiration time of the token in seconds since the unix epoch.
+    pub expires_at: u64,
+    /// HMAC-SHA256 over the client id, account id, and expiration time.
+    pub mac: [u8; 32],
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`UserAuthenticator`] that validates [`HmacAuthToken`]s signed with a shared secret.
+///
+/// Tokens are bound to the client id they were issued for, so a token can't be reused by other clients.
+pub struct HmacUserAuthenticator
+{
+    secret: Vec<u8>,
+}
+
+impl HmacUserAuthenticator
+{
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self
+    {
+        Self{ secret: secret.into() }
+    }
+
+    /// Make a serialized auth token for a client.
+    /// - This should be used by the service that issues tokens to users.
+    pub fn make_token(&self, user_id: u128, account_id: u128, expires_at: SystemTime) -> Vec<u8>
+    {
+        let expires_at = expires_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or(Duration::default())
+            .as_secs();
+        let mac = hmac_sha256::HMAC::mac(Self::mac_input(user_id, account_id, expires_at), &self.secret);
+
+        ser_msg(&HmacAuthToken{ account_id, expires_at, mac })
+    }
+
+    fn mac_input(user_id: u128, account_id: u128, expires_at: u64) -> Vec<u8>
+    {
+        let mut input = Vec::with_capacity(40);
+        input.extend_from_slice(&user_id.to_le_bytes());
+        input.extend_from_slice(&account_id.to_le_bytes());
+        input.extend_from_slice(&expires_at.to_le_bytes());
+        input
+    }
+}
+
+impl Debug for HmacUserAuthenticator
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("HmacUserAuthenticator").finish_non_exhaustive()
+    }
+}
+
+impl UserAuthenticator for HmacUserAuthenticator
+{
+    fn authenticate(&self, user_id: u128, auth: &[u8]) -> Result<u128, String>
+    {
+        let Some(token) = deser_msg::<HmacAuthToken>(auth)
+        else { return Err(String::from("malformed auth token")); };
+
+        let input = Self::mac_input(user_id, token.account_id, token.expires_at);
+        if !hmac_sha256::HMAC::verify(input, &self.secret, &token.mac)
+        { return Err(String::from("invalid auth token signature")); }
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or(Duration::default())
+            .as_secs();
+        if token.expires_at <= now
+        { return Err(String::from("expired auth token")); }
+
+        Ok(token.account_id)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,243 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Config for capturing child process output (see [`ChildProcessLog`]).
+#[derive(Debug, Clone)]
+pub struct ChildProcessLogConfig
+{
+    /// Directory to write log files to (optional: output is only kept in memory and forwarded to `tracing` if `None`).
+    /// - The directory is created if it doesn't exist.
+    pub log_dir: Option<PathBuf>,
+    /// A log file is rotated when it reaches this size in bytes.
+    pub max_file_size: u64,
+    /// Max number of rotated files to keep for each process, in addition to the active file.
+    pub max_rotated_files: usize,
+    /// Number of recent output lines to keep in memory.
+    pub tail_lines: usize,
+    /// Forward output lines to `tracing` if they aren't written to a log file.
+    pub forward_to_tracing: bool,
+}
+
+impl Default for ChildProcessLogConfig
+{
+    fn default() -> Self
+    {
+        Self{
+            log_dir            : None,
+            max_file_size      : 10 * 1024 * 1024,
+            max_rotated_files  : 2,
+            tail_lines         : 50,
+            forward_to_tracing : true,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// `{path}.{index}`
+fn rotated_path(path: &Path, index: usize) -> PathBuf
+{
+    let mut rotated = path.to_path_buf().into_os_string();
+    rotated.push(format!(".{index}"));
+    rotated.into()
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct ChildProcessLogInner
+{
+    config: ChildProcessLogConfig,
+    /// name of the process
+    name: String,
+    /// path of the active log file
+    path: Option<PathBuf>,
+    /// active log file
+    file: Option<File>,
+    /// size of the active log file
+    file_size: u64,
+    /// recent lines
+    tail: VecDeque<String>,
+}
+
+impl ChildProcessLogInner
+{
+    fn open(&mut self)
+    {
+        let Some(path) = &self.path else { return; };
+        match File::create(path)
+        {
+            Ok(file) => { self.file = Some(file); self.file_size = 0; }
+            Err(err) => tracing::warn!(?path, ?err, "failed opening child process log file"),
+        }
+    }
+
+    /// Shifts `name.log.N-1` -> `name.log.N`, ..., `name.log` -> `name.log.1`, then opens a new `name.log`.
+    fn rotate(&mut self)
+    {
+        let Some(path) = self.path.clone() else { return; };
+        self.file = None;
+
+        if self.config.max_rotated_files == 0
+        {
+            let _ = std::fs::remove_file(&path);
+        }
+        else
+        {
+            let _ = std::fs::remove_file(rotated_path(&path, self.config.max_rotated_files));
+            for index in (1..self.config.max_rotated_files).rev()
+            {
+                let _ = std::fs::rename(rotated_path(&path, index), rotated_path(&path, index + 1));
+            }
+            let _ = std::fs::rename(&path, rotated_path(&path, 1));
+        }
+
+        self.open();
+    }
+
+    /// Moves `name.log` and its rotated files to `new_name.log`, then reopens the active file for appending.
+    fn rename(&mut self, new_name: &str)
+    {
+        self.name = String::from(new_name);
+        let Some(path) = self.path.clone() else { return; };
+        let new_path = path.with_file_name(format!("{new_name}.log"));
+        self.file = None;
+
+        for index in 1..=self.config.max_rotated_files
+        {
+            let _ = std::fs::rename(rotated_path(&path, index), rotated_path(&new_path, index));
+        }
+        if let Err(err) = std::fs::rename(&path, &new_path)
+        { tracing::warn!(?path, ?new_path, ?err, "failed renaming child process log file"); }
+        self.path = Some(new_path);
+
+        let Some(path) = &self.path else { return; };
+        match OpenOptions::new().create(true).append(true).open(path)
+        {
+            Ok(file) =>
+            {
+                self.file_size = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+                self.file = Some(file);
+            }
+            Err(err) => tracing::warn!(?path, ?err, "failed opening child process log file"),
+        }
+    }
+
+    fn write_line(&mut self, line: &str)
+    {
+        // save to tail
+        if self.config.tail_lines > 0
+        {
+            if self.tail.len() >= self.config.tail_lines { self.tail.pop_front(); }
+            self.tail.push_back(String::from(line));
+        }
+
+        // forward to tracing if there is no log file
+        if self.path.is_none() && self.config.forward_to_tracing
+        {
+            tracing::info!(process = %self.name, "{line}");
+        }
+
+        // write to file
+        let line_size = line.len() as u64 + 1;
+        if self.file.is_some() && self.file_size > 0 && self.file_size + line_size > self.config.max_file_size
+        {
+            self.rotate();
+        }
+        let Some(file) = &mut self.file else { return; };
+        if let Err(err) = writeln!(file, "{line}")
+        {
+            tracing::warn!(?err, "failed writing to child process log file");
+            self.file = None;
+            return;
+        }
+        self.file_size += line_size;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Captures the output of a child process.
+///
+/// Lines are written to a rotating log file at `{log dir}/{name}.log` (if a log directory is configured, otherwise they
+/// are forwarded to `tracing` unless [`ChildProcessLogConfig::forward_to_tracing`] is disabled), and the most recent
+/// lines are kept in memory.
+///
+/// Clones share the same log.
+#[derive(Debug, Clone)]
+pub struct ChildProcessLog
+{
+    inner: Arc<Mutex<ChildProcessLogInner>>,
+}
+
+impl ChildProcessLog
+{
+    /// Make a new log.
+    /// - Overwrites the existing log file for `name` if there is one.
+    pub fn new(config: ChildProcessLogConfig, name: &str) -> Self
+    {
+        let path = config.log_dir.as_ref().and_then(
+            |dir|
+            {
+                if let Err(err) = std::fs::create_dir_all(dir)
+                {
+                    tracing::warn!(?dir, ?err, "failed creating child process log directory");
+                    return None;
+                }
+                Some(dir.join(format!("{name}.log")))
+            }
+        );
+
+        let mut inner = ChildProcessLogInner{
+            config,
+            name      : String::from(name),
+            path,
+            file      : None,
+            file_size : 0,
+            tail      : VecDeque::default(),
+        };
+        inner.open();
+
+        Self{ inner: Arc::new(Mutex::new(inner)) }
+    }
+
+    /// Get the path of the active log file.
+    /// - Returns `None` if output is only kept in memory.
+    pub fn path(&self) -> Option<PathBuf>
+    {
+        self.inner.lock().unwrap().path.clone()
+    }
+
+    /// Write a line of output.
+    /// - Trailing newlines are removed.
+    pub fn write_line(&self, line: &str)
+    {
+        self.inner.lock().unwrap().write_line(line.trim_end_matches(['\n', '\r']));
+    }
+
+    /// Rename the log.
+    /// - Moves the log file and its rotated files to `{log dir}/{new name}.log` (overwriting existing files) and keeps
+    ///   writing to the moved file.
+    pub fn rename(&self, new_name: &str)
+    {
+        self.inner.lock().unwrap().rename(new_name);
+    }
+
+    /// Get the most recent lines of output.
+    pub fn tail(&self) -> Vec<String>
+    {
+        self.inner.lock().unwrap().tail.iter().cloned().collect()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,143 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use serde::{Serialize, de::DeserializeOwned};
+
+//standard shortcuts
+use std::io::{BufRead, Write};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Marks the start of a binary frame.
+/// - Lets readers skip over stray output (e.g. `println!()` in a child process) between frames.
+pub(crate) const FRAME_MAGIC: [u8; 4] = *b"GIRK";
+
+/// Max size of a binary frame's payload.
+pub(crate) const MAX_FRAME_SIZE: u32 = 64 * 1024 * 1024;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wire format for messages passed over a child process's `stdin`/`stdout`.
+///
+/// See [`manage_child_process()`] and [`run_app_in_child_process()`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum ChildProcessProtocol
+{
+    /// Length-prefixed bincode frames: `GIRK` + payload length (`u32`, little-endian) + payload.
+    #[default]
+    Bincode,
+    /// Newline-delimited JSON. Useful for debugging.
+    Json,
+}
+
+impl ChildProcessProtocol
+{
+    /// Get the protocol's name.
+    /// - Compatible with [`Self::parse`].
+    pub fn as_str(&self) -> &'static str
+    {
+        match self
+        {
+            Self::Bincode => "bincode",
+            Self::Json    => "json",
+        }
+    }
+
+    /// Parse a protocol from its name.
+    ///
+    /// Compatible with [`clap::builder::ValueParser`](https://docs.rs/clap/latest/clap/builder/struct.ValueParser.html).
+    pub fn parse(arg: &str) -> Result<Self, String>
+    {
+        match arg
+        {
+            "bincode" => Ok(Self::Bincode),
+            "json"    => Ok(Self::Json),
+            _         => Err(format!("unknown child process protocol {arg:?}, expected \"bincode\" or \"json\"")),
+        }
+    }
+
+    /// Encode a message.
+    pub fn encode<T: Serialize>(&self, message: &T) -> Result<Vec<u8>, String>
+    {
+        match self
+        {
+            Self::Bincode =>
+            {
+                let payload = ser_msg(message);
+                let Ok(len) = u32::try_from(payload.len()) else { return Err(String::from("message too large")); };
+                if len > MAX_FRAME_SIZE { return Err(String::from("message too large")); }
+
+                let mut frame = Vec::with_capacity(FRAME_MAGIC.len() + 4 + payload.len());
+                frame.extend_from_slice(&FRAME_MAGIC);
+                frame.extend_from_slice(&len.to_le_bytes());
+                frame.extend_from_slice(&payload);
+                Ok(frame)
+            }
+            Self::Json =>
+            {
+                let mut line = serde_json::to_vec(message).map_err(|err| err.to_string())?;
+                line.push(b'\n');
+                Ok(line)
+            }
+        }
+    }
+
+    /// Decode a message from a bincode frame payload or a JSON line.
+    pub(crate) fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Option<T>
+    {
+        match self
+        {
+            Self::Bincode => deser_msg(bytes),
+            Self::Json    => serde_json::from_slice(bytes).ok(),
+        }
+    }
+
+    /// Encode a message and write it to `writer`.
+    pub fn write<T: Serialize>(&self, writer: &mut impl Write, message: &T) -> std::io::Result<()>
+    {
+        let bytes = self.encode(message).map_err(std::io::Error::other)?;
+        writer.write_all(&bytes)?;
+        writer.flush()
+    }
+
+    /// Read the next message from `reader`.
+    /// - Returns `Ok(None)` if the reader is closed.
+    /// - Returns an error if the data can't be decoded.
+    pub fn read<T: DeserializeOwned>(&self, reader: &mut impl BufRead) -> std::io::Result<Option<T>>
+    {
+        let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg);
+
+        match self
+        {
+            Self::Bincode =>
+            {
+                let mut magic = [0u8; 4];
+                match reader.read_exact(&mut magic)
+                {
+                    Ok(()) => (),
+                    Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+                    Err(err) => return Err(err),
+                }
+                if magic != FRAME_MAGIC { return Err(invalid("invalid frame marker")); }
+
+                let mut len = [0u8; 4];
+                reader.read_exact(&mut len)?;
+                let len = u32::from_le_bytes(len);
+                if len > MAX_FRAME_SIZE { return Err(invalid("frame too large")); }
+
+                let mut payload = vec![0u8; len as usize];
+                reader.read_exact(&mut payload)?;
+                self.decode(&payload).map(Some).ok_or_else(|| invalid("failed decoding frame"))
+            }
+            Self::Json =>
+            {
+                let mut line = String::default();
+                if reader.read_line(&mut line)? == 0 { return Ok(None); }
+                self.decode(line.as_bytes()).map(Some).ok_or_else(|| invalid("failed decoding line"))
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
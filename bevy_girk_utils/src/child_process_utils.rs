@@ -4,45 +4,130 @@ use crate::*;
 //third-party shortcuts
 use bevy::prelude::*;
 use enfync::Handle;
-use serde::{Serialize, Deserialize};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
 
 //standard shortcuts
 use std::fmt::Debug;
-use std::io::{BufRead, BufReader, Write};
+use std::io::BufReader;
+use std::time::Duration;
 
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Forward process outputs from the app to `stdout`.
-fn drain_outputs<O: Serialize>(output_receiver: &mut IoReceiver<O>)
+fn drain_outputs<O: Serialize>(protocol: ChildProcessProtocol, output_receiver: &mut IoReceiver<O>)
 {
+    let mut stdout = std::io::stdout().lock();
     while let Some(output) = output_receiver.try_recv()
     {
-        let output_ser = serde_json::to_string(&output).expect("failed serializing process output");
-        let _ = std::io::stdout().write(output_ser.as_bytes());
-        let _ = std::io::stdout().write("\n".as_bytes());
+        if let Err(err) = protocol.write(&mut stdout, &output)
+        { tracing::error!(?err, "failed writing process output"); }
     }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
-fn monitor_for_outputs<O: Serialize + Send + Sync + 'static>(mut output_receiver: ResMut<IoReceiver<O>>)
+/// Read the next output from a child process's `stdout`.
+/// - Returns `None` when `stdout` closes.
+/// - Text that isn't part of an output (e.g. from `println!()` in the child) is written to `log`.
+async fn read_output<O: DeserializeOwned>(
+    protocol : ChildProcessProtocol,
+    id       : u64,
+    reader   : &mut tokio::io::BufReader<tokio::process::ChildStdout>,
+    log      : &ChildProcessLog,
+) -> Option<O>
 {
-    drain_outputs(&mut output_receiver);
+    match protocol
+    {
+        ChildProcessProtocol::Json =>
+        {
+            let mut buf = String::default();
+            loop
+            {
+                buf.clear();
+                match reader.read_line(&mut buf).await
+                {
+                    Ok(0) | Err(_) => return None,
+                    Ok(_) => (),
+                }
+
+                if let Some(output) = protocol.decode(buf.as_bytes()) { return Some(output); }
+                tracing::trace!(id, ?buf, "captured non-output line from process stdout");
+                log.write_line(&buf);
+            }
+        }
+        ChildProcessProtocol::Bincode =>
+        {
+            let mut text = Vec::<u8>::default();
+            let mut matched = 0;
+            loop
+            {
+                // scan for the next frame
+                let Ok(byte) = reader.read_u8().await
+                else
+                {
+                    if !text.is_empty() { log.write_line(&String::from_utf8_lossy(&text)); }
+                    return None;
+                };
+
+                if byte == FRAME_MAGIC[matched]
+                {
+                    matched += 1;
+                    if matched < FRAME_MAGIC.len() { continue; }
+                }
+                else
+                {
+                    // collect text outside frames
+                    text.extend_from_slice(&FRAME_MAGIC[..matched]);
+                    matched = 0;
+                    if byte == FRAME_MAGIC[0] { matched = 1; continue; }
+                    if byte != b'\n' { text.push(byte); continue; }
+
+                    tracing::trace!(id, "captured non-output line from process stdout");
+                    log.write_line(&String::from_utf8_lossy(&text));
+                    text.clear();
+                    continue;
+                }
+
+                // read the frame
+                matched = 0;
+                if !text.is_empty()
+                {
+                    log.write_line(&String::from_utf8_lossy(&text));
+                    text.clear();
+                }
+
+                let Ok(len) = reader.read_u32_le().await else { return None; };
+                if len > MAX_FRAME_SIZE
+                { tracing::warn!(id, len, "process output frame too large, skipping"); continue; }
+
+                let mut payload = vec![0u8; len as usize];
+                if reader.read_exact(&mut payload).await.is_err() { return None; }
+
+                if let Some(output) = protocol.decode(&payload) { return Some(output); }
+                tracing::warn!(id, "failed deserializing process output, skipping");
+            }
+        }
+    }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Manage a child process.
-/// - Spawns an enfync task for managing the child process. Items received from `stdin_receiver` will be serialized
-///   to JSON and forwarded to the child's `stdin`.
-/// - Spawns an enfync task for monitoring the child process's `stdout`. Lines received from the child's `stdout`
-///   will be deserialized from JSON and passed to the `stdout_handler` callback. If that callback returns `Some`
+/// - Spawns an enfync task for managing the child process. Items received from `stdin_receiver` will be encoded
+///   with `protocol` and forwarded to the child's `stdin`.
+/// - Spawns an enfync task for monitoring the child process's `stdout`. Outputs received from the child's `stdout`
+///   will be decoded with `protocol` and passed to the `stdout_handler` callback. If that callback returns `Some`
 ///   (e.g. on receipt of a 'process aborted' message), then the contained result will be returned from the task. The
-///   possible results are true/false to indicate if the task closed 'normally'.
+///   possible results are true/false to indicate if the task closed 'normally'. Text that isn't part of an output is
+///   written to `log`.
+/// - If the child's `stderr` is piped, spawns an enfync task that writes the child's `stderr` to `log`.
+/// - The child process is killed when a signal is received from `kill_receiver`.
+/// - If `stdout` closes before the `stdout_handler` returns a result, `on_stdout_error` is invoked after the child's
+///   remaining `stderr` has been written to `log` (so `on_stdout_error` can inspect [`ChildProcessLog::tail`]).
 ///
 /// Returns handles to the two tasks.
 ///
@@ -50,23 +135,50 @@ fn monitor_for_outputs<O: Serialize + Send + Sync + 'static>(mut output_receiver
 ///
 /// The `child_process` must be created inside a tokio task, so this function will likely be called inside
 /// a tokio task.
+#[allow(clippy::too_many_arguments)]
 pub fn manage_child_process<I, O>(
     spawner             : enfync::builtin::native::TokioHandle,
     id                  : u64,
+    protocol            : ChildProcessProtocol,
     mut child_process   : tokio::process::Child,
+    log                 : ChildProcessLog,
     mut stdin_receiver  : IoReceiver<I>,
+    mut kill_receiver   : IoReceiver<()>,
     mut stdout_handler  : impl FnMut(O) -> Option<bool> + Send + Sync + 'static,
     mut on_stdout_error : impl FnMut() + Send + Sync + 'static,
 ) -> (enfync::PendingResult<bool>, enfync::PendingResult<bool>)
 where
     I: Debug + Serialize + Send + Sync + 'static,
-    O: for<'de> Deserialize<'de> + Send + Sync + 'static
+    O: DeserializeOwned + Send + Sync + 'static
 {
     // extract child process io
     let child_stdin = child_process.stdin.take().unwrap();
     let child_stdout = child_process.stdout.take().unwrap();
     let mut child_stdin_writer = tokio::io::BufWriter::new(child_stdin);
     let mut child_stdout_reader = tokio::io::BufReader::new(child_stdout);
+    let child_stderr = child_process.stderr.take();
+
+    // capture stderr
+    let stderr_log = log.clone();
+    let mut stderr_handle = spawner.spawn(
+        async move
+        {
+            let Some(child_stderr) = child_stderr else { return; };
+            let mut reader = tokio::io::BufReader::new(child_stderr);
+            let mut line = Vec::<u8>::default();
+            loop
+            {
+                // - non-UTF-8 output is written lossily so it can't stop the capture
+                line.clear();
+                match reader.read_until(b'\n', &mut line).await
+                {
+                    Ok(0) => break,
+                    Ok(_) => stderr_log.write_line(&String::from_utf8_lossy(&line)),
+                    Err(err) => { tracing::warn!(id, ?err, "failed reading process stderr"); break; }
+                }
+            }
+        }
+    );
 
     // manage process
     let process_handle = spawner.spawn(
@@ -79,20 +191,14 @@ where
                     // forward inputs to the process
                     Some(input) = stdin_receiver.recv() =>
                     {
-                        let Ok(input_ser) = serde_json::to_string(&input)
+                        let Ok(input_ser) = protocol.encode(&input)
                         else
                         {
                             tracing::warn!(id, "failed serializing input, aborting");
                             let _ = child_process.kill().await;
                             return false;
                         };
-                        if let Err(err) = child_stdin_writer.write(input_ser.as_bytes()).await
-                        {
-                            tracing::warn!(id, ?err, "failed sending input, aborting");
-                            let _ = child_process.kill().await;
-                            return false;
-                        }
-                        if let Err(err) = child_stdin_writer.write("\n".as_bytes()).await
+                        if let Err(err) = child_stdin_writer.write_all(&input_ser).await
                         {
                             tracing::warn!(id, ?err, "failed sending input, aborting");
                             let _ = child_process.kill().await;
@@ -107,6 +213,14 @@ where
                         tracing::trace!(id, ?input, "forwarded input to process");
                     }
 
+                    // kill the process on request
+                    Some(()) = kill_receiver.recv() =>
+                    {
+                        tracing::warn!(id, "received kill signal, killing process");
+                        let _ = child_process.kill().await;
+                        return false;
+                    }
+
                     // await process termination
                     _ = child_process.wait() =>
                     {
@@ -130,34 +244,20 @@ where
     let stdout_handle = spawner.spawn(
         async move
         {
-            let mut buf = String::default();
-
-            loop
+            while let Some(output) = read_output::<O>(protocol, id, &mut child_stdout_reader, &log).await
             {
-                buf.clear();
-                match child_stdout_reader.read_line(&mut buf).await
+                if let Some(result) = (stdout_handler)(output)
                 {
-                    Ok(_) =>
-                    {
-                        let Ok(output) = serde_json::de::from_str::<O>(&buf)
-                        else {
-                            tracing::warn!(id, ?buf, "failed deserializing process output");
-                            (on_stdout_error)();
-                            return false;
-                        };
-
-                        if let Some(result) = (stdout_handler)(output)
-                        {
-                            return result;
-                        }
-                    }
-                    Err(_) =>
-                    {
-                        (on_stdout_error)();
-                        return false;
-                    }
+                    return result;
                 }
             }
+
+            // wait for the rest of stderr
+            // - Use a timeout in case the process's stderr was inherited by another process.
+            tracing::warn!(id, "process stdout closed unexpectedly");
+            let _ = tokio::time::timeout(Duration::from_secs(1), stderr_handle.extract()).await;
+            (on_stdout_error)();
+            false
         }
     );
 
@@ -167,14 +267,16 @@ where
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Run an app in a child process.
-/// - Reads `I` messages from `stdin` (deserialized from JSON) and forwards them to the app via `stdin_sender`. If
-///   the `stdin` handle closes, then `on_stdin_null` will be invoked. This facilitates graceful
-///   handling of parent process closure, although graceful shutdown is not guaranteed on all machines.
-/// - Reads `O` messages from `stdout_receiver`, serializes them to JSON, and forwards them to the process's `stdout`.
+/// - Reads `I` messages from `stdin` (decoded with `protocol`) and forwards them to the app via `stdin_sender`. If
+///   the `stdin` handle closes or an input can't be decoded, then `on_input_failure` will be invoked. This facilitates
+///   graceful handling of parent process closure, although graceful shutdown is not guaranteed on all machines.
+/// - Reads `O` messages from `stdout_receiver`, encodes them with `protocol`, and forwards them to the process's
+///   `stdout`.
 ///
 /// This is designed for compatibility with [`manage_child_process()`].
 pub fn run_app_in_child_process<I, O>(
     id                  : u64,
+    protocol            : ChildProcessProtocol,
     mut app             : App,
     stdin_sender        : IoSender<I>,
     mut stdout_receiver : IoReceiver<O>,
@@ -182,7 +284,7 @@ pub fn run_app_in_child_process<I, O>(
     on_critical_err     : impl FnOnce() + Send + Sync + 'static,
 )
 where
-    I: Debug + for<'de> Deserialize<'de> + Send + Sync + 'static,
+    I: Debug + DeserializeOwned + Send + Sync + 'static,
     O: Clone + Serialize + Send + Sync + 'static
 {
     // spawn thread for monitoring inputs
@@ -190,22 +292,25 @@ where
         move ||
         {
             let mut stdin_reader = BufReader::new(std::io::stdin());
-            let mut line = String::new();
 
             loop
             {
-                // read the next stdin
-                line.clear();
-                let _ = stdin_reader.read_line(&mut line);
-
-                if line.is_empty()
+                // read the next input
+                let input = match protocol.read::<I>(&mut stdin_reader)
                 {
-                    (on_input_failure)();
-                    return;
-                }
-
-                // deserialize input
-                let input = serde_json::de::from_str::<I>(&line).expect("failed deserializing input");
+                    Ok(Some(input)) => input,
+                    Ok(None) =>
+                    {
+                        (on_input_failure)();
+                        return;
+                    }
+                    Err(err) =>
+                    {
+                        tracing::error!(id, ?err, "failed reading process input");
+                        (on_input_failure)();
+                        return;
+                    }
+                };
                 tracing::info!(id, ?input, "received process input");
 
                 // forward to app
@@ -216,7 +321,9 @@ where
 
     // add system for marshalling outputs to the parent process
     app.insert_resource(stdout_receiver.clone())
-        .add_systems(Last, monitor_for_outputs::<O>);
+        .add_systems(Last,
+            move |mut output_receiver: ResMut<IoReceiver<O>>| drain_outputs(protocol, &mut output_receiver)
+        );
 
     // run the app to completion
     if let Err(_) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || { app.run(); }))
@@ -225,7 +332,7 @@ where
     }
 
     // drain any lingering outputs
-    drain_outputs(&mut stdout_receiver);
+    drain_outputs(protocol, &mut stdout_receiver);
 }
 
 //-------------------------------------------------------------------------------------------------------------------
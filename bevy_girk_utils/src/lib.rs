@@ -14,7 +14,13 @@ mod tick_counter;
 #[cfg(all(feature = "process", not(target_family = "wasm")))]
 mod cli;
 #[cfg(all(feature = "process", not(target_family = "wasm")))]
+mod child_process_log;
+#[cfg(all(feature = "process", not(target_family = "wasm")))]
+mod child_process_protocol;
+#[cfg(all(feature = "process", not(target_family = "wasm")))]
 mod child_process_utils;
+#[cfg(not(target_family = "wasm"))]
+mod metrics;
 
 //API exports
 pub use channel::*;
@@ -30,4 +36,10 @@ pub use tick_counter::*;
 #[cfg(all(feature = "process", not(target_family = "wasm")))]
 pub use cli::*;
 #[cfg(all(feature = "process", not(target_family = "wasm")))]
+pub use child_process_log::*;
+#[cfg(all(feature = "process", not(target_family = "wasm")))]
+pub use child_process_protocol::*;
+#[cfg(all(feature = "process", not(target_family = "wasm")))]
 pub use child_process_utils::*;
+#[cfg(not(target_family = "wasm"))]
+pub use metrics::*;
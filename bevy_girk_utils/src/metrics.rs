@@ -0,0 +1,249 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Max number of connections served at once. Extra connections are closed without a response.
+const MAX_CONNECTIONS: usize = 4;
+
+/// Read and write timeout for metrics connections.
+const CONNECTION_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Max size of a request head. Connections with larger request heads are closed without a response.
+const MAX_REQUEST_HEAD_SIZE: usize = 8 * 1024;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reads a request head (everything up to the first empty line).
+/// - Returns `None` if the head is too large or the connection failed before any data was read.
+fn read_request_head(stream: &mut TcpStream) -> Option<String>
+{
+    let mut head = Vec::<u8>::default();
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n")
+    {
+        if head.len() > MAX_REQUEST_HEAD_SIZE { return None; }
+        match stream.read(&mut buffer)
+        {
+            Ok(0) => break,
+            Ok(num_read) => head.extend_from_slice(&buffer[..num_read]),
+            Err(_) if !head.is_empty() => break,
+            Err(_) => return None,
+        }
+    }
+
+    Some(String::from_utf8_lossy(&head).into_owned())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn serve_metrics(mut stream: TcpStream, text: &Mutex<String>)
+{
+    // read the request head
+    // - we only care about the request line, so the rest of the request is ignored
+    let _ = stream.set_read_timeout(Some(CONNECTION_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(CONNECTION_TIMEOUT));
+    let Some(request) = read_request_head(&mut stream) else { return; };
+    let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+
+    // build the response
+    let response = match (request_line.next(), request_line.next())
+    {
+        (Some("GET"), Some("/metrics")) =>
+        {
+            let body = text.lock().map(|text| text.clone()).unwrap_or_default();
+            format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\
+                    Connection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+        }
+        _ => String::from("HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"),
+    };
+
+    let _ = stream.write_all(response.as_bytes());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Config for exporting metrics.
+#[derive(Debug, Copy, Clone)]
+pub struct MetricsConfig
+{
+    /// Address of the local HTTP listener. Metrics are served at `/metrics` in Prometheus text format.
+    /// - Use port `0` to bind a random port (see [`MetricsExporter::local_addr`]).
+    pub listen_addr: SocketAddr,
+    /// Number of ticks to wait between each metrics sample.
+    pub sample_period_ticks: u64,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Builds a metrics exposition in Prometheus text format.
+#[derive(Debug, Default)]
+pub struct PrometheusWriter
+{
+    text: String,
+}
+
+impl PrometheusWriter
+{
+    /// Add a gauge.
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64)
+    {
+        self.gauge_family(name, help, [(String::default(), value)]);
+    }
+
+    /// Add a gauge with one sample per label set.
+    /// - Labels should be formatted like `key="value",key2="value2"`.
+    pub fn gauge_family(&mut self, name: &str, help: &str, samples: impl IntoIterator<Item = (String, f64)>)
+    {
+        self.header(name, help, "gauge");
+        for (labels, value) in samples
+        {
+            self.sample(name, &labels, value);
+        }
+    }
+
+    /// Add a counter.
+    pub fn counter(&mut self, name: &str, help: &str, value: u64)
+    {
+        self.header(name, help, "counter");
+        self.sample(name, "", value as f64);
+    }
+
+    /// Get the exposition text.
+    pub fn finish(self) -> String
+    {
+        self.text
+    }
+
+    fn header(&mut self, name: &str, help: &str, kind: &str)
+    {
+        let _ = writeln!(self.text, "# HELP {name} {help}");
+        let _ = writeln!(self.text, "# TYPE {name} {kind}");
+    }
+
+    fn sample(&mut self, name: &str, labels: &str, value: f64)
+    {
+        match labels.is_empty()
+        {
+            true  => { let _ = writeln!(self.text, "{name} {value}"); }
+            false => { let _ = writeln!(self.text, "{name}{{{labels}}} {value}"); }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Serves the most recently published metrics on a local HTTP listener.
+///
+/// The listener runs on its own thread, which shuts down when the exporter is dropped. Each connection is served on a
+/// separate thread so slow clients can't block other scrapes, up to a small number of concurrent connections.
+#[derive(Debug)]
+pub struct MetricsExporter
+{
+    local_addr: SocketAddr,
+    text: Arc<Mutex<String>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl MetricsExporter
+{
+    /// Make a new exporter listening on the given address.
+    pub fn new(listen_addr: SocketAddr) -> std::io::Result<Self>
+    {
+        let listener = TcpListener::bind(listen_addr)?;
+        let local_addr = listener.local_addr()?;
+
+        let text = Arc::new(Mutex::new(String::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_text = text.clone();
+        let thread_stop = stop.clone();
+
+        std::thread::spawn(
+            move ||
+            {
+                let num_connections = Arc::new(AtomicUsize::new(0));
+
+                for stream in listener.incoming()
+                {
+                    // the exporter wakes us up with a connection when it is dropped
+                    if thread_stop.load(Ordering::Relaxed) { break; }
+
+                    let stream = match stream
+                    {
+                        Ok(stream) => stream,
+                        Err(err) => { tracing::warn!(?err, "metrics listener failed accepting connection"); continue; }
+                    };
+
+                    // close the connection if too many connections are being served
+                    if num_connections.fetch_add(1, Ordering::AcqRel) >= MAX_CONNECTIONS
+                    {
+                        num_connections.fetch_sub(1, Ordering::AcqRel);
+                        tracing::debug!("metrics listener has too many connections, closing new connection");
+                        continue;
+                    }
+
+                    let connection_text = thread_text.clone();
+                    let connection_count = num_connections.clone();
+                    std::thread::spawn(
+                        move ||
+                        {
+                            serve_metrics(stream, &connection_text);
+                            connection_count.fetch_sub(1, Ordering::AcqRel);
+                        }
+                    );
+                }
+            }
+        );
+        tracing::info!(?local_addr, "serving metrics");
+
+        Ok(Self{ local_addr, text, stop })
+    }
+
+    /// Get the address the listener is bound to.
+    pub fn local_addr(&self) -> SocketAddr
+    {
+        self.local_addr
+    }
+
+    /// Publish new metrics text.
+    pub fn publish(&self, text: String)
+    {
+        let Ok(mut current) = self.text.lock() else { return; };
+        *current = text;
+    }
+}
+
+impl Drop for MetricsExporter
+{
+    fn drop(&mut self)
+    {
+        self.stop.store(true, Ordering::Relaxed);
+
+        // wake up the listener thread so it can shut down
+        let mut wake_addr = self.local_addr;
+        match wake_addr.ip()
+        {
+            IpAddr::V4(ip) if ip.is_unspecified() => wake_addr.set_ip(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            IpAddr::V6(ip) if ip.is_unspecified() => wake_addr.set_ip(IpAddr::V6(Ipv6Addr::LOCALHOST)),
+            _ => (),
+        }
+        if let Err(err) = TcpStream::connect_timeout(&wake_addr, CONNECTION_TIMEOUT)
+        { tracing::warn!(?err, "failed waking up metrics listener for shutdown"); }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
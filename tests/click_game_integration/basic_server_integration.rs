@@ -31,9 +31,13 @@ fn make_host_server_test_configs() -> HostServerStartupPack
     let host_server_config = HostServerConfig{
             ticks_per_sec                   : None,  //we will manually update the host server
             ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
         };
     let lobbies_cache_config = LobbiesCacheConfig{
             max_request_size: 10u16,
+            lobby_searcher: None,
+            owner_succession: OwnerSuccession::Disband,
             lobby_checker: Box::new(ClickLobbyChecker{
                     max_lobby_players     : 2u16,
                     max_lobby_watchers    : 0u16,
@@ -58,6 +62,16 @@ fn make_host_server_test_configs() -> HostServerStartupPack
             pending_lobbies_cache_config,
             ongoing_games_cache_config,
             game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
         }
 }
 
@@ -76,12 +90,16 @@ fn make_hub_server_test_configs() -> GameHubServerStartupPack
         };
     let running_games_cache_config = RunningGamesCacheConfig{
             expiry_duration: Duration::from_secs(20),
+            watchdog: None,
         };
 
     GameHubServerStartupPack{
             game_hub_server_config,
             pending_games_cache_config,
             running_games_cache_config,
+            metrics_config: None,
+            game_cost_estimator: None,
+            capacity_policy: None,
         }
 }
 
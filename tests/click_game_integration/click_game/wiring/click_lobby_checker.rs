@@ -121,34 +121,36 @@ impl LobbyChecker for ClickLobbyChecker
         member_id   : u128,
         member_data : LobbyMemberData,
         password    : &String,
-    ) -> bool
+    ) -> Result<(), HostRequestError>
     {
         // check if in lobby already
-        if lobby.has_member(member_id) { return false; }
+        if lobby.has_member(member_id) { return Err(HostRequestError::Rejected); }
 
         // check password
-        if lobby.get_password() != password { return false; }
+        if lobby.get_password() != password { return Err(HostRequestError::WrongPassword); }
 
         // get member type
-        let Ok(member_type) = ClickLobbyMemberType::try_from(member_data.color) else { return false; };
+        let Ok(member_type) = ClickLobbyMemberType::try_from(member_data.color)
+        else { return Err(HostRequestError::Rejected); };
 
         // count current players and watchers
-        let Ok((num_players, num_watchers)) = Self::count_members(&lobby.data) else { return false; };
+        let Ok((num_players, num_watchers)) = Self::count_members(&lobby.data)
+        else { return Err(HostRequestError::Rejected); };
 
         // check if the member's type has exceeded lobby capacity
         match member_type
         {
             ClickLobbyMemberType::Player =>
             {
-                if num_players >= self.max_lobby_players as usize { return false; }
+                if num_players >= self.max_lobby_players as usize { return Err(HostRequestError::LobbyFull); }
             }
             ClickLobbyMemberType::Watcher =>
             {
-                if num_watchers >= self.max_lobby_watchers as usize { return false; }
+                if num_watchers >= self.max_lobby_watchers as usize { return Err(HostRequestError::LobbyFull); }
             }
         }
 
-        true
+        Ok(())
     }
 
     /// Check if a lobby is launchable.
@@ -3,19 +3,48 @@ use crate::test_helpers::*;
 use bevy_girk_backend_public::*;
 use bevy_girk_game_hub_server::*;
 use bevy_girk_game_instance::*;
+use bevy_girk_utils::*;
 
 //third-party shortcuts
+use enfync::{AdoptOrDefault, Handle};
 
 //standard shortcuts
 use std::time::Duration;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Launches game instances that never send reports or terminate.
+/// - Instances signal `kill_sender` when killed. Instances can't be killed if there is no `kill_sender`.
+struct HangingGameLauncher
+{
+    kill_sender: Option<IoSender<()>>,
+}
+
+impl GameInstanceLauncherImpl for HangingGameLauncher
+{
+    fn launch(&self, launch_pack: GameLaunchPack, _report_sender: IoSender<GameInstanceReport>) -> GameInstance
+    {
+        let (command_sender, command_receiver) = new_io_channel::<GameInstanceCommand>();
+        let instance_handle = enfync::builtin::native::TokioHandle::adopt_or_default().spawn(
+            async { std::future::pending::<bool>().await }
+        );
+
+        let game_instance = GameInstance::new(launch_pack.game_id, command_sender, command_receiver, instance_handle);
+        match &self.kill_sender
+        {
+            Some(kill_sender) => game_instance.with_kill_signal(kill_sender.clone()),
+            None              => game_instance,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[test]
 fn cache_running_games_basic()
 {
     // make a cache
-    let cache_config = RunningGamesCacheConfig{ expiry_duration: Duration::from_secs(1) };
+    let cache_config = RunningGamesCacheConfig{ expiry_duration: Duration::from_secs(1), watchdog: None };
     let factory = GameFactory::new(DummyGameFactory{});
     let game_launcher = GameInstanceLauncher::new(GameInstanceLauncherLocal::new(factory));
     let mut cache = RunningGamesCache::new(cache_config, game_launcher);
@@ -80,7 +109,10 @@ fn cache_running_games_expiration()
 {
     // make a cache
     let one_third_duration = Duration::from_millis(15);
-    let cache_config = RunningGamesCacheConfig{ expiry_duration: one_third_duration + one_third_duration + one_third_duration };
+    let cache_config = RunningGamesCacheConfig{
+            expiry_duration : one_third_duration + one_third_duration + one_third_duration,
+            watchdog        : None,
+        };
     let factory = GameFactory::new(DummyGameFactory{});
     let game_launcher = GameInstanceLauncher::new(GameInstanceLauncherLocal::new(factory));
     let mut cache = RunningGamesCache::new(cache_config, game_launcher);
@@ -170,7 +202,10 @@ fn cache_running_games_termination()
 {
     // make a cache
     let one_third_duration = Duration::from_millis(20);
-    let cache_config = RunningGamesCacheConfig{ expiry_duration: Duration::from_secs(1) };  //long expiry duration
+    let cache_config = RunningGamesCacheConfig{
+            expiry_duration : Duration::from_secs(1),  //long expiry duration
+            watchdog        : None,
+        };
     let factory = GameFactory::new(DummyGameFactory{});
     let game_launcher = GameInstanceLauncher::new(GameInstanceLauncherLocal::new(factory));
     let mut cache = RunningGamesCache::new(cache_config, game_launcher);
@@ -254,3 +289,107 @@ fn cache_running_games_termination()
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn cache_running_games_watchdog()
+{
+    // make a cache
+    let watchdog = GameInstanceWatchdogConfig{
+            startup_timeout   : Duration::from_millis(40),
+            heartbeat_timeout : Duration::from_millis(40),
+        };
+    let cache_config = RunningGamesCacheConfig{ expiry_duration: Duration::from_secs(10), watchdog: Some(watchdog) };
+    let (kill_sender, mut kill_receiver) = new_io_channel::<()>();
+    let game_launcher = GameInstanceLauncher::new(HangingGameLauncher{ kill_sender: Some(kill_sender) });
+    let mut cache = RunningGamesCache::new(cache_config, game_launcher);
+
+    // add games
+    let make_game = |cache: &mut RunningGamesCache, game_id: u64|
+    {
+        let launch_pack = GameLaunchPack::new(game_id, ());
+        let start_request = GameStartRequest{ lobby_data: LobbyData{ id: game_id, ..Default::default() } };
+        cache.make_instance(start_request, launch_pack).expect("making game instance should succeed");
+    };
+    let game_id_1 = 1u64;
+    let game_id_2 = 2u64;
+    let game_id_3 = 3u64;
+    make_game(&mut cache, game_id_1);
+    make_game(&mut cache, game_id_2);
+    make_game(&mut cache, game_id_3);
+
+    // games 2 and 3 start
+    assert!(cache.record_game_start(game_id_2));
+    assert!(cache.record_game_start(game_id_3));
+    assert!(cache.drain_unresponsive().into_iter().next().is_none());
+
+    // wait past the timeouts while game 3 sends heartbeats
+    for _ in 0..3
+    {
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(cache.record_heartbeat(game_id_3));
+    }
+
+    // remove unresponsive (should remove games 1 and 2)
+    let mut drained: Vec<(u64, UnresponsiveGameReason)> = cache.drain_unresponsive()
+        .into_iter()
+        .map(
+            |(instance, reason)|
+            {
+                assert!(instance.kill());
+                (instance.id(), reason)
+            }
+        )
+        .collect();
+    drained.sort_by_key(|(game_id, _)| *game_id);
+    assert_eq!(drained, vec![
+            (game_id_1, UnresponsiveGameReason::NoGameStart),
+            (game_id_2, UnresponsiveGameReason::MissedHeartbeats),
+        ]);
+
+    // - the unresponsive games were killed
+    assert_eq!(kill_receiver.try_recv(), Some(()));
+    assert_eq!(kill_receiver.try_recv(), Some(()));
+    assert_eq!(kill_receiver.try_recv(), None);
+
+    assert_eq!(cache.num_running(), 1);
+    assert!(cache.has_game(game_id_3));
+    assert!(!cache.record_heartbeat(game_id_1));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn cache_running_games_watchdog_unkillable()
+{
+    // make a cache with game instances that can't be killed
+    let watchdog = GameInstanceWatchdogConfig{
+            startup_timeout   : Duration::from_millis(20),
+            heartbeat_timeout : Duration::from_millis(20),
+        };
+    let cache_config = RunningGamesCacheConfig{ expiry_duration: Duration::from_millis(60), watchdog: Some(watchdog) };
+    let game_launcher = GameInstanceLauncher::new(HangingGameLauncher{ kill_sender: None });
+    let mut cache = RunningGamesCache::new(cache_config, game_launcher);
+
+    // add a game that never starts
+    let game_id = 1u64;
+    let launch_pack = GameLaunchPack::new(game_id, ());
+    let start_request = GameStartRequest{ lobby_data: LobbyData{ id: game_id, ..Default::default() } };
+    cache.make_instance(start_request, launch_pack).expect("making game instance should succeed");
+
+    // wait past the startup timeout
+    std::thread::sleep(Duration::from_millis(30));
+
+    // remove unresponsive (the game can't be killed so it should stay tracked)
+    assert!(cache.drain_unresponsive().into_iter().next().is_none());
+    assert!(cache.has_game(game_id));
+
+    // wait for expiration
+    std::thread::sleep(Duration::from_millis(40));
+
+    // remove invalid (should remove the game)
+    let drained: Vec<u64> = cache.drain_invalid().into_iter().map(|instance| instance.id()).collect();
+    assert_eq!(drained, vec![game_id]);
+    assert_eq!(cache.num_running(), 0);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,161 @@
+//local shortcuts
+use bevy_girk_game_hub_server::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const GB: u64 = 1024 * 1024 * 1024;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sampler that returns whatever load was last set by the test.
+#[derive(Debug, Clone, Default)]
+struct TestLoadSampler(Arc<Mutex<SystemLoad>>);
+
+impl TestLoadSampler
+{
+    fn set(&self, cpu_load: f32, available_memory: u64, children_memory: u64, num_children: usize)
+    {
+        *self.0.lock().unwrap() = SystemLoad{ cpu_load, available_memory, children_memory, num_children };
+    }
+}
+
+impl SystemLoadSampler for TestLoadSampler
+{
+    fn sample(&mut self) -> Option<SystemLoad>
+    {
+        Some(*self.0.lock().unwrap())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_policy() -> (TestLoadSampler, LoadAwareCapacityPolicy)
+{
+    let sampler = TestLoadSampler::default();
+    let config = LoadAwareCapacityConfig{
+            sample_period           : Duration::from_secs(0),  //sample every update
+            max_cpu_load            : 0.9,
+            resume_cpu_load         : 0.7,
+            min_available_memory    : GB,
+            resume_available_memory : 2 * GB,
+        };
+
+    (sampler.clone(), LoadAwareCapacityPolicy::new(config, Box::new(sampler)))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn capacity_policy_cpu_hysteresis()
+{
+    let (sampler, mut policy) = make_policy();
+    let max = GameHubCapacity(10);
+
+    // idle machine: limited by max capacity
+    sampler.set(0.1, 16 * GB, 0, 0);
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(10));
+
+    // saturated: no new games
+    sampler.set(0.95, 16 * GB, 0, 0);
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(2));
+    assert!(policy.is_saturated());
+
+    // - still saturated between the thresholds
+    sampler.set(0.8, 16 * GB, 0, 0);
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(2));
+
+    // - finished games don't free capacity
+    assert_eq!(policy.capacity_limit(max, 1), GameHubCapacity(1));
+
+    // recovered below the resume threshold
+    sampler.set(0.6, 16 * GB, 0, 0);
+    assert_eq!(policy.capacity_limit(max, 1), GameHubCapacity(10));
+    assert!(!policy.is_saturated());
+
+    // - still not saturated between the thresholds
+    sampler.set(0.8, 16 * GB, 0, 0);
+    assert_eq!(policy.capacity_limit(max, 1), GameHubCapacity(10));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn capacity_policy_memory_limits()
+{
+    let (sampler, mut policy) = make_policy();
+    let max = GameHubCapacity(10);
+
+    // two games using 1GB each, 5GB available: room for 3 more games before reaching the resume threshold
+    sampler.set(0.1, 5 * GB, 2 * GB, 2);
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(5));
+
+    // low memory: saturated
+    sampler.set(0.1, GB / 2, 2 * GB, 2);
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(2));
+
+    // - still saturated between the thresholds
+    sampler.set(0.1, GB + GB / 2, 2 * GB, 2);
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(2));
+
+    // recovered: limited by memory headroom
+    // - the limit rises by one game per sample
+    sampler.set(0.1, 4 * GB, 2 * GB, 2);
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(3));
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(4));
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(4));
+
+    // lots of memory: limited by max capacity
+    sampler.set(0.1, 64 * GB, 2 * GB, 2);
+    for _ in 0..6 { policy.capacity_limit(max, 2); }
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(10));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn capacity_policy_memory_limit_smoothing()
+{
+    let (sampler, mut policy) = make_policy();
+    let max = GameHubCapacity(20);
+
+    // two games using 1GB each, 6GB available: room for 4 more games
+    sampler.set(0.1, 6 * GB, 2 * GB, 2);
+    assert_eq!(policy.capacity_limit(max, 2), GameHubCapacity(6));
+
+    // a new game is still warming up, so the memory estimate per game drops
+    // - the limit only rises by one game
+    sampler.set(0.1, 6 * GB, 2 * GB, 3);
+    assert_eq!(policy.capacity_limit(max, 3), GameHubCapacity(7));
+
+    // the new game's memory use grows: the limit drops immediately
+    sampler.set(0.1, 5 * GB, 3 * GB, 3);
+    assert_eq!(policy.capacity_limit(max, 3), GameHubCapacity(6));
+
+    // - and recovers one game per sample
+    sampler.set(0.1, 8 * GB, 3 * GB, 3);
+    assert_eq!(policy.capacity_limit(max, 3), GameHubCapacity(7));
+    assert_eq!(policy.capacity_limit(max, 3), GameHubCapacity(8));
+    assert_eq!(policy.capacity_limit(max, 3), GameHubCapacity(9));
+    assert_eq!(policy.capacity_limit(max, 3), GameHubCapacity(9));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(target_os = "linux")]
+#[test]
+fn proc_load_sampler()
+{
+    let load = ProcLoadSampler.sample().expect("sampling /proc should succeed");
+    assert!(load.cpu_load >= 0.0);
+    assert!(load.available_memory > 0);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,162 @@
+//local shortcuts
+use crate::game_hub_server::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_game_hub_server::*;
+use bevy_girk_host_server::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> GameHubServerStartupPack
+{
+    let game_hub_server_config = GameHubServerConfig{
+            ticks_per_sec                   : None,
+            initial_max_capacity            : 10u16,
+            running_game_purge_period_ticks : 100u64,
+        };
+    let pending_games_cache_config = PendingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(20),
+        };
+    let running_games_cache_config = RunningGamesCacheConfig{
+            expiry_duration: Duration::from_secs(20),
+            watchdog: None,
+        };
+
+    GameHubServerStartupPack{
+            game_hub_server_config,
+            pending_games_cache_config,
+            running_games_cache_config,
+            metrics_config: None,
+            game_cost_estimator: None,
+            capacity_policy: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn get_stats(hub_command_sender: &Sender<GameHubCommand>, hub_server_app: &mut App) -> GameHubStats
+{
+    let (stats_sender, stats_receiver) = new_channel::<GameHubStats>();
+    hub_command_sender.send(GameHubCommand::GetStats(stats_sender)).unwrap();
+    hub_server_app.update();
+
+    stats_receiver.try_recv().expect("game hub should respond with stats")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn game_hub_stats_pending_game()
+{
+    // make a websocket host server
+    let mut host_hub_server = make_test_host_hub_server();
+
+    // make a game hub server whose launch pack source never responds
+    let (hub_command_sender, mut hub_server_app) = make_test_game_hub_server(
+            host_hub_server.url(),
+            false,
+            make_configs(),
+            100,
+            10,
+            None
+        );
+    hub_server_app.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - hub connects to server
+    let Some((connected_hub_id, HostHubServerEvent::Report(HostHubServerReport::Connected(_, _)))) = host_hub_server.next()
+    else { panic!("host hub server did not receive game hub server connection report"); };
+
+
+    // update so the hub handles its connection report
+    hub_server_app.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // initial stats
+    let stats = get_stats(&hub_command_sender, &mut hub_server_app);
+    assert_eq!(stats.max_capacity, GameHubCapacity(10));
+    assert_eq!(stats.capacity, GameHubCapacity(10));
+    assert!(stats.pending_games.is_empty());
+    assert!(stats.running_games.is_empty());
+    assert_eq!((stats.launch_failures, stats.aborts, stats.game_overs), (0, 0, 0));
+    assert!(stats.host_connected);
+
+
+    // request game start
+    let game_id = 1u64;
+    let start_request = GameStartRequest{ lobby_data: LobbyData{ id: game_id, ..Default::default() } };
+    host_hub_server.send(connected_hub_id, HostToHubMsg::StartGame(start_request));
+    std::thread::sleep(Duration::from_millis(15));
+    hub_server_app.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the game is pending
+    let stats = get_stats(&hub_command_sender, &mut hub_server_app);
+    assert_eq!(stats.capacity, GameHubCapacity(9));
+    assert_eq!(stats.pending_games.len(), 1);
+    assert_eq!(stats.pending_games[0].id, game_id);
+    assert!(stats.pending_games[0].age > Duration::from_millis(0));
+    assert!(stats.running_games.is_empty());
+
+
+    // shut down the host server
+    drop(host_hub_server);
+    std::thread::sleep(Duration::from_millis(15));
+
+    // update so the hub handles its disconnection report
+    hub_server_app.update();
+
+    // - the hub is disconnected
+    let stats = get_stats(&hub_command_sender, &mut hub_server_app);
+    assert!(!stats.host_connected);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn game_hub_stats_launch_failure()
+{
+    // make a websocket host server
+    let mut host_hub_server = make_test_host_hub_server();
+
+    // make a game hub server whose launch pack source fails
+    let (hub_command_sender, mut hub_server_app) = make_test_game_hub_server(
+            host_hub_server.url(),
+            false,
+            make_configs(),
+            100,
+            10,
+            Some(false)
+        );
+    hub_server_app.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - hub connects to server
+    let Some((connected_hub_id, HostHubServerEvent::Report(HostHubServerReport::Connected(_, _)))) = host_hub_server.next()
+    else { panic!("host hub server did not receive game hub server connection report"); };
+
+
+    // request game start
+    let start_request = GameStartRequest{ lobby_data: LobbyData{ id: 1u64, ..Default::default() } };
+    host_hub_server.send(connected_hub_id, HostToHubMsg::StartGame(start_request));
+    std::thread::sleep(Duration::from_millis(15));
+    hub_server_app.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the launch failed
+    let stats = get_stats(&hub_command_sender, &mut hub_server_app);
+    assert_eq!(stats.capacity, GameHubCapacity(10));
+    assert!(stats.pending_games.is_empty());
+    assert!(stats.running_games.is_empty());
+    assert_eq!((stats.launch_failures, stats.aborts, stats.game_overs), (1, 0, 0));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
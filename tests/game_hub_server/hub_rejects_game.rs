@@ -24,12 +24,16 @@ fn make_configs(initial_max_capacity: u16) -> GameHubServerStartupPack
     };
     let running_games_cache_config = RunningGamesCacheConfig{
         expiry_duration: Duration::from_secs(20),
+        watchdog: None,
     };
 
     GameHubServerStartupPack{
         game_hub_server_config,
         pending_games_cache_config,
         running_games_cache_config,
+        metrics_config: None,
+        game_cost_estimator: None,
+        capacity_policy: None,
     }
 }
 
@@ -1,6 +1,9 @@
 //module tree
 mod cache_pending_games;
 mod cache_running_games;
+mod capacity_policy;
+mod game_hub_drains;
+mod game_hub_stats;
 mod game_lifecycle;
 mod host_reconnects;
 mod hub_rejects_game;
@@ -35,7 +35,7 @@ fn make_test_host_hub_client_with_id(
                 reconnect_interval: std::time::Duration::from_millis(5),
                 ..Default::default()
             },
-        ()
+        GameHubMetadata::default()
     )
 }
 
@@ -1,2 +1,4 @@
 //module tree
+#[cfg(unix)]
+mod process_pool;
 mod tests;
@@ -0,0 +1,177 @@
+//local shortcuts
+use bevy_girk_game_instance::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use enfync::AdoptOrDefault;
+
+//standard shortcuts
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stand-in for a game app binary.
+/// - Dies immediately if `{script}.die` exists.
+/// - Otherwise waits for a launch pack on stdin, then aborts the game with reason 'pooled'.
+/// - Only understands the JSON protocol.
+const FAKE_GAME_SCRIPT: &str = r#"#!/bin/sh
+if [ -e "$0.die" ]; then exit 3; fi
+read -r pack || exit 0
+id=$(echo "$pack" | sed 's/.*"game_id":\([0-9]*\).*/\1/')
+echo "{\"GameAborted\":[$id,\"pooled\"]}"
+"#;
+
+/// Stand-in for a game app binary that writes a lot of output while it is idle.
+/// - Writes more than a pipe buffer's worth of output to stderr before waiting for a launch pack.
+const NOISY_FAKE_GAME_SCRIPT: &str = r#"#!/bin/sh
+echo "idle stdout"
+i=0
+while [ $i -lt 2000 ]; do echo "idle stderr line $i padded with some extra text to fill the pipe" >&2; i=$((i+1)); done
+read -r pack || exit 0
+id=$(echo "$pack" | sed 's/.*"game_id":\([0-9]*\).*/\1/')
+echo "{\"GameAborted\":[$id,\"pooled\"]}"
+"#;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_fake_game(script: &str) -> PathBuf
+{
+    let dir = std::env::temp_dir().join(format!("bevy_girk_process_pool_{}", gen_rand128()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("fake_game.sh");
+    std::fs::write(&path, script).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn wait_for(mut condition: impl FnMut() -> bool)
+{
+    let start = Instant::now();
+    while !condition()
+    {
+        assert!(start.elapsed() < Duration::from_secs(5), "timed out");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn launch_and_check(launcher: &GameInstanceLauncherProcessPool, game_id: u64)
+{
+    let (report_sender, mut report_receiver) = new_io_channel::<GameInstanceReport>();
+    let mut instance = launcher.launch(GameLaunchPack{ game_id, game_launch_data: Vec::default() }, report_sender);
+
+    wait_for(|| !instance.is_running());
+    assert_eq!(instance.try_get(), Some(false));
+
+    let Some(GameInstanceReport::GameAborted(id, reason)) = report_receiver.try_recv()
+    else { panic!("did not receive game aborted report"); };
+    assert_eq!(id, game_id);
+    assert_eq!(reason, "pooled");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn process_pool_launches_games()
+{
+    let path = make_fake_game(FAKE_GAME_SCRIPT);
+    let launcher = GameInstanceLauncherProcessPool::new(
+            path.to_string_lossy().into_owned(),
+            enfync::builtin::native::TokioHandle::adopt_or_default(),
+            GameInstanceProcessPoolConfig{
+                pool_size       : 2,
+                refill_interval : Duration::from_millis(20),
+                protocol        : ChildProcessProtocol::Json,
+                ..Default::default()
+            }
+        );
+
+    // the pool fills up
+    wait_for(|| launcher.num_idle() == 2);
+
+    // launch games in pooled processes
+    launch_and_check(&launcher, 7u64);
+    launch_and_check(&launcher, 8u64);
+
+    // the pool refills
+    wait_for(|| launcher.num_idle() == 2);
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn process_pool_replaces_dead_processes()
+{
+    // idle processes die on startup
+    let path = make_fake_game(FAKE_GAME_SCRIPT);
+    let die_marker = path.with_extension("sh.die");
+    std::fs::write(&die_marker, "").unwrap();
+
+    let launcher = GameInstanceLauncherProcessPool::new(
+            path.to_string_lossy().into_owned(),
+            enfync::builtin::native::TokioHandle::adopt_or_default(),
+            GameInstanceProcessPoolConfig{
+                pool_size       : 1,
+                refill_interval : Duration::from_millis(20),
+                protocol        : ChildProcessProtocol::Json,
+                ..Default::default()
+            }
+        );
+    std::thread::sleep(Duration::from_millis(100));
+
+    // idle processes stop dying
+    std::fs::remove_file(&die_marker).unwrap();
+    std::thread::sleep(Duration::from_millis(200));
+
+    // - dead processes were replaced, so the game launches in a live process
+    launch_and_check(&launcher, 9u64);
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn process_pool_captures_idle_output()
+{
+    let path = make_fake_game(NOISY_FAKE_GAME_SCRIPT);
+    let log_dir = path.parent().unwrap().join("logs");
+    let launcher = GameInstanceLauncherProcessPool::new(
+            path.to_string_lossy().into_owned(),
+            enfync::builtin::native::TokioHandle::adopt_or_default(),
+            GameInstanceProcessPoolConfig{
+                pool_size       : 1,
+                refill_interval : Duration::from_secs(10),
+                protocol        : ChildProcessProtocol::Json,
+                log_config      : ChildProcessLogConfig{ log_dir: Some(log_dir.clone()), ..Default::default() },
+            }
+        );
+    wait_for(|| launcher.num_idle() == 1);
+
+    // - the idle process's output is drained so it doesn't block on a full pipe
+    wait_for(
+        || std::fs::read_to_string(log_dir.join("idle_0.log"))
+            .is_ok_and(|log| log.contains("idle stderr line 1999"))
+    );
+
+    // launch a game in the idle process
+    launch_and_check(&launcher, 5u64);
+
+    // - the idle output was kept in the game's log
+    let log = std::fs::read_to_string(log_dir.join("game_5.log")).unwrap();
+    assert!(log.contains("idle stdout"));
+    assert!(log.contains("idle stderr line 0"));
+    assert!(!log_dir.join("idle_0.log").exists());
+
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -4,12 +4,31 @@ use bevy_girk_game_instance::*;
 use bevy_girk_utils::*;
 
 //third-party shortcuts
+use bevy::prelude::*;
 
 //standard shortcuts
 use std::time::Duration;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Dummy game factory that sends heartbeats every 2 ticks.
+#[derive(Debug)]
+struct HeartbeatGameFactory;
+
+impl GameFactoryImpl for HeartbeatGameFactory
+{
+    type Launch = DummyLaunchPack;
+
+    fn new_game(&self, app: &mut App, game_id: u64, pack: DummyLaunchPack) -> Result<GameStartReport, String>
+    {
+        let report = DummyGameFactory.new_game(app, game_id, pack)?;
+        app.insert_resource(GameInstanceHeartbeatConfig{ interval_ticks: 2 });
+        Ok(report)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[test]
 fn basic_game()
 {
@@ -165,3 +184,43 @@ fn abort_game()
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn game_heartbeats()
+{
+    // prepare game instance launcher
+    let (report_sender, mut report_receiver) = new_io_channel::<GameInstanceReport>();
+    let factory = GameFactory::new(HeartbeatGameFactory);
+    let game_launcher = GameInstanceLauncher::new(GameInstanceLauncherLocal::new(factory));
+
+    // game config
+    let game_config = DummyGameConfig{
+            ticks_per_sec       : 100,
+            game_duration_ticks : 7,
+        };
+
+
+    // run the game to completion
+    let game_id = 1u64;
+    let dummy_pack = DummyLaunchPack{ config: game_config, clients: Vec::default() };
+    let launch_pack = GameLaunchPack::new(game_id, dummy_pack);
+    let mut instance = game_launcher.launch(launch_pack, report_sender);
+    std::thread::sleep(Duration::from_millis(150));
+    assert!(instance.try_get().unwrap());
+
+    // - game start report
+    let Some(GameInstanceReport::GameStart(id, _)) = report_receiver.try_recv()
+    else { panic!("did not receive game instance report"); };
+    assert_eq!(id, game_id);
+
+    // - heartbeats were sent until the game ended
+    let mut num_heartbeats = 0;
+    while let Some(GameInstanceReport::Heartbeat(id)) = report_receiver.try_recv()
+    {
+        assert_eq!(id, game_id);
+        num_heartbeats += 1;
+    }
+    assert!(num_heartbeats >= 3, "expected at least 3 heartbeats, received {num_heartbeats}");
+}
+
+//-------------------------------------------------------------------------------------------------------------------
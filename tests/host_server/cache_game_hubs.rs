@@ -51,8 +51,8 @@ fn cache_game_hubs()
     assert_eq!(cache.highest_nonzero_capacity_hub(), None);
 
     // add pending game
-    cache.add_pending_game(0, 0).expect("inserting pending game should succeed");
-    let Err(_) = cache.add_pending_game(0, 0) else { panic!("inserting duplicate pending game should fail"); };
+    cache.add_pending_game(0, 0, 1).expect("inserting pending game should succeed");
+    let Err(_) = cache.add_pending_game(0, 0, 1) else { panic!("inserting duplicate pending game should fail"); };
 
     // try to remove game
     if let Ok(_) = cache.remove_game(0, 0) { panic!("removing game should fail"); }
@@ -62,8 +62,8 @@ fn cache_game_hubs()
     let Err(_) = cache.remove_pending_game(0, 0) else { panic!("removing unknown pending game should fail"); };
 
     // add pending game
-    cache.add_pending_game(0, 0).expect("inserting pending game should succeed");
-    let Err(_) = cache.add_pending_game(0, 0) else { panic!("inserting duplicate pending game should fail"); };
+    cache.add_pending_game(0, 0, 1).expect("inserting pending game should succeed");
+    let Err(_) = cache.add_pending_game(0, 0, 1) else { panic!("inserting duplicate pending game should fail"); };
 
     // upgrade to game
     cache.upgrade_pending_game(0, 0).expect("upgrading pending game should succeed");
@@ -78,3 +78,69 @@ fn cache_game_hubs()
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn cache_game_hubs_weighted_costs()
+{
+    // make a cache with one hub
+    let mut cache = GameHubsCache::default();
+    cache.insert_hub(0).expect("inserting hub should succeed");
+    cache.set_hub_capacity(0, GameHubCapacity(10)).expect("setting capacity should succeed");
+
+    // pending games reduce available capacity by their cost
+    cache.add_pending_game(0, 0, 4).expect("inserting pending game should succeed");
+    cache.add_pending_game(0, 1, 3).expect("inserting pending game should succeed");
+
+    let hub = cache.hubs().next().unwrap();
+    assert_eq!(hub.num_pending, 2);
+    assert_eq!(hub.pending_cost, 7);
+    assert_eq!(hub.available_capacity(), 3);
+
+    // upgraded games no longer count as pending (the hub's next capacity report includes them)
+    cache.upgrade_pending_game(0, 0).expect("upgrading pending game should succeed");
+
+    let hub = cache.hubs().next().unwrap();
+    assert_eq!(hub.pending_cost, 3);
+    assert_eq!(hub.available_capacity(), 7);
+
+    // removed pending games release their cost
+    cache.remove_pending_game(0, 1).expect("removing pending game should succeed");
+
+    let hub = cache.hubs().next().unwrap();
+    assert_eq!(hub.pending_cost, 0);
+    assert_eq!(hub.available_capacity(), 10);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn cache_game_hubs_capacity_freed()
+{
+    // make a cache with one hub
+    let mut cache = GameHubsCache::default();
+    cache.insert_hub(0).expect("inserting hub should succeed");
+    assert!(!cache.take_capacity_freed());
+
+    // capacity increases free capacity
+    cache.set_hub_capacity(0, GameHubCapacity(10)).expect("setting capacity should succeed");
+    assert!(cache.take_capacity_freed());
+    assert!(!cache.take_capacity_freed());
+
+    // capacity decreases and new pending games don't free capacity
+    cache.set_hub_capacity(0, GameHubCapacity(5)).expect("setting capacity should succeed");
+    cache.add_pending_game(0, 0, 1).expect("inserting pending game should succeed");
+    cache.add_pending_game(0, 1, 1).expect("inserting pending game should succeed");
+    assert!(!cache.take_capacity_freed());
+
+    // removing or upgrading pending games frees capacity
+    cache.remove_pending_game(0, 0).expect("removing pending game should succeed");
+    assert!(cache.take_capacity_freed());
+    cache.upgrade_pending_game(0, 1).expect("upgrading pending game should succeed");
+    assert!(cache.take_capacity_freed());
+
+    // metadata changes may make the hub eligible for more games
+    cache.set_hub_metadata(0, GameHubMetadata::default()).expect("setting metadata should succeed");
+    assert!(cache.take_capacity_freed());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
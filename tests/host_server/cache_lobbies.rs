@@ -2,6 +2,7 @@
 use crate::test_helpers::*;
 use bevy_girk_backend_public::*;
 use bevy_girk_host_server::*;
+use bevy_girk_utils::*;
 
 //third-party shortcuts
 use renet2_setup::*;
@@ -22,6 +23,8 @@ fn cache_lobbies_basic()
     let mut cache = LobbiesCache::new(
             LobbiesCacheConfig{
                     max_request_size,
+                    lobby_searcher: None,
+                    owner_succession: OwnerSuccession::Disband,
                     lobby_checker: Box::new(BasicLobbyChecker{
                         max_lobby_players,
                         max_lobby_watchers,
@@ -53,13 +56,13 @@ fn cache_lobbies_basic()
             1u128,
             LobbyMemberData{ connection: ConnectionType::inferred(), color: BasicLobbyMemberType::Player.into() },
             &String::from("test")
-        ));
-    assert!(!cache.try_add_member(
+        ).is_ok());
+    assert_eq!(cache.try_add_member(
             lobby_id,
             2u128,
             LobbyMemberData{ connection: ConnectionType::inferred(), color: BasicLobbyMemberType::Player.into() },
             &String::from("bad_pass")
-        ));
+        ), Err(HostRequestError::WrongPassword));
     let lobby_ref_mut = cache.lobby_ref_mut(lobby_id).expect("should have mut lobby");
     assert_eq!(lobby_ref_mut.num_members(), 2);
 
@@ -93,6 +96,8 @@ fn cache_lobbies_owner_member_type()
     let mut cache = LobbiesCache::new(
             LobbiesCacheConfig{
                     max_request_size,
+                    lobby_searcher: None,
+                    owner_succession: OwnerSuccession::Disband,
                     lobby_checker: Box::new(BasicLobbyChecker{
                         max_lobby_players,
                         max_lobby_watchers,
@@ -134,6 +139,8 @@ fn cache_lobbies_added_member_type()
     let mut cache = LobbiesCache::new(
             LobbiesCacheConfig{
                     max_request_size,
+                    lobby_searcher: None,
+                    owner_succession: OwnerSuccession::Disband,
                     lobby_checker: Box::new(BasicLobbyChecker{
                         max_lobby_players,
                         max_lobby_watchers,
@@ -159,30 +166,30 @@ fn cache_lobbies_added_member_type()
             1u128,
             LobbyMemberData{ connection: ConnectionType::inferred(), color: BasicLobbyMemberType::Watcher.into() },
             &String::from("test")
-        ));
-    assert!(!cache.try_add_member(
+        ).is_ok());
+    assert_eq!(cache.try_add_member(
             lobby_id,
             1u128,
             LobbyMemberData{ connection: ConnectionType::inferred(), color: BasicLobbyMemberType::Player.into() },
             &String::from("test")
-        ));
+        ), Err(HostRequestError::Rejected));
     let lobby_ref = cache.lobby_ref(lobby_id).expect("should have lobby");
     assert_eq!(lobby_ref.num_members(), 2);
     assert!(lobby_ref.has_member(1u128));
 
     // add a member to the lobby w/ any type (invalid, all slots taken)
-    assert!(!cache.try_add_member(
+    assert_eq!(cache.try_add_member(
             lobby_id,
             2u128,
             LobbyMemberData{ connection: ConnectionType::inferred(), color: BasicLobbyMemberType::Player.into() },
             &String::from("test")
-        ));
-    assert!(!cache.try_add_member(
+        ), Err(HostRequestError::LobbyFull));
+    assert_eq!(cache.try_add_member(
             lobby_id,
             2u128,
             LobbyMemberData{ connection: ConnectionType::inferred(), color: BasicLobbyMemberType::Watcher.into() },
             &String::from("test")
-        ));
+        ), Err(HostRequestError::LobbyFull));
     let lobby_ref = cache.lobby_ref(lobby_id).expect("should have lobby");
     assert_eq!(lobby_ref.num_members(), 2);
     assert!(!lobby_ref.has_member(2u128));
@@ -201,6 +208,8 @@ fn cache_lobbies_search()
     let mut cache = LobbiesCache::new(
             LobbiesCacheConfig{
                     max_request_size,
+                    lobby_searcher: None,
+                    owner_succession: OwnerSuccession::Disband,
                     lobby_checker: Box::new(BasicLobbyChecker{
                         max_lobby_players,
                         max_lobby_watchers,
@@ -350,3 +359,140 @@ fn cache_lobbies_search()
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Sorts lobbies by distance between the owner id and a target id in the custom filter.
+#[derive(Debug)]
+struct OwnerDistanceSearcher;
+
+impl LobbySearcher for OwnerDistanceSearcher
+{
+    fn make_filter(&self, custom_filter: &[u8]) -> Option<Box<dyn LobbyFilter + '_>>
+    {
+        Some(Box::new(OwnerDistanceFilter(deser_msg::<u128>(custom_filter))))
+    }
+}
+
+/// Matches lobbies with owners near the target id (or all lobbies if there is no target).
+struct OwnerDistanceFilter(Option<u128>);
+
+impl LobbyFilter for OwnerDistanceFilter
+{
+    fn sort_key(&self, lobby: &LobbyData) -> Option<i64>
+    {
+        let Some(target) = self.0 else { return Some(0); };
+        let distance = lobby.owner_id.abs_diff(target);
+        (distance <= 10).then_some(distance as i64)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn cache_lobbies_filtered_search()
+{
+    // make a cache
+    let max_request_size      = 2;
+    let max_lobby_players     = 2;
+    let max_lobby_watchers    = 0;
+    let min_players_to_launch = max_lobby_players;
+    let mut cache = LobbiesCache::new(
+            LobbiesCacheConfig{
+                    max_request_size,
+                    lobby_searcher: Some(Box::new(OwnerDistanceSearcher)),
+                    owner_succession: OwnerSuccession::Disband,
+                    lobby_checker: Box::new(BasicLobbyChecker{
+                        max_lobby_players,
+                        max_lobby_watchers,
+                        min_players_to_launch,
+                    })
+                }
+        );
+
+    // add lobbies
+    let player = LobbyMemberData{ connection: ConnectionType::inferred(), color: BasicLobbyMemberType::Player.into() };
+    let first_lobby_id  = cache.new_lobby(10u128, player, String::from(""), Vec::default()).unwrap();
+    let second_lobby_id = cache.new_lobby(15u128, player, String::from("test"), Vec::default()).unwrap();
+    let third_lobby_id  = cache.new_lobby(100u128, player, String::from(""), Vec::default()).unwrap();
+    assert!(cache.try_add_member(second_lobby_id, 16u128, player, &String::from("test")).is_ok());
+
+    // no filter: all lobbies from newest to oldest
+    let result = get_searched_lobbies(&cache,
+            LobbySearchRequest::Filtered{ filter: LobbySearchFilter::default(), after: None, num: 10u16 }
+        );
+    assert_eq!(result.lobbies.len(), 2);
+    assert_eq!(result.lobbies.get(0).unwrap().id, third_lobby_id);
+    assert_eq!(result.lobbies.get(1).unwrap().id, second_lobby_id);
+    assert_eq!(result.num_younger, 0);
+    assert_eq!(result.total, 3);
+    assert_eq!(result.next_cursor, Some(LobbySearchCursor{ sort_key: 0, id: second_lobby_id }));
+
+    // a new lobby sorted before the page doesn't shift the next page
+    let fourth_lobby_id = cache.new_lobby(200u128, player, String::from(""), Vec::default()).unwrap();
+
+    // next page
+    let result = get_searched_lobbies(&cache,
+            LobbySearchRequest::Filtered{ filter: LobbySearchFilter::default(), after: result.next_cursor, num: 10u16 }
+        );
+    assert_eq!(result.lobbies.len(), 1);
+    assert_eq!(result.lobbies.get(0).unwrap().id, first_lobby_id);
+    assert_eq!(result.num_younger, 3);
+    assert_eq!(result.total, 4);
+    assert_eq!(result.next_cursor, Some(LobbySearchCursor{ sort_key: 0, id: first_lobby_id }));
+    cache.extract_lobby(fourth_lobby_id).unwrap();
+
+    // built-in filters
+    let filter = LobbySearchFilter{ max_members: Some(1), ..Default::default() };
+    let result = get_searched_lobbies(&cache, LobbySearchRequest::Filtered{ filter, after: None, num: 10u16 });
+    assert_eq!(result.total, 2);
+    assert!(result.lobbies.iter().all(|lobby| lobby.id != second_lobby_id));
+
+    let filter = LobbySearchFilter{ has_password: Some(true), ..Default::default() };
+    let result = get_searched_lobbies(&cache, LobbySearchRequest::Filtered{ filter, after: None, num: 10u16 });
+    assert_eq!(result.total, 1);
+    assert_eq!(result.lobbies.get(0).unwrap().id, second_lobby_id);
+
+    // custom filter and sort
+    let filter = LobbySearchFilter{ custom: ser_msg(&14u128), ..Default::default() };
+    let result = get_searched_lobbies(&cache, LobbySearchRequest::Filtered{ filter, after: None, num: 10u16 });
+    assert_eq!(result.total, 2);
+    assert_eq!(result.lobbies.get(0).unwrap().id, second_lobby_id);
+    assert_eq!(result.lobbies.get(1).unwrap().id, first_lobby_id);
+
+    // custom filter combined with built-in filters
+    let filter = LobbySearchFilter{ has_password: Some(false), custom: ser_msg(&14u128), ..Default::default() };
+    let result = get_searched_lobbies(&cache, LobbySearchRequest::Filtered{ filter, after: None, num: 10u16 });
+    assert_eq!(result.total, 1);
+    assert_eq!(result.lobbies.get(0).unwrap().id, first_lobby_id);
+
+    // a removed cursor lobby still marks the page position
+    let cursor = LobbySearchCursor{ sort_key: 0, id: second_lobby_id };
+    cache.extract_lobby(second_lobby_id).unwrap();
+    let result = get_searched_lobbies(&cache,
+            LobbySearchRequest::Filtered{ filter: LobbySearchFilter::default(), after: Some(cursor), num: 10u16 }
+        );
+    assert_eq!(result.lobbies.len(), 1);
+    assert_eq!(result.lobbies.get(0).unwrap().id, first_lobby_id);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn typed_lobby_searcher()
+{
+    let searcher = TypedLobbySearcher::<u32, u32>::new(
+            |rating, max_rating| rating <= max_rating,
+            |rating, _| -(*rating as i64),
+        );
+
+    let mut lobby = Lobby::new(0u64, 0u128, String::default(), ser_msg(&1500u32));
+    let filter = searcher.make_filter(&ser_msg(&2000u32)).expect("filter should be valid");
+    assert_eq!(filter.sort_key(&lobby.data), Some(-1500));
+    assert_eq!(searcher.make_filter(&ser_msg(&1000u32)).unwrap().sort_key(&lobby.data), None);
+
+    // invalid data never matches
+    assert!(searcher.make_filter(&Vec::default()).is_none());
+    lobby.data.serialized_custom_data = Vec::default();
+    assert_eq!(filter.sort_key(&lobby.data), None);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
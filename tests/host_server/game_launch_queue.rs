@@ -0,0 +1,374 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+use bevy::prelude::*;
+use renet2_setup::*;
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Launches lobbies with more members first.
+#[derive(Debug)]
+struct MembersLaunchPrioritizer;
+
+impl LaunchPrioritizer for MembersLaunchPrioritizer
+{
+    fn launch_priority(&self, lobby: &LobbyData) -> i32
+    {
+        lobby.members.len() as i32
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn lobby_data(id: u64, num_members: u128) -> LobbyData
+{
+    let mut lobby = LobbyData{ id, ..Default::default() };
+    for member_id in 0..num_members
+    {
+        lobby.members.insert(member_id, LobbyMemberData{
+                connection : ConnectionType::inferred(),
+                color      : BasicLobbyMemberType::Player.into(),
+            });
+    }
+    lobby
+}
+
+/// Games cost 2 if the lobby is owned by a specific user, otherwise 1.
+#[derive(Debug)]
+struct OwnerGameCost
+{
+    expensive_owner: u128,
+}
+
+impl GameCostEstimator for OwnerGameCost
+{
+    fn game_cost(&self, request: &GameStartRequest) -> u16
+    {
+        if request.lobby_data.owner_id == self.expensive_owner { 2 } else { 1 }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs(queue_timeout: Duration) -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 1u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 1u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: Some(GameLaunchQueueConfig{ queue_timeout, prioritizer: None }),
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_queued_lobby(host_server: &mut App, user: &mut HostUserClient) -> u64
+{
+    // user makes lobby
+    user.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+    // user launches lobby
+    user.request(UserToHostRequest::LaunchLobbyGame{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::PendingLobbyAckRequest{ id })) = user.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, made_lobby_id);
+    let Some(HostUserClientEvent::Ack(_)) = user.next()
+    else { panic!("client did not receive server msg"); };
+
+    // user acks lobby
+    user.send(UserToHostMsg::AckPendingLobby{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    made_lobby_id
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn game_launch_queue_order()
+{
+    let mut launch_queue = GameLaunchQueueCache::new(GameLaunchQueueConfig{
+            queue_timeout : Duration::from_secs(100),
+            prioritizer   : Some(Box::new(MembersLaunchPrioritizer)),
+        });
+
+    // lobbies are ordered by priority, then FIFO
+    assert!(launch_queue.add_lobby(&lobby_data(1, 1)));
+    assert!(launch_queue.add_lobby(&lobby_data(2, 2)));
+    assert!(launch_queue.add_lobby(&lobby_data(3, 1)));
+    assert!(launch_queue.add_lobby(&lobby_data(4, 2)));
+    assert!(!launch_queue.add_lobby(&lobby_data(1, 1)));
+    assert_eq!(launch_queue.lobby_ids().collect::<Vec<_>>(), vec![2, 4, 1, 3]);
+
+    // all positions are reported the first time
+    assert_eq!(launch_queue.take_position_updates(), vec![(2, 1), (4, 2), (1, 3), (3, 4)]);
+    assert_eq!(launch_queue.take_position_updates(), vec![]);
+
+    // only changed positions are reported
+    launch_queue.remove_lobby(4).unwrap();
+    assert_eq!(launch_queue.take_position_updates(), vec![(1, 2), (3, 3)]);
+
+    // no wait estimate until lobbies have launched
+    assert_eq!(launch_queue.estimated_wait(1), None);
+    launch_queue.remove_launched_lobby(2).unwrap();
+    assert_eq!(launch_queue.estimated_wait(1), None);
+    launch_queue.remove_launched_lobby(1).unwrap();
+    assert!(launch_queue.estimated_wait(1).is_some());
+    assert_eq!(launch_queue.num_queued(), 1);
+    assert!(launch_queue.remove_launched_lobby(1).is_err());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn game_launches_wait_for_hub_capacity()
+{
+    // make a host server
+    let (mut host_server, host_hub_url, host_user_url) = make_test_host_server(make_configs(Duration::from_secs(100)));
+
+    // make game hub client
+    let (_, mut hub) = make_test_host_hub_client(host_hub_url);
+
+    // make user clients
+    let (_, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (_, mut user2) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostHubClientEvent::Report(_) = hub.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+
+
+    // users launch lobbies while the hub has no capacity
+    let lobby_id1 = make_queued_lobby(&mut host_server, &mut user1);
+
+    // - user 1's lobby is queued
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameLaunchQueued{ id, position, estimated_wait })) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!((id, position, estimated_wait), (lobby_id1, 1, None));
+
+    let lobby_id2 = make_queued_lobby(&mut host_server, &mut user2);
+
+    // - user 2's lobby is queued behind user 1's lobby
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameLaunchQueued{ id, position, estimated_wait })) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!((id, position, estimated_wait), (lobby_id2, 2, None));
+    let None = user1.next() else { panic!("client received server msg unexpectedly"); };
+    let None = hub.next() else { panic!("hub received server msg unexpectedly"); };
+
+
+    // hub gains capacity for one game
+    hub.send(HubToHostMsg::Capacity(GameHubCapacity(1)));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - hub receives user 1's game request
+    let Some(HostHubClientEvent::Msg(HostToHubMsg::StartGame(request))) = hub.next()
+    else { panic!("hub did not receive server msg"); };
+    assert_eq!(request.game_id(), lobby_id1);
+
+    // - user 2's lobby moves to the front of the queue
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameLaunchQueued{ id, position, estimated_wait: _ })) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!((id, position), (lobby_id2, 1));
+
+    let None = hub.next() else { panic!("hub received server msg unexpectedly"); };
+    let None = user1.next() else { panic!("client received server msg unexpectedly"); };
+    let None = user2.next() else { panic!("client received server msg unexpectedly"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn queued_game_launch_times_out()
+{
+    // make a host server
+    let (mut host_server, host_hub_url, host_user_url) = make_test_host_server(make_configs(Duration::from_millis(50)));
+
+    // make game hub client
+    let (_, mut hub) = make_test_host_hub_client(host_hub_url);
+
+    // make user client
+    let (user_id, mut user) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostHubClientEvent::Report(_) = hub.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user.next().unwrap() else { unimplemented!(); };
+
+
+    // user launches lobby while the hub has no capacity
+    let made_lobby_id = make_queued_lobby(&mut host_server, &mut user);
+
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameLaunchQueued{ id, position: 1, estimated_wait: None })) = user.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, made_lobby_id);
+
+
+    // wait for the queue timeout
+    std::thread::sleep(Duration::from_millis(60));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - user gets an ack fail
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::PendingLobbyAckFail{ id })) = user.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, made_lobby_id);
+    assert_eq!(host_server.world().resource::<UsersCache>().get_user_state(user_id), Some(UserState::InLobby(made_lobby_id)));
+    assert_eq!(host_server.world().resource::<GameLaunchQueueCache>().num_queued(), 0);
+
+    let None = hub.next() else { panic!("hub received server msg unexpectedly"); };
+    let None = user.next() else { panic!("client received server msg unexpectedly"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn game_launch_queue_keeps_order()
+{
+    // make a host server where user 1's games cost more than other games
+    let mut configs = make_configs(Duration::from_secs(100));
+    configs.game_cost_estimator = Some(Box::new(OwnerGameCost{ expensive_owner: 1u128 }));
+    let (mut host_server, host_hub_url, host_user_url) = make_test_host_server(configs);
+
+    // make game hub client
+    let (_, mut hub) = make_test_host_hub_client(host_hub_url);
+
+    // make user clients
+    let (_, mut user1) = make_test_host_user_client_with_id(1u128, host_user_url.clone());
+    let (_, mut user2) = make_test_host_user_client_with_id(2u128, host_user_url.clone());
+    let (_, mut user3) = make_test_host_user_client_with_id(3u128, host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostHubClientEvent::Report(_) = hub.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user3.next().unwrap() else { unimplemented!(); };
+
+
+    // users 1 and 2 launch lobbies while the hub has no capacity
+    let lobby_id1 = make_queued_lobby(&mut host_server, &mut user1);
+    let lobby_id2 = make_queued_lobby(&mut host_server, &mut user2);
+
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameLaunchQueued{ id, position: 1, .. })) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, lobby_id1);
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameLaunchQueued{ id, position: 2, .. })) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, lobby_id2);
+
+
+    // hub gains capacity for user 2's game but not user 1's game
+    hub.send(HubToHostMsg::Capacity(GameHubCapacity(1)));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - user 2's game doesn't launch ahead of user 1's game
+    let None = hub.next() else { panic!("hub received server msg unexpectedly"); };
+
+
+    // user 3 launches a lobby that the hub has capacity for
+    let lobby_id3 = make_queued_lobby(&mut host_server, &mut user3);
+
+    // - user 3's lobby is queued behind the other lobbies
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameLaunchQueued{ id, position: 3, .. })) = user3.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, lobby_id3);
+    let None = hub.next() else { panic!("hub received server msg unexpectedly"); };
+
+
+    // hub gains capacity for user 1's and user 2's games
+    hub.send(HubToHostMsg::Capacity(GameHubCapacity(3)));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - hub receives the game requests in queue order
+    let Some(HostHubClientEvent::Msg(HostToHubMsg::StartGame(request))) = hub.next()
+    else { panic!("hub did not receive server msg"); };
+    assert_eq!(request.game_id(), lobby_id1);
+    let Some(HostHubClientEvent::Msg(HostToHubMsg::StartGame(request))) = hub.next()
+    else { panic!("hub did not receive server msg"); };
+    assert_eq!(request.game_id(), lobby_id2);
+    let None = hub.next() else { panic!("hub received server msg unexpectedly"); };
+
+    // - user 3's lobby moves to the front of the queue
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameLaunchQueued{ id, position: 1, .. })) = user3.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, lobby_id3);
+    assert_eq!(host_server.world().resource::<GameLaunchQueueCache>().lobby_ids().collect::<Vec<_>>(), vec![lobby_id3]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
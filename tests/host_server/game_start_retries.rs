@@ -0,0 +1,286 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : Some(GameStartRetryPolicy{ max_attempts: 2u16 }),
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 1u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 1u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn game_start_retries_on_other_hubs()
+{
+    // make a host server
+    let (mut host_server, host_hub_url, host_user_url) = make_test_host_server(make_configs());
+
+    // make game hub clients
+    let (_, mut hub1) = make_test_host_hub_client_with_id(0u128, host_hub_url.clone());
+    let (_, mut hub2) = make_test_host_hub_client_with_id(1u128, host_hub_url.clone());
+    let (_, mut hub3) = make_test_host_hub_client_with_id(2u128, host_hub_url);
+
+    // make user client
+    let (_, mut user) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostHubClientEvent::Report(_) = hub1.next().unwrap() else { unimplemented!(); };
+    let HostHubClientEvent::Report(_) = hub2.next().unwrap() else { unimplemented!(); };
+    let HostHubClientEvent::Report(_) = hub3.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user.next().unwrap() else { unimplemented!(); };
+
+    // hubs initialize their capacity
+    hub1.send(HubToHostMsg::Capacity(GameHubCapacity(5)));
+    hub2.send(HubToHostMsg::Capacity(GameHubCapacity(3)));
+    hub3.send(HubToHostMsg::Capacity(GameHubCapacity(1)));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+
+    // user makes lobby
+    user.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+
+    // user launches and acks the lobby
+    user.request(UserToHostRequest::LaunchLobbyGame{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::PendingLobbyAckRequest{ id })) = user.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, made_lobby_id);
+    let Some(HostUserClientEvent::Ack(_)) = user.next()
+    else { panic!("client did not receive server msg"); };
+
+    user.send(UserToHostMsg::AckPendingLobby{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - hub 1 receives game request (it has the most capacity)
+    let Some(HostHubClientEvent::Msg(HostToHubMsg::StartGame(request))) = hub1.next()
+    else { panic!("hub did not receive server msg"); };
+    assert_eq!(request.game_id(), made_lobby_id);
+
+
+    // hub 1 rejects the game
+    hub1.send(HubToHostMsg::Abort{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - hub 2 receives the game request (hub 1 is excluded even though it has the most capacity)
+    let Some(HostHubClientEvent::Msg(HostToHubMsg::StartGame(request))) = hub2.next()
+    else { panic!("hub did not receive server msg"); };
+    assert_eq!(request.game_id(), made_lobby_id);
+    let None = hub1.next() else { panic!("hub received server msg unexpectedly"); };
+
+
+    // hub 2 rejects the game
+    hub2.send(HubToHostMsg::Abort{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the user gets an ack fail (the lobby ran out of attempts)
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::PendingLobbyAckFail{ id })) = user.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, made_lobby_id);
+    assert!(host_server.world().resource::<PendingLobbiesCache>().lobby_ref(made_lobby_id).is_none());
+
+    // - hubs and user receive nothing else
+    let None = hub1.next() else { panic!("hub received server msg unexpectedly"); };
+    let None = hub2.next() else { panic!("hub received server msg unexpectedly"); };
+    let None = hub3.next() else { panic!("hub received server msg unexpectedly"); };
+    let None = user.next() else { panic!("client received server msg unexpectedly"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn game_start_retries_abort_queued_launch()
+{
+    // make a host server with a game launch queue
+    let mut configs = make_configs();
+    configs.host_server_config.game_start_retries = Some(GameStartRetryPolicy{ max_attempts: 3u16 });
+    configs.game_launch_queue_config = Some(GameLaunchQueueConfig{
+            queue_timeout : Duration::from_secs(100),
+            prioritizer   : None,
+        });
+    let (mut host_server, host_hub_url, host_user_url) = make_test_host_server(configs);
+
+    // make game hub clients
+    let (_, mut hub1) = make_test_host_hub_client_with_id(0u128, host_hub_url.clone());
+    let (_, mut hub2) = make_test_host_hub_client_with_id(1u128, host_hub_url);
+
+    // make user client
+    let (_, mut user) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostHubClientEvent::Report(_) = hub1.next().unwrap() else { unimplemented!(); };
+    let HostHubClientEvent::Report(_) = hub2.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user.next().unwrap() else { unimplemented!(); };
+
+    // only hub 1 has capacity
+    hub1.send(HubToHostMsg::Capacity(GameHubCapacity(5)));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+
+    // user makes lobby
+    user.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+
+    // user launches and acks the lobby
+    user.request(UserToHostRequest::LaunchLobbyGame{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::PendingLobbyAckRequest{ id })) = user.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, made_lobby_id);
+    let Some(HostUserClientEvent::Ack(_)) = user.next()
+    else { panic!("client did not receive server msg"); };
+
+    user.send(UserToHostMsg::AckPendingLobby{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - hub 1 receives game request
+    let Some(HostHubClientEvent::Msg(HostToHubMsg::StartGame(request))) = hub1.next()
+    else { panic!("hub did not receive server msg"); };
+    assert_eq!(request.game_id(), made_lobby_id);
+
+
+    // hub 1 rejects the game
+    hub1.send(HubToHostMsg::Abort{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the lobby is queued (hub 2 hasn't failed yet but has no capacity)
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameLaunchQueued{ id, position, estimated_wait: _ })) = user.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!((id, position), (made_lobby_id, 1));
+    let None = hub1.next() else { panic!("hub received server msg unexpectedly"); };
+    let None = hub2.next() else { panic!("hub received server msg unexpectedly"); };
+
+
+    // hub 2 gains capacity
+    hub2.send(HubToHostMsg::Capacity(GameHubCapacity(1)));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - hub 2 receives the game request
+    let Some(HostHubClientEvent::Msg(HostToHubMsg::StartGame(request))) = hub2.next()
+    else { panic!("hub did not receive server msg"); };
+    assert_eq!(request.game_id(), made_lobby_id);
+
+
+    // hub 2 rejects the game
+    hub2.send(HubToHostMsg::Abort{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the user gets an ack fail (all hubs failed, so the lobby is not queued again despite having attempts left)
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::PendingLobbyAckFail{ id })) = user.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, made_lobby_id);
+    assert!(host_server.world().resource::<PendingLobbiesCache>().lobby_ref(made_lobby_id).is_none());
+    assert!(!host_server.world().resource::<GameLaunchQueueCache>().has_lobby(made_lobby_id));
+
+    // - hubs and user receive nothing else
+    let None = hub1.next() else { panic!("hub received server msg unexpectedly"); };
+    let None = hub2.next() else { panic!("hub received server msg unexpectedly"); };
+    let None = user.next() else { panic!("client received server msg unexpectedly"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,232 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_lobbies_cache_config(max_request_size: u16) -> LobbiesCacheConfig
+{
+    LobbiesCacheConfig{
+            max_request_size,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 2u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 2u16,
+            })
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = make_lobbies_cache_config(10u16);
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn host_server_commands()
+{
+    // make a host server
+    let (command_sender, mut host_server, host_hub_url, host_user_url) =
+        make_test_host_server_with_commands(make_configs());
+
+    // make game hub client
+    let (hub_id, _hub) = make_test_host_hub_client(host_hub_url);
+
+    // make user clients
+    let (user1_id, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (user2_id, mut user2) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 makes lobby
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+
+    // list users, lobbies, games, and hubs
+    let (users_sender, users_receiver) = new_channel();
+    let (lobbies_sender, lobbies_receiver) = new_channel();
+    let (games_sender, games_receiver) = new_channel();
+    let (hubs_sender, hubs_receiver) = new_channel();
+    command_sender.send(HostServerCommand::ListUsers(users_sender)).unwrap();
+    command_sender.send(HostServerCommand::ListLobbies(lobbies_sender)).unwrap();
+    command_sender.send(HostServerCommand::ListOngoingGames(games_sender)).unwrap();
+    command_sender.send(HostServerCommand::ListGameHubs(hubs_sender)).unwrap();
+    host_server.update();
+
+    let mut users = users_receiver.try_recv().unwrap();
+    users.sort_by_key(|(id, _)| *id);
+    let mut expected_users = vec![(user1_id, UserState::InLobby(made_lobby_id)), (user2_id, UserState::Idle)];
+    expected_users.sort_by_key(|(id, _)| *id);
+    assert_eq!(users.iter().map(|(id, info)| (*id, info.user_state())).collect::<Vec<_>>(), expected_users);
+
+    let lobbies = lobbies_receiver.try_recv().unwrap();
+    assert_eq!(lobbies.len(), 1);
+    assert_eq!(lobbies[0].lobby.data.id, made_lobby_id);
+    assert!(!lobbies[0].pending);
+
+    assert!(games_receiver.try_recv().unwrap().is_empty());
+
+    let hubs = hubs_receiver.try_recv().unwrap();
+    assert_eq!(hubs.len(), 1);
+    assert_eq!(hubs[0].id, hub_id);
+
+
+    // close the lobby
+    let (close_sender, close_receiver) = new_channel();
+    command_sender.send(HostServerCommand::CloseLobby{ id: made_lobby_id, response: close_sender.clone() }).unwrap();
+    command_sender.send(HostServerCommand::CloseLobby{ id: made_lobby_id, response: close_sender }).unwrap();
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the first close succeeds and the second fails
+    assert_eq!(close_receiver.try_recv(), Some(Ok(())));
+    assert_eq!(close_receiver.try_recv(), Some(Err(HostServerCommandError::LobbyNotFound)));
+
+    // - user 1 leaves the lobby
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyLeave{ id })) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, made_lobby_id);
+    assert_eq!(host_server.world().resource::<UsersCache>().get_user_state(user1_id), Some(UserState::Idle));
+    assert!(host_server.world().resource::<LobbiesCache>().lobby_ref(made_lobby_id).is_none());
+
+
+    // abort a game that doesn't exist
+    let (abort_sender, abort_receiver) = new_channel();
+    command_sender.send(HostServerCommand::AbortGame{ id: made_lobby_id, response: abort_sender }).unwrap();
+    host_server.update();
+    assert_eq!(abort_receiver.try_recv(), Some(Err(HostServerCommandError::GameNotFound)));
+
+
+    // update lobbies cache config
+    let (config_sender, config_receiver) = new_channel();
+    command_sender.send(HostServerCommand::SetLobbiesCacheConfig{
+            config   : make_lobbies_cache_config(2u16),
+            response : config_sender
+        }).unwrap();
+    host_server.update();
+    assert_eq!(config_receiver.try_recv(), Some(()));
+    assert_eq!(host_server.world().resource::<LobbiesCache>().max_request_size(), 2u16);
+
+
+    // disconnect user 2
+    let (dc_sender, dc_receiver) = new_channel();
+    command_sender.send(HostServerCommand::DisconnectUser{ id: user2_id, response: dc_sender.clone() }).unwrap();
+    command_sender.send(HostServerCommand::DisconnectUser{ id: gen_rand128(), response: dc_sender }).unwrap();
+    host_server.update();
+    assert_eq!(dc_receiver.try_recv(), Some(Ok(())));
+    assert_eq!(dc_receiver.try_recv(), Some(Err(HostServerCommandError::UserNotFound)));
+
+    // - user 2 is unregistered
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    assert!(!host_server.world().resource::<UsersCache>().has_user(user2_id));
+    assert!(host_server.world().resource::<UsersCache>().has_user(user1_id));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn host_server_commands_disconnected_game_hub()
+{
+    // make a host server that buffers disconnected game hubs
+    let mut configs = make_configs();
+    configs.game_hub_disconnect_buffer_config.expiry_duration = Duration::from_secs(100);
+    let (command_sender, mut host_server, host_hub_url, _) = make_test_host_server_with_commands(configs);
+
+    // make game hub client
+    let (hub_id, hub) = make_test_host_hub_client(host_hub_url);
+
+    // hub connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    assert!(host_server.world().resource::<GameHubsCache>().has_hub(hub_id));
+
+
+    // hub disconnects
+    std::mem::drop(hub);
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+
+    // - the hub is buffered
+    assert!(host_server.world().resource::<GameHubsCache>().has_hub(hub_id));
+    assert!(host_server.world().resource::<GameHubDisconnectBuffer>().has_game_hub(hub_id));
+
+
+    // disconnecting the buffered hub fails
+    let (dc_sender, dc_receiver) = new_channel();
+    command_sender.send(HostServerCommand::DisconnectGameHub{ id: hub_id, response: dc_sender.clone() }).unwrap();
+    command_sender.send(HostServerCommand::DisconnectGameHub{ id: gen_rand128(), response: dc_sender }).unwrap();
+    host_server.update();
+    assert_eq!(dc_receiver.try_recv(), Some(Err(HostServerCommandError::GameHubNotConnected)));
+    assert_eq!(dc_receiver.try_recv(), Some(Err(HostServerCommandError::GameHubNotFound)));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
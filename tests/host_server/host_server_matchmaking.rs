@@ -0,0 +1,304 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Matches the longest-waiting users into groups of a fixed size.
+#[derive(Debug)]
+struct FifoMatchmaker
+{
+    group_size: usize,
+}
+
+impl Matchmaker for FifoMatchmaker
+{
+    fn try_make_match(&self, queue: &[QueuedUser]) -> Option<MatchmakerMatch>
+    {
+        if queue.len() < self.group_size { return None; }
+
+        let members: Vec<u128> = queue.iter().take(self.group_size).map(|queued| queued.user_id).collect();
+        Some(MatchmakerMatch{ owner_id: members[0], members, serialized_custom_data: Vec::default() })
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 2u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 2u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+    let matchmaking_queue_config = MatchmakingQueueConfig{
+            max_queue_size           : 10usize,
+            matchmaking_period_ticks : 1u64,
+            matchmaker               : Box::new(FifoMatchmaker{ group_size: 2usize }),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: Some(matchmaking_queue_config),
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn queued_users_are_matched_into_pending_lobby()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+
+    // make user clients
+    let (user1_id, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (user2_id, mut user2) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 enters the queue
+    user1.request(UserToHostRequest::EnterQueue{ mcolor: BasicLobbyMemberType::Player.into(), data: Vec::default() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - user 1 is queued but not matched
+    let Some(HostUserClientEvent::Ack(_request_id)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert!(user1.next().is_none());
+    assert_eq!(host_server.world().resource::<UsersCache>().get_user_state(user1_id), Some(UserState::InQueue));
+
+
+    // user 2 enters the queue
+    user2.request(UserToHostRequest::EnterQueue{ mcolor: BasicLobbyMemberType::Player.into(), data: Vec::default() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - both users receive the new lobby and an ack request
+    let Some(HostUserClientEvent::Ack(_request_id)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyState{ lobby })) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let matched_lobby_id = lobby.id;
+    assert_eq!(lobby.owner_id, user1_id);
+    assert_eq!(lobby.members.len(), 2);
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::PendingLobbyAckRequest{ id })) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, matched_lobby_id);
+
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyState{ lobby })) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(lobby.id, matched_lobby_id);
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::PendingLobbyAckRequest{ id })) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, matched_lobby_id);
+
+    // - the queue is empty and users are in the pending lobby
+    assert_eq!(host_server.world().resource::<MatchmakingQueueCache>().num_queued(), 0);
+    assert_eq!(host_server.world().resource::<UsersCache>().get_user_state(user1_id),
+        Some(UserState::InPendingLobby(matched_lobby_id)));
+    assert_eq!(host_server.world().resource::<UsersCache>().get_user_state(user2_id),
+        Some(UserState::InPendingLobby(matched_lobby_id)));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn user_leaves_queue()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+
+    // make user clients
+    let (user1_id, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (_, mut user2) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 enters then leaves the queue
+    user1.request(UserToHostRequest::EnterQueue{ mcolor: BasicLobbyMemberType::Player.into(), data: Vec::default() });
+    user1.request(UserToHostRequest::LeaveQueue);
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Ack(_request_id)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let Some(HostUserClientEvent::Ack(_request_id)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(host_server.world().resource::<UsersCache>().get_user_state(user1_id), Some(UserState::Idle));
+
+
+    // user 2 enters the queue
+    user2.request(UserToHostRequest::EnterQueue{ mcolor: BasicLobbyMemberType::Player.into(), data: Vec::default() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - user 2 is not matched with user 1
+    let Some(HostUserClientEvent::Ack(_request_id)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    assert!(user1.next().is_none());
+    assert!(user2.next().is_none());
+    assert_eq!(host_server.world().resource::<MatchmakingQueueCache>().num_queued(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn rejected_users_dont_stall_queue()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+
+    // make user clients
+    let (user1_id, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (user2_id, mut user2) = make_test_host_user_client(host_user_url.clone());
+    let (user3_id, mut user3) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user3.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 enters the queue as a watcher, which the lobby checker rejects
+    user1.request(UserToHostRequest::EnterQueue{ mcolor: BasicLobbyMemberType::Watcher.into(), data: Vec::default() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Ack(_request_id)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+
+
+    // users 2 and 3 enter the queue as players
+    user2.request(UserToHostRequest::EnterQueue{ mcolor: BasicLobbyMemberType::Player.into(), data: Vec::default() });
+    user3.request(UserToHostRequest::EnterQueue{ mcolor: BasicLobbyMemberType::Player.into(), data: Vec::default() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Ack(_request_id)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    let Some(HostUserClientEvent::Ack(_request_id)) = user3.next()
+    else { panic!("client did not receive server msg"); };
+
+    // - users 2 and 3 are matched even though the longest-waiting user was rejected
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyState{ lobby })) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    let mut member_ids: Vec<u128> = lobby.members.iter().map(|(member_id, _)| *member_id).collect();
+    member_ids.sort();
+    let mut expected_ids = vec![user2_id, user3_id];
+    expected_ids.sort();
+    assert_eq!(member_ids, expected_ids);
+    assert_eq!(host_server.world().resource::<LobbiesCache>().generated_count(), 1u64);  //no ids wasted on discarded matches
+
+    // - user 1 is still queued
+    assert!(user1.next().is_none());
+    assert_eq!(host_server.world().resource::<UsersCache>().get_user_state(user1_id), Some(UserState::InQueue));
+    assert!(host_server.world().resource::<MatchmakingQueueCache>().has_user(user1_id));
+    assert_eq!(host_server.world().resource::<MatchmakingQueueCache>().num_queued(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn duplicate_matched_members_are_removed()
+{
+    /// Matches the longest-waiting user with themself and the next user.
+    #[derive(Debug)]
+    struct DuplicatingMatchmaker;
+
+    impl Matchmaker for DuplicatingMatchmaker
+    {
+        fn try_make_match(&self, queue: &[QueuedUser]) -> Option<MatchmakerMatch>
+        {
+            if queue.len() < 2 { return None; }
+            let members = vec![queue[0].user_id, queue[0].user_id, queue[1].user_id];
+            Some(MatchmakerMatch{ owner_id: members[0], members, serialized_custom_data: Vec::default() })
+        }
+    }
+
+    let mut queue = MatchmakingQueueCache::new(MatchmakingQueueConfig{
+            max_queue_size           : 10usize,
+            matchmaking_period_ticks : 1u64,
+            matchmaker               : Box::new(DuplicatingMatchmaker),
+        });
+    let member_data = LobbyMemberData{
+            connection : renet2_setup::ConnectionType::Native,
+            color      : BasicLobbyMemberType::Player.into()
+        };
+    queue.add_user(1u128, member_data, Vec::default()).unwrap();
+    queue.add_user(2u128, member_data, Vec::default()).unwrap();
+
+    let matched = queue.try_next_match(&mut Default::default()).expect("match should be found");
+    assert_eq!(matched.members, vec![1u128, 2u128]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,126 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::io::{Read, Write};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn scrape_metrics(addr: std::net::SocketAddr, path: &str) -> String
+{
+    let mut stream = std::net::TcpStream::connect(addr).unwrap();
+    stream.write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes()).unwrap();
+    let mut response = String::default();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 2u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 2u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: Some(MetricsConfig{
+                listen_addr         : "127.0.0.1:0".parse().unwrap(),
+                sample_period_ticks : 1u64,
+            }),
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn host_server_metrics()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+    let metrics_addr = host_server.world().resource::<HostServerMetrics>().local_addr();
+
+    // make user clients
+    let (_, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (_, _user2) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 makes lobby
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+
+
+    // scrape metrics
+    let response = scrape_metrics(metrics_addr, "/metrics");
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("# TYPE girk_host_lobbies gauge\ngirk_host_lobbies 1\n"));
+    assert!(response.contains("girk_host_pending_lobbies 0\n"));
+    assert!(response.contains("girk_host_users{state=\"idle\"} 1\n"));
+    assert!(response.contains("girk_host_users{state=\"in_lobby\"} 1\n"));
+    assert!(response.contains("# TYPE girk_host_hub_aborts_total counter\ngirk_host_hub_aborts_total 0\n"));
+
+    // unknown paths are not found
+    let response = scrape_metrics(metrics_addr, "/");
+    assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,303 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_snapshot_path() -> PathBuf
+{
+    std::env::temp_dir().join(format!("bevy_girk_host_snapshot_{}.bin", gen_rand128()))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs(snapshot_path: PathBuf, restored_user_expiry: Duration) -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 2u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 2u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(10),
+        };
+    let persistence_config = HostServerPersistenceConfig{
+            snapshot_period_ticks : 1u64,
+            restored_user_expiry,
+            backend               : Box::new(HostServerSnapshotFile::new(snapshot_path)),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: Some(persistence_config),
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn host_restores_lobby_after_restart()
+{
+    let snapshot_path = make_snapshot_path();
+
+    // make a host server
+    let (mut host_server, _, host_user_url) =
+        make_test_host_server(make_configs(snapshot_path.clone(), Duration::from_secs(10)));
+
+    // make a user client
+    let (user1_id, mut user1) = make_test_host_user_client(host_user_url);
+
+    // client connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 makes lobby
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - user 1 recieves lobby
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+
+    // restart the host server
+    drop(host_server);
+    std::mem::drop(user1);
+    std::thread::sleep(Duration::from_millis(15));
+
+    let (mut host_server, _, host_user_url) =
+        make_test_host_server(make_configs(snapshot_path.clone(), Duration::from_secs(10)));
+
+    // - the lobby and its member were restored
+    let lobby = host_server.world().resource::<LobbiesCache>().lobby_ref(made_lobby_id).expect("lobby restored");
+    assert!(lobby.is_owner(user1_id));
+    assert_eq!(host_server.world().resource::<UsersCache>().get_user_state(user1_id),
+        Some(UserState::InLobby(made_lobby_id)));
+    assert!(host_server.world().resource::<UserDisconnectBuffer>().has_user(user1_id));
+
+
+    // user 1 reconnects
+    let (_, mut user1) = make_test_host_user_client_with_id(user1_id, host_user_url);
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - user 1 receives their lobby
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyState{ lobby })) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(lobby.id, made_lobby_id);
+    assert!(!host_server.world().resource::<UserDisconnectBuffer>().has_user(user1_id));
+
+
+    // new lobbies don't collide with restored lobby ids
+    let (_, mut user2) = make_test_host_user_client(host_server.world().resource::<HostUserServer>().url());
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+
+    user2.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    assert!(lobby.id > made_lobby_id);
+
+    let _ = std::fs::remove_file(snapshot_path);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn host_drops_restored_users_that_dont_reconnect()
+{
+    let snapshot_path = make_snapshot_path();
+
+    // save a snapshot with a lobby
+    let owner_id = gen_rand128();
+    let mut lobby = Lobby::new(5u64, owner_id, String::from("test"), Vec::default());
+    lobby.add_member(
+        owner_id,
+        LobbyMemberData{ connection: renet2_setup::ConnectionType::Native, color: BasicLobbyMemberType::Player.into() }
+    );
+    HostServerSnapshotFile::new(snapshot_path.clone())
+        .save(&HostServerSnapshot{ lobby_id_counter: 5u64, lobbies: vec![lobby], ongoing_games: Vec::default() })
+        .expect("saving snapshot should work");
+
+    // make a host server that restores the snapshot
+    let (mut host_server, _, _) = make_test_host_server(make_configs(snapshot_path.clone(), Duration::from_secs(0)));
+    assert!(host_server.world().resource::<LobbiesCache>().lobby_ref(5u64).is_some());
+    assert!(host_server.world().resource::<UsersCache>().has_user(owner_id));
+
+    // the owner doesn't reconnect, so the restored user and their lobby are removed
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+
+    assert!(host_server.world().resource::<LobbiesCache>().lobby_ref(5u64).is_none());
+    assert!(!host_server.world().resource::<UsersCache>().has_user(owner_id));
+
+    // the snapshot no longer contains the lobby
+    let snapshot = HostServerSnapshotFile::new(snapshot_path.clone()).load().expect("snapshot should exist");
+    assert_eq!(snapshot.lobbies.len(), 0);
+    assert_eq!(snapshot.lobby_id_counter, 5u64);
+
+    let _ = std::fs::remove_file(snapshot_path);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn restored_user_keeps_account_id_on_reconnect()
+{
+    let snapshot_path = make_snapshot_path();
+
+    // save a snapshot with a lobby
+    let owner_id = gen_rand128();
+    let mut lobby = Lobby::new(5u64, owner_id, String::from("test"), Vec::default());
+    lobby.add_member(
+        owner_id,
+        LobbyMemberData{ connection: renet2_setup::ConnectionType::Native, color: BasicLobbyMemberType::Player.into() }
+    );
+    HostServerSnapshotFile::new(snapshot_path.clone())
+        .save(&HostServerSnapshot{ lobby_id_counter: 5u64, lobbies: vec![lobby], ongoing_games: Vec::default() })
+        .expect("saving snapshot should work");
+
+    // make a host server that restores the snapshot and authenticates users
+    let authenticator = HmacUserAuthenticator::new(b"test secret".as_slice());
+    let mut configs = make_configs(snapshot_path.clone(), Duration::from_secs(10));
+    configs.user_authenticator = Some(Box::new(HmacUserAuthenticator::new(b"test secret".as_slice())));
+    let (mut host_server, _, host_user_url) = make_test_host_server(configs);
+    assert!(host_server.world().resource::<UserDisconnectBuffer>().has_user(owner_id));
+
+    // the owner reconnects with an account token
+    let token = authenticator.make_token(owner_id, 42u128, SystemTime::now() + Duration::from_secs(100));
+    let (_, mut owner) = make_test_host_user_client_with_auth(owner_id, host_user_url, token);
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = owner.next().unwrap() else { unimplemented!(); };
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyState{ .. })) = owner.next()
+    else { panic!("client did not receive server msg"); };
+
+    // - the reclaimed user has their account id
+    assert!(!host_server.world().resource::<UserDisconnectBuffer>().has_user(owner_id));
+    let users_cache = host_server.world().resource::<UsersCache>();
+    assert_eq!(users_cache.get_user_info(owner_id).and_then(|info| info.account_id()), Some(42u128));
+
+    let _ = std::fs::remove_file(snapshot_path);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn host_restores_ongoing_game_after_restart()
+{
+    let snapshot_path = make_snapshot_path();
+
+    // save a snapshot with an ongoing game
+    let user_id = gen_rand128();
+    let hub_id  = gen_rand128();
+    let report  = dummy_game_start_report(vec![user_id]);
+    let ongoing_game = OngoingGame{
+            game_id     : 7u64,
+            game_hub_id : hub_id,
+            metas       : report.metas,
+            start_infos : report.start_infos,
+        };
+    HostServerSnapshotFile::new(snapshot_path.clone())
+        .save(&HostServerSnapshot{ lobby_id_counter: 7u64, lobbies: Vec::default(), ongoing_games: vec![ongoing_game] })
+        .expect("saving snapshot should work");
+
+    // make a host server that restores the snapshot
+    let (mut host_server, host_hub_url, host_user_url) =
+        make_test_host_server(make_configs(snapshot_path.clone(), Duration::from_secs(10)));
+
+    // - the game's hub is waiting to reconnect
+    assert!(host_server.world().resource::<GameHubsCache>().has_game(hub_id, 7u64));
+    assert!(host_server.world().resource::<GameHubDisconnectBuffer>().has_game_hub(hub_id));
+
+    // the hub and user reconnect
+    let (_, mut hub) = make_test_host_hub_client_with_id(hub_id, host_hub_url);
+    let (_, mut user) = make_test_host_user_client_with_id(user_id, host_user_url);
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostHubClientEvent::Report(_) = hub.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user.next().unwrap() else { unimplemented!(); };
+
+    // - the hub re-owns its game
+    assert!(!host_server.world().resource::<GameHubDisconnectBuffer>().has_game_hub(hub_id));
+    assert!(host_server.world().resource::<GameHubsCache>().has_game(hub_id, 7u64));
+
+    // - the user is reconnected to the game
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::GameStart{ id, .. })) = user.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, 7u64);
+
+    let _ = std::fs::remove_file(snapshot_path);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
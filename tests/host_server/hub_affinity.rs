@@ -0,0 +1,241 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Requires games owned by a specific user to be placed in the "us" region.
+#[derive(Debug)]
+struct OwnerRegionAffinityChecker
+{
+    us_owner: u128,
+}
+
+impl HubAffinityChecker for OwnerRegionAffinityChecker
+{
+    fn hub_affinity(&self, lobby: &LobbyData) -> HubAffinity
+    {
+        if lobby.owner_id != self.us_owner { return HubAffinity::default(); }
+        HubAffinity{ required_regions: vec![String::from("us")], ..Default::default() }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn hub(id: u128, capacity: u16, region: &str, tags: &[&str]) -> GameHubInfo
+{
+    GameHubInfo{
+        id,
+        capacity     : GameHubCapacity(capacity),
+        num_pending  : 0,
+        pending_cost : 0,
+        num_games    : 0,
+        metadata     : GameHubMetadata{
+            region : Some(String::from(region)),
+            tags   : tags.iter().map(|tag| String::from(*tag)).collect(),
+            custom : Vec::default(),
+        },
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs(us_owner: u128) -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 1u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 1u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: Some(Box::new(OwnerRegionAffinityChecker{ us_owner })),
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn hub_affinity_filters_hubs()
+{
+    let hubs = vec![hub(1, 1, "eu", &["gpu"]), hub(2, 0, "us", &[]), hub(3, 1, "us", &["gpu"]), hub(4, 1, "asia", &[])];
+    let ids = |hubs: &Vec<GameHubInfo>| hubs.iter().map(|hub| hub.id).collect::<Vec<_>>();
+
+    // no constraints
+    let mut filtered = hubs.clone();
+    HubAffinity::default().filter_hubs(&mut filtered, 1u16);
+    assert_eq!(ids(&filtered), vec![1, 2, 3, 4]);
+
+    // required regions
+    let mut filtered = hubs.clone();
+    HubAffinity{ required_regions: vec![String::from("us")], ..Default::default() }.filter_hubs(&mut filtered, 1u16);
+    assert_eq!(ids(&filtered), vec![2, 3]);
+
+    // required tags
+    let mut filtered = hubs.clone();
+    HubAffinity{ required_tags: vec![String::from("gpu")], ..Default::default() }.filter_hubs(&mut filtered, 1u16);
+    assert_eq!(ids(&filtered), vec![1, 3]);
+
+    // preferred regions with capacity
+    let mut filtered = hubs.clone();
+    HubAffinity{ preferred_regions: vec![String::from("us")], ..Default::default() }.filter_hubs(&mut filtered, 1u16);
+    assert_eq!(ids(&filtered), vec![2, 3]);
+
+    // required and preferred regions combine
+    let mut filtered = hubs.clone();
+    HubAffinity{
+        preferred_regions : vec![String::from("us")],
+        required_tags     : vec![],
+        required_regions  : vec![String::from("us"), String::from("asia")],
+    }.filter_hubs(&mut filtered, 1u16);
+    assert_eq!(ids(&filtered), vec![2, 3]);
+
+    // preferred regions without capacity fall back to other hubs
+    let mut filtered = vec![hub(2, 0, "us", &[]), hub(4, 1, "asia", &[])];
+    HubAffinity{ preferred_regions: vec![String::from("us")], ..Default::default() }.filter_hubs(&mut filtered, 1u16);
+    assert_eq!(ids(&filtered), vec![2, 4]);
+
+    // preferred regions without enough capacity for the game's cost fall back to other hubs
+    let mut filtered = vec![hub(2, 2, "us", &[]), hub(4, 3, "asia", &[])];
+    HubAffinity{ preferred_regions: vec![String::from("us")], ..Default::default() }.filter_hubs(&mut filtered, 3u16);
+    assert_eq!(ids(&filtered), vec![2, 4]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn games_are_placed_in_required_region()
+{
+    // make a host server
+    // - user 1's games must be placed in the us region
+    let user1_id = gen_rand128();
+    let (mut host_server, host_hub_url, host_user_url) = make_test_host_server(make_configs(user1_id));
+
+    // make game hub clients
+    let eu_metadata = GameHubMetadata{ region: Some(String::from("eu")), ..Default::default() };
+    let us_metadata = GameHubMetadata{ region: Some(String::from("us")), ..Default::default() };
+    let (_, mut hub1) = make_test_host_hub_client_with_metadata(0u128, host_hub_url.clone(), eu_metadata);
+    let (_, mut hub2) = make_test_host_hub_client_with_metadata(1u128, host_hub_url, us_metadata);
+
+    // make user clients
+    let (_, mut user1) = make_test_host_user_client_with_id(user1_id, host_user_url.clone());
+    let (_, mut user2) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostHubClientEvent::Report(_) = hub1.next().unwrap() else { unimplemented!(); };
+    let HostHubClientEvent::Report(_) = hub2.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+
+    // hubs initialize their capacity
+    // - the eu hub has more capacity, so it would be selected if there were no affinity
+    hub1.send(HubToHostMsg::Capacity(GameHubCapacity(5)));
+    hub2.send(HubToHostMsg::Capacity(GameHubCapacity(1)));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the host saved the hubs' metadata
+    let hubs: Vec<GameHubInfo> = host_server.world().resource::<GameHubsCache>().hubs().collect();
+    assert!(hubs.iter().any(|hub| hub.id == 1u128 && hub.metadata.region.as_deref() == Some("us")));
+
+
+    // users make lobbies
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    user2.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id1 = lobby.id;
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id2 = lobby.id;
+
+
+    // users launch and ack their lobbies
+    user1.request(UserToHostRequest::LaunchLobbyGame{ id: made_lobby_id1 });
+    user2.request(UserToHostRequest::LaunchLobbyGame{ id: made_lobby_id2 });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    user1.send(UserToHostMsg::AckPendingLobby{ id: made_lobby_id1 });
+    user2.send(UserToHostMsg::AckPendingLobby{ id: made_lobby_id2 });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the us hub receives the us lobby's game
+    let Some(HostHubClientEvent::Msg(HostToHubMsg::StartGame(request))) = hub2.next()
+    else { panic!("hub did not receive server msg"); };
+    assert_eq!(request.game_id(), made_lobby_id1);
+
+    // - the eu hub receives the other game
+    let Some(HostHubClientEvent::Msg(HostToHubMsg::StartGame(request))) = hub1.next()
+    else { panic!("hub did not receive server msg"); };
+    assert_eq!(request.game_id(), made_lobby_id2);
+
+    let None = hub1.next() else { panic!("hub received server msg unexpectedly"); };
+    let None = hub2.next() else { panic!("hub received server msg unexpectedly"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
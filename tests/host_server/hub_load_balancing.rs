@@ -18,9 +18,13 @@ fn make_configs() -> HostServerStartupPack
     let host_server_config = HostServerConfig{
             ticks_per_sec                   : None,  //we will manually update the host server
             ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
         };
     let lobbies_cache_config = LobbiesCacheConfig{
             max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
             lobby_checker: Box::new(BasicLobbyChecker{
                 max_lobby_players     : 1u16,
                 max_lobby_watchers    : 0u16,
@@ -44,6 +48,16 @@ fn make_configs() -> HostServerStartupPack
             pending_lobbies_cache_config,
             ongoing_games_cache_config,
             game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
         }
 }
 
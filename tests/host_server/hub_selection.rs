@@ -0,0 +1,93 @@
+//local shortcuts
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn hub(id: u128, capacity: u16, num_pending: usize, num_games: usize) -> GameHubInfo
+{
+    GameHubInfo{
+        id,
+        capacity     : GameHubCapacity(capacity),
+        num_pending,
+        pending_cost : num_pending as u32,
+        num_games,
+        metadata     : GameHubMetadata::default(),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn greedy_hub_selector()
+{
+    let request = GameStartRequest::default();
+    let mut selector = GreedyHubSelector;
+
+    // no hubs
+    assert_eq!(selector.select_hub(&request, &[]), None);
+
+    // no hubs with available capacity
+    assert_eq!(selector.select_hub(&request, &[hub(1, 0, 0, 5), hub(2, 1, 1, 0)]), None);
+
+    // highest available capacity wins
+    assert_eq!(selector.select_hub(&request, &[hub(1, 3, 0, 0), hub(2, 5, 1, 0), hub(3, 2, 0, 0)]), Some(2));
+
+    // ties are broken by highest id
+    assert_eq!(selector.select_hub(&request, &[hub(1, 3, 0, 0), hub(2, 3, 0, 0)]), Some(2));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn round_robin_hub_selector()
+{
+    let request = GameStartRequest::default();
+    let mut selector = RoundRobinHubSelector::default();
+    let hubs = [hub(1, 1, 0, 0), hub(2, 0, 0, 0), hub(3, 5, 0, 0), hub(4, 2, 0, 0)];
+
+    // cycle through hubs with available capacity
+    assert_eq!(selector.select_hub(&request, &hubs), Some(1));
+    assert_eq!(selector.select_hub(&request, &hubs), Some(3));
+    assert_eq!(selector.select_hub(&request, &hubs), Some(4));
+    assert_eq!(selector.select_hub(&request, &hubs), Some(1));
+
+    // the previous hub was removed
+    assert_eq!(selector.select_hub(&request, &[hub(3, 5, 0, 0), hub(4, 2, 0, 0)]), Some(3));
+
+    // no hubs with available capacity
+    assert_eq!(selector.select_hub(&request, &[hub(2, 0, 0, 0)]), None);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn bin_packing_hub_selector()
+{
+    let request = GameStartRequest::default();
+    let mut selector = BinPackingHubSelector;
+
+    // busy hub with the least available capacity wins
+    assert_eq!(selector.select_hub(&request, &[hub(1, 5, 0, 0), hub(2, 1, 0, 4), hub(3, 0, 0, 9)]), Some(2));
+
+    // hubs are packed by cost, not by number of games
+    assert_eq!(selector.select_hub(&request, &[hub(1, 8, 0, 5), hub(2, 3, 0, 1)]), Some(2));
+
+    // pending games make a hub busy
+    assert_eq!(selector.select_hub(&request, &[hub(1, 2, 0, 0), hub(2, 5, 1, 0)]), Some(2));
+
+    // idle hubs are used when busy hubs are full
+    assert_eq!(selector.select_hub(&request, &[hub(1, 5, 0, 0), hub(2, 1, 1, 4)]), Some(1));
+
+    // ties are broken by lowest id
+    assert_eq!(selector.select_hub(&request, &[hub(1, 3, 0, 2), hub(2, 3, 0, 1)]), Some(1));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
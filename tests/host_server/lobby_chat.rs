@@ -0,0 +1,177 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Drops messages containing 'bad'.
+#[derive(Debug)]
+struct BadWordFilter;
+
+impl LobbyChatFilter for BadWordFilter
+{
+    fn filter(&self, _sender_id: u128, _lobby: &LobbyData, text: String) -> Option<String>
+    {
+        if text.contains("bad") { return None; }
+        Some(text)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 2u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 2u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+    let lobby_chat_config = LobbyChatConfig{
+            max_message_len         : 10usize,
+            max_messages_per_period : 4u16,
+            rate_limit_period       : Duration::from_secs(100),
+            filter                  : Some(Box::new(BadWordFilter)),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: Some(lobby_chat_config),
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn lobby_chat_is_relayed()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+
+    // make user clients
+    let (user1_id, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (_, mut user2) = make_test_host_user_client(host_user_url.clone());
+    let (_, mut user3) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user3.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 makes lobby
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+
+    // user 2 joins lobby
+    user2.request(UserToHostRequest::JoinLobby{
+            id     : made_lobby_id,
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test")
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ .. }, _)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyState{ .. })) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyState{ .. })) = user2.next()
+    else { panic!("client did not receive server msg"); };
+
+
+    // user 1 sends chat messages
+    // - valid, too long, filtered, valid, rate limited
+    user1.send(UserToHostMsg::LobbyChat{ id: made_lobby_id, text: String::from("hello") });
+    user1.send(UserToHostMsg::LobbyChat{ id: made_lobby_id, text: String::from("hello there friend") });
+    user1.send(UserToHostMsg::LobbyChat{ id: made_lobby_id, text: String::from("bad") });
+    user1.send(UserToHostMsg::LobbyChat{ id: made_lobby_id, text: String::from("gl hf") });
+    user1.send(UserToHostMsg::LobbyChat{ id: made_lobby_id, text: String::from("spam") });
+
+    // user 3 (not in the lobby) sends a chat message
+    user3.send(UserToHostMsg::LobbyChat{ id: made_lobby_id, text: String::from("hi") });
+
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - lobby members receive the valid messages
+    for user in [&mut user1, &mut user2]
+    {
+        let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyChat{ id, sender, text })) = user.next()
+        else { panic!("client did not receive server msg"); };
+        assert_eq!(id, made_lobby_id);
+        assert_eq!(sender, user1_id);
+        assert_eq!(text, String::from("hello"));
+
+        let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyChat{ text, .. })) = user.next()
+        else { panic!("client did not receive server msg"); };
+        assert_eq!(text, String::from("gl hf"));
+
+        assert!(user.next().is_none());
+    }
+
+    // - user 3 receives nothing
+    assert!(user3.next().is_none());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
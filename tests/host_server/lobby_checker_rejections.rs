@@ -18,9 +18,13 @@ fn make_configs(lobby_size: u16) -> HostServerStartupPack
     let host_server_config = HostServerConfig{
             ticks_per_sec                   : None,  //we will manually update the host server
             ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
         };
     let lobbies_cache_config = LobbiesCacheConfig{
             max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
             lobby_checker: Box::new(BasicLobbyChecker{
                 max_lobby_players     : lobby_size,
                 max_lobby_watchers    : 0u16,
@@ -44,6 +48,16 @@ fn make_configs(lobby_size: u16) -> HostServerStartupPack
             pending_lobbies_cache_config,
             ongoing_games_cache_config,
             game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
         }
 }
 
@@ -93,10 +107,11 @@ fn lobby_owner_invalid()
     host_server.update();
     std::thread::sleep(Duration::from_millis(15));
 
-    // - user 1 recieves nothing (make lobby failed because no players are allowed in the lobby)
-    let Some(HostUserClientEvent::Reject(id)) = user1.next()
+    // - user 1 recieves an error (make lobby failed because no players are allowed in the lobby)
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(err), id)) = user1.next()
     else { panic!("client did not receive server message"); };
     assert_eq!(id, request.id());
+    assert_eq!(err, HostRequestError::LobbyFull);
 
 
     // nothing more to receive
@@ -148,10 +163,11 @@ fn lobby_data_invalid()
     host_server.update();
     std::thread::sleep(Duration::from_millis(15));
 
-    // - user 1 recieves request rejection (make lobby failed because no custom data is allowed)
-    let Some(HostUserClientEvent::Reject(id)) = user1.next()
+    // - user 1 recieves an error (make lobby failed because no custom data is allowed)
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(err), id)) = user1.next()
     else { panic!("client did not receive server message"); };
     assert_eq!(id, request.id());
+    assert_eq!(err, HostRequestError::Rejected);
 
     
     // no more incoming
@@ -221,10 +237,11 @@ fn lobby_member_invalid_type()
     host_server.update();
     std::thread::sleep(Duration::from_millis(15));
 
-    // - user 2 receives nothing (no watchers allowed)
-    let Some(HostUserClientEvent::Reject(id)) = user2.next()
+    // - user 2 receives an error (no watchers allowed)
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(err), id)) = user2.next()
     else { panic!("client did not receive server message"); };
     assert_eq!(id, request.id());
+    assert_eq!(err, HostRequestError::LobbyFull);
 
 
     // nothing more to receive
@@ -294,10 +311,11 @@ fn lobby_member_invalid_password()
     host_server.update();
     std::thread::sleep(Duration::from_millis(15));
 
-    // - user 2 receives nothing (bad password)
-    let Some(HostUserClientEvent::Reject(id)) = user2.next()
+    // - user 2 receives an error (bad password)
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(err), id)) = user2.next()
     else { panic!("client did not receive server message"); };
     assert_eq!(id, request.id());
+    assert_eq!(err, HostRequestError::WrongPassword);
 
 
     // nothing more to receive
@@ -0,0 +1,157 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 3u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 3u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn owner_updates_lobby_data()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+
+    // make user clients
+    let (_, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (_, mut user2) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 makes lobby
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+
+    // user 2 joins lobby
+    user2.request(UserToHostRequest::JoinLobby{
+            id     : made_lobby_id,
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test")
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+    while user1.next().is_some() {}
+    while user2.next().is_some() {}
+
+
+    // user 2 (not owner) tries to update the lobby data
+    user2.request(UserToHostRequest::UpdateLobbyData{ id: made_lobby_id, data: Vec::default() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(HostRequestError::NotOwner), _)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+
+
+    // user 1 tries to set invalid lobby data (no custom data is allowed)
+    user1.request(UserToHostRequest::UpdateLobbyData{ id: made_lobby_id, data: vec![1u8] });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - request is rejected and the lobby is unchanged
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(HostRequestError::Rejected), _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert!(user2.next().is_none());
+    let lobbies_cache = host_server.world().resource::<LobbiesCache>();
+    assert_eq!(lobbies_cache.lobby_ref(made_lobby_id).unwrap().custom_data().len(), 0);
+
+
+    // user 1 sets valid lobby data
+    user1.request(UserToHostRequest::UpdateLobbyData{ id: made_lobby_id, data: Vec::default() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - members receive the new lobby state
+    for user in [&mut user1, &mut user2]
+    {
+        let Some(HostUserClientEvent::Msg(HostToUserMsg::LobbyState{ lobby })) = user.next()
+        else { panic!("client did not receive server msg"); };
+        assert_eq!(lobby.id, made_lobby_id);
+    }
+    let Some(HostUserClientEvent::Ack(_)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,301 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 3u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 3u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+    let lobby_invites_config = LobbyInvitesConfig{
+            invite_duration       : Duration::from_secs(100),
+            code_len              : 8usize,
+            max_invites_per_lobby : 2usize,
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: Some(lobby_invites_config),
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn private_lobby_joined_with_invite()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+
+    // make user clients
+    let (_, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (user2_id, mut user2) = make_test_host_user_client(host_user_url.clone());
+    let (_, mut user3) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user3.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 makes a private lobby
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+    user1.request(UserToHostRequest::SetLobbyPrivate{ id: made_lobby_id, private: true });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Ack(_)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+
+
+    // user 2 can't find or join the lobby without an invite
+    user2.request(UserToHostRequest::LobbySearch(LobbySearchRequest::PageOlder{ youngest_id: u64::MAX, num: 10 }));
+    user2.request(UserToHostRequest::JoinLobby{
+            id     : made_lobby_id,
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test")
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbySearchResult(result), _)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(result.lobbies.len(), 0);
+    assert_eq!(result.total, 0);
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(HostRequestError::InviteOnly), _)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+
+
+    // user 1 makes a single-use invite
+    user1.request(UserToHostRequest::MakeLobbyInvite{ id: made_lobby_id, single_use: true });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyInvite{ id, code, .. }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(id, made_lobby_id);
+    assert_eq!(code.len(), 8);
+
+
+    // user 2 joins with the invite, then user 3 tries to reuse it
+    user2.request(UserToHostRequest::JoinLobbyWithInvite{ code: code.clone(), mcolor: BasicLobbyMemberType::Player.into() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+    user3.request(UserToHostRequest::JoinLobbyWithInvite{ code, mcolor: BasicLobbyMemberType::Player.into() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(lobby.id, made_lobby_id);
+    assert!(lobby.members.contains_key(&user2_id));
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(HostRequestError::InvalidInvite), _)) = user3.next()
+    else { panic!("client did not receive server msg"); };
+    while user1.next().is_some() {}
+
+
+    // user 1 makes a reusable invite then revokes it
+    user1.request(UserToHostRequest::MakeLobbyInvite{ id: made_lobby_id, single_use: false });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyInvite{ code, .. }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+
+    user1.request(UserToHostRequest::RevokeLobbyInvite{ id: made_lobby_id, code: code.clone() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Ack(_)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+
+    // - user 3 can't use the revoked invite
+    user3.request(UserToHostRequest::JoinLobbyWithInvite{ code, mcolor: BasicLobbyMemberType::Player.into() });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(HostRequestError::InvalidInvite), _)) = user3.next()
+    else { panic!("client did not receive server msg"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn lobby_invites_revoked_when_lobby_closes()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+
+    // make user clients
+    let (_, mut user1) = make_test_host_user_client(host_user_url.clone());
+    let (_, mut user2) = make_test_host_user_client(host_user_url.clone());
+    let (_, mut user3) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user2.next().unwrap() else { unimplemented!(); };
+    let HostUserClientEvent::Report(_) = user3.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 makes a lobby with an invite
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+    user1.request(UserToHostRequest::MakeLobbyInvite{ id: made_lobby_id, single_use: false });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyInvite{ .. }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(host_server.world().resource::<LobbyInvitesCache>().num_invites(), 1);
+
+
+    // user 1 leaves, which disbands the lobby
+    user1.request(UserToHostRequest::LeaveLobby{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the lobby's invites were revoked
+    assert!(host_server.world().resource::<LobbiesCache>().lobby_ref(made_lobby_id).is_none());
+    assert_eq!(host_server.world().resource::<LobbyInvitesCache>().num_invites(), 0);
+    while user1.next().is_some() {}
+
+
+    // user 1 makes another lobby with an invite, and users 2 and 3 join with it
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ lobby }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let made_lobby_id = lobby.id;
+
+    user1.request(UserToHostRequest::MakeLobbyInvite{ id: made_lobby_id, single_use: false });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyInvite{ code, .. }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+
+    let mcolor = BasicLobbyMemberType::Player.into();
+    user2.request(UserToHostRequest::JoinLobbyWithInvite{ code: code.clone(), mcolor });
+    user3.request(UserToHostRequest::JoinLobbyWithInvite{ code, mcolor });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ .. }, _)) = user2.next()
+    else { panic!("client did not receive server msg"); };
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ .. }, _)) = user3.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(host_server.world().resource::<LobbyInvitesCache>().num_invites(), 1);
+
+
+    // user 1 launches the lobby
+    user1.request(UserToHostRequest::LaunchLobbyGame{ id: made_lobby_id });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the lobby's invites were revoked
+    assert!(host_server.world().resource::<PendingLobbiesCache>().lobby_ref(made_lobby_id).is_some());
+    assert_eq!(host_server.world().resource::<LobbyInvitesCache>().num_invites(), 0);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -6,14 +6,29 @@ mod cache_pending_lobbies;
 mod cache_users;
 mod game_hub_dc_buffer;
 mod game_hub_reconnects;
+mod game_launch_queue;
 mod game_lifecycle;
+mod game_start_retries;
+mod host_server_commands;
+mod host_server_matchmaking;
+mod host_server_metrics;
+mod host_server_persistence;
+mod hub_affinity;
 mod hub_load_balancing;
 mod hub_rejects_game;
+mod hub_selection;
+mod lobby_chat;
 mod lobby_checker_rejections;
+mod lobby_data_update;
+mod lobby_invites;
+mod lobby_kick_member;
+mod lobby_ownership_transfer;
 mod ongoing_game_aborted;
 mod pending_lobby_expires;
+mod user_authentication;
 mod user_leaves_lobby;
 mod user_nacks_pending_lobby;
+mod user_rate_limits;
 mod user_reconnects;
 mod utils;
 
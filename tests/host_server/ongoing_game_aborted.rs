@@ -19,9 +19,13 @@ fn make_configs(ongoing_game_expiry: Duration) -> HostServerStartupPack
     let host_server_config = HostServerConfig{
             ticks_per_sec                   : None,  //we will manually update the host server
             ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
         };
     let lobbies_cache_config = LobbiesCacheConfig{
             max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
             lobby_checker: Box::new(BasicLobbyChecker{
                 max_lobby_players     : 2u16,
                 max_lobby_watchers    : 0u16,
@@ -45,6 +49,16 @@ fn make_configs(ongoing_game_expiry: Duration) -> HostServerStartupPack
             pending_lobbies_cache_config,
             ongoing_games_cache_config,
             game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
         }
 }
 
@@ -112,7 +126,7 @@ fn ongoing_game_expires()
 
     // - user 2 receives lobby response
     let Some(HostUserClientEvent::Response(
-            HostToUserResponse::LobbySearchResult(LobbySearchResult{ req: _, lobbies, num_younger: _, total: _ }), _
+            HostToUserResponse::LobbySearchResult(LobbySearchResult{ lobbies, .. }), _
         )) = user2.next()
     else { panic!("client did not receive server msg"); };
 
@@ -292,7 +306,7 @@ fn game_hub_disconnects()
 
     // - user 2 receives lobby response
     let Some(HostUserClientEvent::Response(
-            HostToUserResponse::LobbySearchResult(LobbySearchResult{ req: _, lobbies, num_younger: _, total: _ }), _
+            HostToUserResponse::LobbySearchResult(LobbySearchResult{ lobbies, .. }), _
         )) = user2.next()
     else { panic!("client did not receive server msg"); };
 
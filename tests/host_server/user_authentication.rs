@@ -0,0 +1,142 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::time::{Duration, SystemTime};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+const SECRET: &[u8] = b"test secret";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 2u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 2u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: Some(Box::new(HmacUserAuthenticator::new(SECRET))),
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn hmac_authenticator()
+{
+    let authenticator = HmacUserAuthenticator::new(SECRET);
+    let expires_at = SystemTime::now() + Duration::from_secs(100);
+
+    // valid token
+    let token = authenticator.make_token(1u128, 42u128, expires_at);
+    assert_eq!(authenticator.authenticate(1u128, &token), Ok(42u128));
+
+    // token issued for a different client
+    assert!(authenticator.authenticate(2u128, &token).is_err());
+
+    // token signed with a different secret
+    let bad_token = HmacUserAuthenticator::new(b"other secret".as_slice()).make_token(1u128, 42u128, expires_at);
+    assert!(authenticator.authenticate(1u128, &bad_token).is_err());
+
+    // expired token
+    let expired_token = authenticator.make_token(1u128, 42u128, SystemTime::now() - Duration::from_secs(1));
+    assert!(authenticator.authenticate(1u128, &expired_token).is_err());
+
+    // malformed token
+    assert!(authenticator.authenticate(1u128, &[]).is_err());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn users_are_authenticated_on_connect()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+
+    // make user clients
+    let authenticator = HmacUserAuthenticator::new(SECRET);
+    let expires_at = SystemTime::now() + Duration::from_secs(100);
+
+    let user1_id = gen_rand128();
+    let user1_token = authenticator.make_token(user1_id, 42u128, expires_at);
+    let (_, _user1) = make_test_host_user_client_with_auth(user1_id, host_user_url.clone(), user1_token);
+
+    // - user 2 presents user 1's token
+    let user2_id = gen_rand128();
+    let user2_token = authenticator.make_token(user1_id, 43u128, expires_at);
+    let (_, _user2) = make_test_host_user_client_with_auth(user2_id, host_user_url.clone(), user2_token);
+
+    // - user 3 has no token
+    let (user3_id, _user3) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // only user 1 is registered, with their account id
+    let users_cache = host_server.world().resource::<UsersCache>();
+    assert_eq!(users_cache.get_user_info(user1_id).and_then(|info| info.account_id()), Some(42u128));
+    assert!(!users_cache.has_user(user2_id));
+    assert!(!users_cache.has_user(user3_id));
+
+    // rejected users are disconnected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+
+    let users_cache = host_server.world().resource::<UsersCache>();
+    assert!(users_cache.has_user(user1_id));
+    assert!(!users_cache.has_user(user2_id));
+    assert!(!users_cache.has_user(user3_id));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
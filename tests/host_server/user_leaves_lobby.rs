@@ -18,9 +18,13 @@ fn make_configs(lobby_size: u16) -> HostServerStartupPack
     let host_server_config = HostServerConfig{
             ticks_per_sec                   : None,  //we will manually update the host server
             ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : None,
+            game_start_retries              : None,
         };
     let lobbies_cache_config = LobbiesCacheConfig{
             max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
             lobby_checker: Box::new(BasicLobbyChecker{
                 max_lobby_players     : lobby_size,
                 max_lobby_watchers    : 0u16,
@@ -44,6 +48,16 @@ fn make_configs(lobby_size: u16) -> HostServerStartupPack
             pending_lobbies_cache_config,
             ongoing_games_cache_config,
             game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
         }
 }
 
@@ -110,7 +124,7 @@ fn client_leaves_lobby()
 
     // - user 2 receives lobby response
     let Some(HostUserClientEvent::Response(
-            HostToUserResponse::LobbySearchResult(LobbySearchResult{ req: _, lobbies, num_younger: _, total: _ }), _
+            HostToUserResponse::LobbySearchResult(LobbySearchResult{ lobbies, .. }), _
         )) = user2.next()
     else { panic!("client did not receive server msg"); };
 
@@ -219,7 +233,7 @@ fn client_leaves_lobby()
 
     // - user 1 receives lobby response
     let Some(HostUserClientEvent::Response(
-            HostToUserResponse::LobbySearchResult(LobbySearchResult{ req: _, lobbies, num_younger: _, total: _ }), _
+            HostToUserResponse::LobbySearchResult(LobbySearchResult{ lobbies, .. }), _
         )) = user1.next()
     else { panic!("client did not receive server msg"); };
     assert_eq!(lobbies.len(), 0);
@@ -300,7 +314,7 @@ fn client_leaves_pending_lobby()
 
     // - user 2 receives lobby response
     let Some(HostUserClientEvent::Response(
-            HostToUserResponse::LobbySearchResult(LobbySearchResult{ req: _, lobbies, num_younger: _, total: _ }), _
+            HostToUserResponse::LobbySearchResult(LobbySearchResult{ lobbies, .. }), _
         )) = user2.next()
     else { panic!("client did not receive server msg"); };
 
@@ -448,8 +462,8 @@ fn client_leaves_pending_lobby()
     host_server.update();
     std::thread::sleep(Duration::from_millis(15));
 
-    // - user 2 receives rejection for leave lobby request
-    let Some(HostUserClientEvent::Reject(_request_id)) = user2.next()
+    // - user 2 receives an error for leave lobby request
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(HostRequestError::Rejected), _)) = user2.next()
     else { panic!("client did not receive server msg"); };
 
     // - users 1, 2 receive nothing (leave lobby failed because pending lobby is fully acked)
@@ -0,0 +1,216 @@
+//local shortcuts
+use crate::host_server::*;
+use crate::test_helpers::*;
+use bevy_girk_backend_public::*;
+use bevy_girk_host_server::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_configs() -> HostServerStartupPack
+{
+    // configs
+    let host_server_config = HostServerConfig{
+            ticks_per_sec                   : None,  //we will manually update the host server
+            ongoing_game_purge_period_ticks : 1u64,
+            user_rate_limits                : Some(UserRateLimitConfig{
+                search           : TokenBucketConfig{ capacity: 2u32, refill_period: Duration::from_secs(100) },
+                lobby            : TokenBucketConfig{ capacity: 10u32, refill_period: Duration::from_secs(1) },
+                game             : TokenBucketConfig{ capacity: 10u32, refill_period: Duration::from_secs(1) },
+                message          : TokenBucketConfig{ capacity: 10u32, refill_period: Duration::from_secs(1) },
+                max_violations   : 1u32,
+                violation_window : Duration::from_secs(100),
+            }),
+            game_start_retries              : None,
+        };
+    let lobbies_cache_config = LobbiesCacheConfig{
+            max_request_size      : 10u16,
+            lobby_searcher        : None,
+            owner_succession      : OwnerSuccession::Disband,
+            lobby_checker: Box::new(BasicLobbyChecker{
+                max_lobby_players     : 2u16,
+                max_lobby_watchers    : 0u16,
+                min_players_to_launch : 2u16,
+            })
+        };
+    let pending_lobbies_cache_config = PendingLobbiesConfig{
+            ack_timeout  : Duration::from_secs(10),
+            start_buffer : Duration::from_secs(3),
+        };
+    let ongoing_games_cache_config = OngoingGamesCacheConfig{
+            expiry_duration: Duration::from_secs(100),
+        };
+    let game_hub_disconnect_buffer_config = GameHubDisconnectBufferConfig{
+            expiry_duration: Duration::from_secs(0),
+        };
+
+    HostServerStartupPack{
+            host_server_config,
+            lobbies_cache_config,
+            pending_lobbies_cache_config,
+            ongoing_games_cache_config,
+            game_hub_disconnect_buffer_config,
+            lobby_chat_config: None,
+            lobby_invites_config: None,
+            matchmaking_queue_config: None,
+            game_launch_queue_config: None,
+            persistence_config: None,
+            metrics_config: None,
+            user_authenticator: None,
+            hub_selector: None,
+            hub_affinity_checker: None,
+            game_cost_estimator: None,
+        }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn user_requests_are_rate_limited()
+{
+    // make a host server
+    let (mut host_server, _, host_user_url) = make_test_host_server(make_configs());
+
+    // make user clients
+    let (user1_id, mut user1) = make_test_host_user_client(host_user_url);
+
+    // clients connected
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+
+
+    // user 1 sends 3 lobby searches
+    for _ in 0..3
+    {
+        user1.request(UserToHostRequest::LobbySearch(LobbySearchRequest::PageOlder{ youngest_id: 0u64, num: 10u16 }));
+    }
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the third search is rejected
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbySearchResult(_), _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbySearchResult(_), _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+    let Some(HostUserClientEvent::Response(HostToUserResponse::Error(HostRequestError::RateLimited), _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+
+
+    // user 1 makes a lobby
+    user1.request(UserToHostRequest::MakeLobby{
+            mcolor : BasicLobbyMemberType::Player.into(),
+            pwd    : String::from("test"),
+            data   : Vec::default()
+        });
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - other request categories are not affected
+    let Some(HostUserClientEvent::Response(HostToUserResponse::LobbyJoin{ .. }, _)) = user1.next()
+    else { panic!("client did not receive server msg"); };
+
+
+    // user 1 sends another lobby search
+    user1.request(UserToHostRequest::LobbySearch(LobbySearchRequest::PageOlder{ youngest_id: 0u64, num: 10u16 }));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - user 1 is disconnected for exceeding max violations
+    let mut closed = false;
+    while let Some(event) = user1.next()
+    {
+        if let HostUserClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_)) = event { closed = true; }
+    }
+    assert!(closed);
+    assert!(!host_server.world().resource::<UsersCache>().has_user(user1_id));
+
+
+    // user 1 reconnects
+    let (_, mut user1) = make_test_host_user_client_with_id(user1_id, host_server.world().resource::<HostUserServer>().url());
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+
+    // user 1 sends another lobby search
+    user1.request(UserToHostRequest::LobbySearch(LobbySearchRequest::PageOlder{ youngest_id: 0u64, num: 10u16 }));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - user 1's rate limits survived the reconnect, so they are disconnected again instead of getting results
+    let mut closed = false;
+    while let Some(event) = user1.next()
+    {
+        if let HostUserClientEvent::Response(HostToUserResponse::LobbySearchResult(_), _) = event
+        { panic!("rate limits were reset by reconnecting"); }
+        if let HostUserClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_)) = event { closed = true; }
+    }
+    assert!(closed);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn pending_lobby_acks_are_not_rate_limited()
+{
+    assert_eq!(UserRequestCategory::from_msg(&UserToHostMsg::AckPendingLobby{ id: 0u64 }), None);
+    assert_eq!(UserRequestCategory::from_msg(&UserToHostMsg::NackPendingLobby{ id: 0u64 }), None);
+    assert_eq!(
+        UserRequestCategory::from_msg(&UserToHostMsg::LobbyChat{ id: 0u64, text: String::default() }),
+        Some(UserRequestCategory::Message)
+    );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn unregistered_users_are_not_rate_limited()
+{
+    // make a host server that rejects users without auth tokens
+    let mut configs = make_configs();
+    configs.user_authenticator = Some(Box::new(HmacUserAuthenticator::new(b"test secret".as_slice())));
+    let (mut host_server, _, host_user_url) = make_test_host_server(configs);
+
+    // make user client without an auth token
+    let (user1_id, mut user1) = make_test_host_user_client(host_user_url);
+    std::thread::sleep(Duration::from_millis(15));
+
+    let HostUserClientEvent::Report(_) = user1.next().unwrap() else { unimplemented!(); };
+
+    // user 1 sends a lobby search before the server rejects their connection
+    user1.request(UserToHostRequest::LobbySearch(LobbySearchRequest::PageOlder{ youngest_id: 0u64, num: 10u16 }));
+    std::thread::sleep(Duration::from_millis(15));
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - the request is ignored
+    assert!(!host_server.world().resource::<UsersCache>().has_user(user1_id));
+    assert!(!host_server.world().resource::<UserRateLimitsCache>().has_user(user1_id));
+
+    // user 1 is disconnected
+    host_server.update();
+    std::thread::sleep(Duration::from_millis(15));
+
+    // - no rate limits are left behind
+    assert!(!host_server.world().resource::<UserRateLimitsCache>().has_user(user1_id));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
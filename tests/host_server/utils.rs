@@ -15,6 +15,19 @@ use std::time::Duration;
 
 pub fn make_test_host_server(configs: HostServerStartupPack) -> (App, url::Url, url::Url)
 {
+    let (_, host_server, host_hub_url, host_user_url) = make_test_host_server_with_commands(configs);
+    (host_server, host_hub_url, host_user_url)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub fn make_test_host_server_with_commands(
+    configs: HostServerStartupPack
+) -> (Sender<HostServerCommand>, App, url::Url, url::Url)
+{
+    // command channel
+    let (command_sender, command_receiver) = new_channel::<HostServerCommand>();
+
     // host-hub server
     let host_hub_server = host_hub_server_factory().new_server(
             enfync::builtin::Handle::default(),
@@ -52,7 +65,8 @@ pub fn make_test_host_server(configs: HostServerStartupPack) -> (App, url::Url,
     let host_user_url = host_user_server.url();
 
     (
-        make_host_server(configs, host_hub_server, host_user_server),
+        command_sender,
+        make_host_server(configs, command_receiver, host_hub_server, host_user_server),
         host_hub_url,
         host_user_url,
     )
@@ -61,6 +75,17 @@ pub fn make_test_host_server(configs: HostServerStartupPack) -> (App, url::Url,
 //-------------------------------------------------------------------------------------------------------------------
 
 pub fn make_test_host_hub_client_with_id(id: u128, hub_server_url: url::Url) -> (u128, HostHubClient)
+{
+    make_test_host_hub_client_with_metadata(id, hub_server_url, GameHubMetadata::default())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub fn make_test_host_hub_client_with_metadata(
+    id             : u128,
+    hub_server_url : url::Url,
+    metadata       : GameHubMetadata,
+) -> (u128, HostHubClient)
 {
     let auth = bevy_simplenet::AuthRequest::None{ client_id: id };
 
@@ -78,7 +103,7 @@ pub fn make_test_host_hub_client_with_id(id: u128, hub_server_url: url::Url) ->
                     reconnect_on_server_close: true,
                     ..Default::default()
                 },
-                ()
+                metadata
             )
     )
 }
@@ -93,6 +118,17 @@ pub fn make_test_host_hub_client(hub_server_url: url::Url) -> (u128, HostHubClie
 //-------------------------------------------------------------------------------------------------------------------
 
 pub fn make_test_host_user_client_with_id(id: u128, user_server_url: url::Url) -> (u128, HostUserClient)
+{
+    make_test_host_user_client_with_auth(id, user_server_url, Vec::default())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+pub fn make_test_host_user_client_with_auth(
+    id              : u128,
+    user_server_url : url::Url,
+    user_auth       : Vec<u8>,
+) -> (u128, HostUserClient)
 {
     let auth = bevy_simplenet::AuthRequest::None{ client_id: id };
 
@@ -103,7 +139,7 @@ pub fn make_test_host_user_client_with_id(id: u128, user_server_url: url::Url) -
                 user_server_url,
                 auth,
                 bevy_simplenet::ClientConfig::default(),
-                HostUserConnectMsg::new()
+                HostUserConnectMsg::with_auth(user_auth)
             )
     )
 }
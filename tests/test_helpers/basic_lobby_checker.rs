@@ -104,34 +104,35 @@ impl LobbyChecker for BasicLobbyChecker
         member_id   : u128,
         member_data : LobbyMemberData,
         password    : &String,
-    ) -> bool
+    ) -> Result<(), HostRequestError>
     {
         // check if in lobby already
-        if lobby.has_member(member_id) { return false; }
+        if lobby.has_member(member_id) { return Err(HostRequestError::Rejected); }
 
         // check password
-        if lobby.get_password() != password { return false; }
+        if lobby.get_password() != password { return Err(HostRequestError::WrongPassword); }
 
         // get member type
-        let Ok(member_type) = BasicLobbyMemberType::try_from(member_data.color) else { return false; };
+        let Ok(member_type) = BasicLobbyMemberType::try_from(member_data.color)
+        else { return Err(HostRequestError::Rejected); };
 
         // count current players and watchers
-        let Some((num_players, num_watchers)) = count_members(lobby) else { return false; };
+        let Some((num_players, num_watchers)) = count_members(lobby) else { return Err(HostRequestError::Rejected); };
 
         // check if the member's type has exceeded lobby capacity
         match member_type
         {
             BasicLobbyMemberType::Player =>
             {
-                if num_players >= self.max_lobby_players as usize { return false; }
+                if num_players >= self.max_lobby_players as usize { return Err(HostRequestError::LobbyFull); }
             }
             BasicLobbyMemberType::Watcher =>
             {
-                if num_watchers >= self.max_lobby_watchers as usize { return false; }
+                if num_watchers >= self.max_lobby_watchers as usize { return Err(HostRequestError::LobbyFull); }
             }
         }
 
-        true
+        Ok(())
     }
 
     /// Check if a lobby is launchable.
@@ -0,0 +1,172 @@
+//local shortcuts
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use enfync::AdoptOrDefault;
+
+//standard shortcuts
+use std::path::PathBuf;
+use std::process::Stdio;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_log_dir() -> PathBuf
+{
+    std::env::temp_dir().join(format!("bevy_girk_child_process_log_{}", gen_rand128()))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn child_process_log_tail()
+{
+    // in-memory log
+    let log = ChildProcessLog::new(ChildProcessLogConfig{ tail_lines: 2, ..Default::default() }, "test");
+    assert_eq!(log.path(), None);
+
+    log.write_line("a\n");
+    log.write_line("b");
+    log.write_line("c");
+    assert_eq!(log.tail(), vec![String::from("b"), String::from("c")]);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn child_process_log_rotation()
+{
+    let log_dir = make_log_dir();
+    let config = ChildProcessLogConfig{
+            log_dir            : Some(log_dir.clone()),
+            max_file_size      : 8,
+            max_rotated_files  : 2,
+            tail_lines         : 10,
+            forward_to_tracing : false,
+        };
+    let log = ChildProcessLog::new(config, "test");
+    assert_eq!(log.path(), Some(log_dir.join("test.log")));
+
+    // each file holds two 4-byte lines
+    for line in ["aaa", "bbb", "ccc", "ddd", "eee", "fff", "ggg"]
+    {
+        log.write_line(line);
+    }
+
+    // - the oldest file was discarded
+    let read = |name: &str| std::fs::read_to_string(log_dir.join(name)).unwrap();
+    assert_eq!(read("test.log"), "ggg\n");
+    assert_eq!(read("test.log.1"), "eee\nfff\n");
+    assert_eq!(read("test.log.2"), "ccc\nddd\n");
+    assert!(!log_dir.join("test.log.3").exists());
+
+    // - the tail is unaffected by rotation
+    assert_eq!(log.tail().len(), 7);
+
+    let _ = std::fs::remove_dir_all(log_dir);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn child_process_log_rename()
+{
+    let log_dir = make_log_dir();
+    let config = ChildProcessLogConfig{
+            log_dir            : Some(log_dir.clone()),
+            max_file_size      : 8,
+            max_rotated_files  : 2,
+            tail_lines         : 10,
+            forward_to_tracing : false,
+        };
+    let log = ChildProcessLog::new(config, "idle");
+    for line in ["aaa", "bbb", "ccc"]
+    {
+        log.write_line(line);
+    }
+
+    // rename the log
+    log.rename("game");
+    assert_eq!(log.path(), Some(log_dir.join("game.log")));
+    assert!(!log_dir.join("idle.log").exists());
+    assert!(!log_dir.join("idle.log.1").exists());
+
+    // - new lines are appended to the moved file
+    log.write_line("ddd");
+    let read = |name: &str| std::fs::read_to_string(log_dir.join(name)).unwrap();
+    assert_eq!(read("game.log"), "ccc\nddd\n");
+    assert_eq!(read("game.log.1"), "aaa\nbbb\n");
+
+    let _ = std::fs::remove_dir_all(log_dir);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn child_process_log_captures_output()
+{
+    let log_dir = make_log_dir();
+    let log = ChildProcessLog::new(
+            ChildProcessLogConfig{ log_dir: Some(log_dir.clone()), ..Default::default() },
+            "crashed"
+        );
+
+    // run a process that writes a report, some unexpected output (including invalid UTF-8), and then crashes
+    let spawner = enfync::builtin::native::TokioHandle::adopt_or_default();
+    let (stdin_sender, stdin_receiver) = new_io_channel::<u32>();
+    let (error_sender, mut error_receiver) = new_io_channel::<Vec<String>>();
+    let (report_sender, mut report_receiver) = new_io_channel::<u32>();
+    let error_log = log.clone();
+    let (_process_handle, mut stdout_handle) = spawner.0.clone().block_on(
+        async move
+        {
+            let script = concat!(
+                    "echo 42; echo not a report; ",
+                    r"printf 'bad \377 byte\n' >&2; echo panicked at oops >&2; exit 101"
+                );
+            let child_process = tokio::process::Command::new("sh")
+                .args(["-c", script])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("spawning sh should succeed");
+
+            manage_child_process(
+                spawner,
+                0u64,
+                ChildProcessProtocol::Json,
+                child_process,
+                log,
+                stdin_receiver,
+                new_io_channel::<()>().1,
+                move |report: u32| -> Option<bool> { let _ = report_sender.send(report); None },
+                move || { let _ = error_sender.send(error_log.tail()); }
+            )
+        }
+    );
+    let result = enfync::builtin::native::TokioHandle::adopt_or_default().0.block_on(stdout_handle.extract());
+    drop(stdin_sender);
+
+    // - the process died unexpectedly
+    assert_eq!(result, Ok(false));
+    assert_eq!(report_receiver.try_recv(), Some(42));
+
+    // - the error callback saw all captured output
+    let mut tail = error_receiver.try_recv().expect("error callback should run");
+    tail.sort();
+    assert_eq!(
+        tail,
+        vec![String::from("bad \u{FFFD} byte"), String::from("not a report"), String::from("panicked at oops")]
+    );
+
+    // - captured output was written to the log file
+    let contents = std::fs::read_to_string(log_dir.join("crashed.log")).unwrap();
+    assert!(contents.contains("not a report\n"));
+    assert!(contents.contains("panicked at oops\n"));
+
+    let _ = std::fs::remove_dir_all(log_dir);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
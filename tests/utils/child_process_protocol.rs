@@ -0,0 +1,102 @@
+//local shortcuts
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+use enfync::AdoptOrDefault;
+
+//standard shortcuts
+use std::process::Stdio;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn child_process_protocol_names()
+{
+    for protocol in [ChildProcessProtocol::Bincode, ChildProcessProtocol::Json]
+    {
+        assert_eq!(ChildProcessProtocol::parse(protocol.as_str()), Ok(protocol));
+    }
+    assert!(ChildProcessProtocol::parse("xml").is_err());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn child_process_protocol_round_trip()
+{
+    for protocol in [ChildProcessProtocol::Bincode, ChildProcessProtocol::Json]
+    {
+        // write several messages back-to-back
+        let mut buffer = Vec::<u8>::default();
+        protocol.write(&mut buffer, &String::from("hello\nworld")).unwrap();
+        protocol.write(&mut buffer, &String::from("")).unwrap();
+
+        // read them back
+        let mut reader = buffer.as_slice();
+        assert_eq!(protocol.read::<String>(&mut reader).unwrap(), Some(String::from("hello\nworld")));
+        assert_eq!(protocol.read::<String>(&mut reader).unwrap(), Some(String::from("")));
+        assert_eq!(protocol.read::<String>(&mut reader).unwrap(), None);
+    }
+
+    // garbage is rejected
+    let mut reader = "not a frame".as_bytes();
+    assert!(ChildProcessProtocol::Bincode.read::<String>(&mut reader).is_err());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn child_process_protocol_skips_stray_output()
+{
+    // write a bincode frame to a file so the child process can print it between lines of stray text
+    let frame_path = std::env::temp_dir().join(format!("bevy_girk_child_process_protocol_{}", gen_rand128()));
+    std::fs::write(&frame_path, ChildProcessProtocol::Bincode.encode(&42u32).unwrap()).unwrap();
+    let script = format!("echo stray text; cat '{}'; echo more stray text", frame_path.display());
+
+    // run a process that writes a framed report surrounded by stray output
+    let log = ChildProcessLog::new(ChildProcessLogConfig::default(), "test");
+    let spawner = enfync::builtin::native::TokioHandle::adopt_or_default();
+    let (stdin_sender, stdin_receiver) = new_io_channel::<u32>();
+    let (report_sender, mut report_receiver) = new_io_channel::<u32>();
+    let stdout_log = log.clone();
+    let (_process_handle, mut stdout_handle) = spawner.0.clone().block_on(
+        async move
+        {
+            let child_process = tokio::process::Command::new("sh")
+                .args(["-c", &script])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("spawning sh should succeed");
+
+            manage_child_process(
+                spawner,
+                0u64,
+                ChildProcessProtocol::Bincode,
+                child_process,
+                stdout_log,
+                stdin_receiver,
+                new_io_channel::<()>().1,
+                move |report: u32| -> Option<bool> { let _ = report_sender.send(report); None },
+                move || ()
+            )
+        }
+    );
+    let _ = enfync::builtin::native::TokioHandle::adopt_or_default().0.block_on(stdout_handle.extract());
+    drop(stdin_sender);
+
+    // - the report was decoded
+    assert_eq!(report_receiver.try_recv(), Some(42));
+    assert_eq!(report_receiver.try_recv(), None);
+
+    // - stray output was captured in the log
+    let mut tail = log.tail();
+    tail.sort();
+    assert_eq!(tail, vec![String::from("more stray text"), String::from("stray text")]);
+
+    let _ = std::fs::remove_file(&frame_path);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
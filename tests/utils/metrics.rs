@@ -0,0 +1,117 @@
+//local shortcuts
+use bevy_girk_utils::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn read_response(mut stream: TcpStream) -> String
+{
+    let mut response = String::default();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn make_exporter() -> (MetricsExporter, SocketAddr)
+{
+    let exporter = MetricsExporter::new("127.0.0.1:0".parse().unwrap()).unwrap();
+    let mut writer = PrometheusWriter::default();
+    writer.gauge("test_gauge", "A test gauge.", 1.0);
+    exporter.publish(writer.finish());
+    let addr = exporter.local_addr();
+
+    (exporter, addr)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn metrics_request_split_across_writes()
+{
+    let (_exporter, addr) = make_exporter();
+
+    // send the request in pieces
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(b"GET /metr").unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+    stream.write_all(b"ics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+
+    // - the full request was read
+    let response = read_response(stream);
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("test_gauge 1\n"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn metrics_slow_client_doesnt_block_scrapes()
+{
+    let (_exporter, addr) = make_exporter();
+
+    // a client connects but doesn't send a request
+    let _idle_stream = TcpStream::connect(addr).unwrap();
+    std::thread::sleep(Duration::from_millis(100));
+
+    // - other clients are still served promptly
+    let start = Instant::now();
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(b"GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+    let response = read_response(stream);
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(start.elapsed() < Duration::from_millis(500));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn metrics_connections_are_capped()
+{
+    let (_exporter, addr) = make_exporter();
+
+    // many clients connect but don't send requests
+    let idle_streams: Vec<TcpStream> = (0..8).map(|_| TcpStream::connect(addr).unwrap()).collect();
+    std::thread::sleep(Duration::from_millis(100));
+
+    // - new clients are turned away
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let _ = stream.write_all(b"GET /metrics HTTP/1.1\r\n\r\n");
+    let mut response = String::default();
+    let _ = stream.read_to_string(&mut response);
+    assert!(response.is_empty());
+
+    // idle clients disconnect
+    std::mem::drop(idle_streams);
+    std::thread::sleep(Duration::from_millis(100));
+
+    // - new clients are served again
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(b"GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+    let response = read_response(stream);
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn metrics_listener_stops_on_drop()
+{
+    let (exporter, addr) = make_exporter();
+
+    // drop the exporter
+    std::mem::drop(exporter);
+    std::thread::sleep(Duration::from_millis(100));
+
+    // - the listener is closed
+    assert!(TcpStream::connect(addr).is_err());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,2 +1,5 @@
 //module tree
+mod child_process_log;
+mod child_process_protocol;
+mod metrics;
 mod rand64;